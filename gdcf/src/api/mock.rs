@@ -0,0 +1,195 @@
+//! Module containing an [`ApiClient`] that answers requests with canned responses
+//!
+//! The [`MockClient`] never accesses the network. Every request it should be able to process has to
+//! be registered beforehand, together with the response it should produce. Additionally, it keeps
+//! track of how often each request was made, so that tests can verify that GDCF issues exactly the
+//! requests they expect.
+
+use crate::{
+    api::{
+        client::{MakeRequest, Response},
+        request::Request,
+        ApiClient,
+    },
+    error::ApiError,
+    Secondary,
+};
+use failure::Fail;
+use futures::future::{self, FutureResult};
+use log::{debug, warn};
+use std::{
+    any::{Any, TypeId},
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+#[derive(Debug, Fail, Clone, PartialEq, Eq)]
+pub enum MockError {
+    /// The request resulted in no data, just like the boomlings servers responding with `-1`
+    #[fail(display = "The request resulted in no data")]
+    NoResult,
+
+    /// A canned error other than [`MockError::NoResult`]
+    #[fail(display = "{}", _0)]
+    Custom(String),
+
+    /// The [`MockClient`] was asked to process a request no response was registered for
+    #[fail(display = "No response registered for request {}", _0)]
+    Unexpected(String),
+}
+
+impl ApiError for MockError {
+    fn is_no_result(&self) -> bool {
+        match self {
+            MockError::NoResult => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Canned<T> {
+    Exact(T),
+    More(T, Vec<Secondary>),
+    Error(MockError),
+}
+
+impl<T: Clone> Canned<T> {
+    fn produce(&self) -> Result<Response<T>, MockError> {
+        match self {
+            Canned::Exact(result) => Ok(Response::Exact(result.clone())),
+            Canned::More(result, secondaries) => Ok(Response::More(result.clone(), secondaries.clone())),
+            Canned::Error(error) => Err(error.clone()),
+        }
+    }
+}
+
+/// Identifies a request by its type and its hash
+type RequestKey = (TypeId, u64);
+
+fn request_key<R: Request + Hash>(request: &R) -> RequestKey {
+    let mut state = DefaultHasher::new();
+
+    request.hash(&mut state);
+
+    (TypeId::of::<R>(), state.finish())
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    responses: HashMap<RequestKey, Box<dyn Any + Send>>,
+    calls: HashMap<RequestKey, usize>,
+}
+
+/// An [`ApiClient`] that answers requests with previously registered responses
+///
+/// Clones of a [`MockClient`] share their registered responses and call counts, so a clone can be
+/// handed to [`Gdcf`](crate::Gdcf) while the original is kept around to inspect the calls made.
+#[derive(Debug, Clone, Default)]
+pub struct MockClient {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockClient {
+    pub fn new() -> Self {
+        MockClient::default()
+    }
+
+    fn register<R>(&self, request: &R, canned: Canned<R::Result>)
+    where
+        R: Request + Hash,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .insert(request_key(request), Box::new(canned));
+    }
+
+    /// Makes this client answer the given request with [`Response::Exact`]
+    pub fn respond<R>(&self, request: &R, result: R::Result)
+    where
+        R: Request + Hash,
+    {
+        self.register(request, Canned::Exact(result))
+    }
+
+    /// Makes this client answer the given request with [`Response::More`]
+    pub fn respond_more<R>(&self, request: &R, result: R::Result, secondaries: Vec<Secondary>)
+    where
+        R: Request + Hash,
+    {
+        self.register(request, Canned::More(result, secondaries))
+    }
+
+    /// Makes this client fail the given request with the given error
+    pub fn fail<R>(&self, request: &R, error: MockError)
+    where
+        R: Request + Hash,
+    {
+        self.register(request, Canned::<R::Result>::Error(error))
+    }
+
+    /// Returns how often the given request has been made
+    pub fn calls<R>(&self, request: &R) -> usize
+    where
+        R: Request + Hash,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .get(&request_key(request))
+            .cloned()
+            .unwrap_or(0)
+    }
+
+    /// Returns how many requests have been made in total
+    pub fn total_calls(&self) -> usize {
+        self.state.lock().unwrap().calls.values().sum()
+    }
+
+    /// Resets all call counts to zero, leaving the registered responses untouched
+    pub fn reset_calls(&self) {
+        self.state.lock().unwrap().calls.clear()
+    }
+}
+
+impl ApiClient for MockClient {
+    type Err = MockError;
+}
+
+impl<R> MakeRequest<R> for MockClient
+where
+    R: Request + Hash,
+    R::Result: Clone,
+{
+    type Future = FutureResult<Response<R::Result>, MockError>;
+
+    fn make(&self, request: &R) -> Self::Future {
+        let key = request_key(request);
+        let mut state = self.state.lock().unwrap();
+
+        *state.calls.entry(key).or_insert(0) += 1;
+
+        let result = match state
+            .responses
+            .get(&key)
+            .and_then(|canned| canned.downcast_ref::<Canned<R::Result>>())
+        {
+            Some(canned) => {
+                debug!("Answering {:?} with canned response", request);
+
+                canned.produce()
+            },
+            None => {
+                warn!("No response registered for {:?}", request);
+
+                Err(MockError::Unexpected(format!("{:?}", request)))
+            },
+        };
+
+        future::result(result)
+    }
+}
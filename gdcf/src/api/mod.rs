@@ -3,6 +3,7 @@
 //! Particularly, this contains all the structs modelling requests to the Geometry Dash API
 
 pub mod client;
+pub mod mock;
 pub mod request;
 
 pub use self::client::ApiClient;
@@ -0,0 +1,133 @@
+//! Module containing request definitions and types related to Geometry Dash accounts
+
+use crate::api::request::{BaseRequest, Request, GD_21};
+use gdcf_model::user::Account;
+use std::{
+    fmt::{Debug, Display, Error, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// The secret String the servers use to identify valid clients on account related endpoints
+pub const ACCOUNT_SECRET: &str = "Wmfv3899gc9";
+
+/// The credentials of a Geometry Dash account, used by api clients to make requests on behalf of
+/// that account
+///
+/// Can be obtained from a successful [`LoginRequest`] via [`LoginRequest::credentials`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    /// The account's unique account ID
+    ///
+    /// ## GD Internals:
+    /// This field is called `accountID` in the boomlings API
+    pub account_id: u64,
+
+    /// The name of the account
+    ///
+    /// ## GD Internals:
+    /// This field is called `userName` in the boomlings API. Some endpoints also include it in the
+    /// `chk` checksum of their requests
+    pub user_name: String,
+
+    /// The account's password, in plain text.
+    ///
+    /// ## GD Internals:
+    /// The password is never sent in plain text, it is always GJP-encoded (XOR-encrypted with key
+    /// `37526` and then base64-encoded). The encoded password is called `gjp` in the boomlings API
+    pub password: String,
+}
+
+impl Credentials {
+    pub fn new(account_id: u64, user_name: impl Into<String>, password: impl Into<String>) -> Self {
+        Credentials {
+            account_id,
+            user_name: user_name.into(),
+            password: password.into(),
+        }
+    }
+}
+
+/// Manual `Debug` impl that doesn't leak the password into logs
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.debug_struct("Credentials")
+            .field("account_id", &self.account_id)
+            .field("user_name", &self.user_name)
+            .finish()
+    }
+}
+
+impl Display for Credentials {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "Credentials({}, {})", self.account_id, self.user_name)
+    }
+}
+
+/// Struct modelled after a request to `loginGJAccount.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to log into an account, which retrieves the
+/// account's account ID and user ID. Since logins are never cached, this request should be made
+/// directly through an api client instead of through [`Gdcf`](crate::Gdcf).
+#[derive(Clone)]
+pub struct LoginRequest {
+    /// The base request data. Note that this endpoint requires [`ACCOUNT_SECRET`] as its secret.
+    pub base: BaseRequest,
+
+    /// The name of the account to log into
+    ///
+    /// ## GD Internals:
+    /// This field is called `userName` in the boomlings API
+    pub user_name: String,
+
+    /// The account's password, in plain text
+    ///
+    /// ## GD Internals:
+    /// This field is called `password` in the boomlings API. It's the only endpoint that takes
+    /// the plain text password instead of the GJP-encoded one.
+    pub password: String,
+}
+
+impl LoginRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub fn new(user_name: impl Into<String>, password: impl Into<String>) -> Self {
+        LoginRequest {
+            base: BaseRequest::new(GD_21.game_version, GD_21.binary_version, ACCOUNT_SECRET),
+            user_name: user_name.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Constructs the [`Credentials`] of the account this request successfully logged into
+    pub fn credentials(self, account: Account) -> Credentials {
+        Credentials::new(account.account_id, self.user_name, self.password)
+    }
+}
+
+/// Manual `Debug` impl that doesn't leak the password into logs
+impl Debug for LoginRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.debug_struct("LoginRequest")
+            .field("base", &self.base)
+            .field("user_name", &self.user_name)
+            .finish()
+    }
+}
+
+/// Manual `Hash` impl that doesn't hash `base`.
+impl Hash for LoginRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.user_name.hash(state);
+        self.password.hash(state);
+    }
+}
+
+impl Display for LoginRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "LoginRequest({})", self.user_name)
+    }
+}
+
+impl Request for LoginRequest {
+    type Result = Account;
+}
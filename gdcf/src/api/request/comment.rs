@@ -188,3 +188,122 @@ impl PaginatableRequest for ProfileCommentsRequest {
         self.page += 1;
     }
 }
+
+/// Struct modelled after a request to `uploadGJComment21.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to post a comment on a level. It can only be
+/// made by an authenticated client, as the comment is posted from the client's account. Once the
+/// comment has been posted, all cached comment pages of the level are invalidated.
+#[derive(Debug, Clone)]
+pub struct UploadLevelCommentRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The id of the level to post the comment on
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    pub level_id: u64,
+
+    /// The content of the comment
+    ///
+    /// ## GD Internals:
+    /// This field is called `comment` in the boomlings API. The content is base64 encoded
+    pub content: String,
+
+    /// The progress on the level to display next to the comment, in percent
+    ///
+    /// ## GD Internals:
+    /// This field is called `percent` in the boomlings API
+    pub progress: u8,
+}
+
+impl UploadLevelCommentRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(progress: u8);
+
+    pub fn new(level_id: u64, content: impl Into<String>) -> Self {
+        UploadLevelCommentRequest {
+            base: GD_21,
+            level_id,
+            content: content.into(),
+            progress: 0,
+        }
+    }
+}
+
+impl Display for UploadLevelCommentRequest {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "UploadLevelCommentRequest({}, {})", self.level_id, self.content)
+    }
+}
+
+impl Hash for UploadLevelCommentRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.level_id.hash(state);
+        self.content.hash(state);
+        self.progress.hash(state);
+    }
+}
+
+/// The result is the ID of the newly posted comment
+impl Request for UploadLevelCommentRequest {
+    type Result = u64;
+}
+
+/// Struct modelled after a request to `uploadGJAccComment20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to post a comment on the profile of the account
+/// making the request. It can only be made by an authenticated client. Once the comment has been
+/// posted, all cached comment pages of the profile are invalidated.
+#[derive(Debug, Clone)]
+pub struct UploadProfileCommentRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The account id of the account the client making the request is authenticated as.
+    ///
+    /// Used to determine which cached comment pages to invalidate.
+    ///
+    /// ## GD Internals:
+    /// This value is sent as the `accountID` of the client's credentials, it is not part of the
+    /// request itself
+    pub account_id: u64,
+
+    /// The content of the comment
+    ///
+    /// ## GD Internals:
+    /// This field is called `comment` in the boomlings API. The content is base64 encoded
+    pub content: String,
+}
+
+impl UploadProfileCommentRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub fn new(account_id: u64, content: impl Into<String>) -> Self {
+        UploadProfileCommentRequest {
+            base: GD_21,
+            account_id,
+            content: content.into(),
+        }
+    }
+}
+
+impl Display for UploadProfileCommentRequest {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "UploadProfileCommentRequest({}, {})", self.account_id, self.content)
+    }
+}
+
+impl Hash for UploadProfileCommentRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.account_id.hash(state);
+        self.content.hash(state);
+    }
+}
+
+/// The result is the ID of the newly posted comment
+impl Request for UploadProfileCommentRequest {
+    type Result = u64;
+}
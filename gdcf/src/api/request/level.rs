@@ -1,7 +1,7 @@
-//! Module containing request definitions for retrieving levels
+//! Module containing request definitions for retrieving and uploading levels
 
 use crate::api::request::{BaseRequest, PaginatableRequest, Request, GD_21};
-use gdcf_model::level::{DemonRating, Level, LevelLength, LevelRating, PartialLevel};
+use gdcf_model::level::{DailyLevel, DemonRating, Gauntlet, Level, LevelLength, LevelRating, MapPack, PartialLevel};
 use std::{
     fmt::{Display, Error, Formatter},
     hash::{Hash, Hasher},
@@ -46,6 +46,112 @@ impl Hash for LevelRequest {
     }
 }
 
+/// Struct modelled after a request to `downloadGJLevel22.php` for the current daily level or
+/// weekly demon.
+///
+/// In the Geometry Dash API, these are retrieved by requesting the special level IDs `-1` and `-2`
+/// respectively. Since the level behind these IDs changes whenever the servers replace the daily
+/// level, they are modelled as their own request instead of as a [`LevelRequest`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpecialLevelRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// Whether to retrieve the weekly demon instead of the daily level
+    ///
+    /// ## GD Internals:
+    /// This field is sent as the `levelID` field in the boomlings API, which is `-1` for the daily
+    /// level and `-2` for the weekly demon
+    pub weekly: bool,
+
+    /// Some weird field the Geometry Dash Client sends along
+    ///
+    /// ## GD Internals:
+    /// This value needs to be converted to an integer for the boomlings API
+    pub inc: bool,
+
+    /// Some weird field the Geometry Dash Client sends along
+    ///
+    /// ## GD Internals:
+    /// This field is called `extras` in the boomlings API and needs to be
+    /// converted to an integer
+    pub extra: bool,
+}
+
+/// Manual `Hash` impl that doesn't hash `base`.
+impl Hash for SpecialLevelRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.weekly.hash(state);
+        self.inc.hash(state);
+        self.extra.hash(state);
+    }
+}
+
+/// Struct modelled after a request to `getGJDailyLevel.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve the index of the current daily level
+/// or weekly demon, and the time left until it is replaced. The level itself isn't part of the
+/// response and has to be retrieved with a [`SpecialLevelRequest`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DailyLevelRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// Whether to retrieve the weekly demon instead of the daily level
+    ///
+    /// ## GD Internals:
+    /// This field is called `weekly` in the boomlings API and needs to be converted to an integer
+    pub weekly: bool,
+}
+
+/// Manual `Hash` impl that doesn't hash `base`.
+impl Hash for DailyLevelRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.weekly.hash(state);
+    }
+}
+
+/// Struct modelled after a request to `getGJMapPacks21.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve a page of map packs. The levels
+/// contained in a map pack can be retrieved with a [`LevelsRequest`] of type
+/// [`LevelRequestType::MapPack`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MapPacksRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The page of map packs to retrieve. The first page is page `0`
+    ///
+    /// ## GD Internals:
+    /// This field is called `page` in the boomlings API
+    pub page: u32,
+}
+
+/// Manual `Hash` impl that doesn't hash `base`.
+impl Hash for MapPacksRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.page.hash(state);
+    }
+}
+
+/// Struct modelled after a request to `getGJGauntlets21.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve all gauntlets at once. The levels
+/// contained in a gauntlet can be retrieved with a [`LevelsRequest`] of type
+/// [`LevelRequestType::MapPack`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GauntletsRequest {
+    /// The base request data
+    pub base: BaseRequest,
+}
+
+/// Manual `Hash` impl that doesn't hash `base`. Since that's the only field, all
+/// `GauntletsRequest`s are identical.
+impl Hash for GauntletsRequest {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
 /// Struct modelled after a request to `getGJLevels21.php`
 ///
 /// In the Geometry Dash API, this endpoint is used to retrieve a list of
@@ -123,6 +229,18 @@ pub struct LevelsRequest {
     /// This value is ignored unless [`LevelsRequest::request_type`] is set to
     /// [`LevelRequestType::Search`]
     pub search_filters: SearchFilters,
+
+    /// The account IDs of the users whose levels to retrieve
+    ///
+    /// This value is ignored unless [`LevelsRequest::request_type`] is set to
+    /// [`LevelRequestType::Followed`] or [`LevelRequestType::Friends`]. For the latter, the servers
+    /// determine the friends of the account making the request on their own, but the IDs are still
+    /// part of the cache key, so that results for different friend lists don't get mixed up.
+    ///
+    /// ## GD Internals:
+    /// This field is called `followed` in the boomlings API and needs to be converted to a comma
+    /// separated list of integers. It is omitted if empty.
+    pub followed: Vec<u64>,
 }
 
 /// Manual Hash impl which doesn't hash the base
@@ -136,6 +254,10 @@ impl Hash for LevelsRequest {
         self.search_string.hash(state);
         self.request_type.hash(state);
         self.page.hash(state);
+
+        if !self.followed.is_empty() {
+            self.followed.hash(state);
+        }
     }
 }
 
@@ -338,13 +460,17 @@ pub enum LevelRequestType {
     /// This variant is represented by the value `11` in requests
     Awarded,
 
-    /// Unknown how this works
+    /// Request to retrieve the levels made by a list of accounts, the way the Geometry Dash client
+    /// retrieves the levels of the creators the player follows.
+    ///
+    /// The accounts are given by [`LevelsRequest::followed`]
     ///
     /// ## GD Internals:
     /// This variant is represented by the value `12` in requests
     Followed,
 
-    /// Unknown what this is
+    /// Request to retrieve the levels made by the friends of the account making the request. Can
+    /// only be made by an authenticated client.
     ///
     /// ## GD Internals:
     /// This variant is represented by the value `13` in requests
@@ -428,6 +554,49 @@ impl SearchFilters {
     }
 }
 
+/// Struct modelled after a request to `uploadGJLevel21.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to upload new levels and to update existing
+/// ones. It can only be made by an authenticated client, as the level is uploaded to the client's
+/// account. The servers respond with the ID of the uploaded level, under which the level is then
+/// stored in the cache, as if it had been retrieved via a [`LevelRequest`].
+#[derive(Debug, Clone)]
+pub struct UploadLevelRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The level to upload.
+    ///
+    /// Only the values the Geometry Dash client sends when uploading a level are used, which are
+    /// its name, description, version, length, song, password, object and coin amount, requested
+    /// stars, the level it is a copy of and its level data. All other values are assigned by the
+    /// servers.
+    ///
+    /// ## GD Internals:
+    /// The level's ID is sent as the `levelID` field. A level ID of `0` uploads a new level, any
+    /// other ID updates the existing level with that ID. Whether the level is a two player level is
+    /// sent as the `twoPlayer` field, which corresponds to [`PartialLevel::index_31`]
+    pub level: Level<Option<u64>, u64>,
+
+    /// Whether the level should be unlisted, meaning it can only be found via its ID
+    ///
+    /// ## GD Internals:
+    /// This field is called `unlisted` in the boomlings API and needs to be converted to an integer
+    pub unlisted: bool,
+}
+
+/// Manual `Hash` impl that doesn't hash `base`. Since levels aren't hashable, only the values
+/// identifying the uploaded version of the level are hashed.
+impl Hash for UploadLevelRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.level.base.level_id.hash(state);
+        self.level.base.name.hash(state);
+        self.level.base.version.hash(state);
+        self.level.level_data.hash(state);
+        self.unlisted.hash(state);
+    }
+}
+
 impl LevelRequest {
     const_setter! {
         /// Sets the [`BaseRequest`] to be used
@@ -465,6 +634,85 @@ impl LevelRequest {
     }
 }
 
+impl SpecialLevelRequest {
+    const_setter! {
+        /// Sets the [`BaseRequest`] to be used
+        ///
+        /// Allows builder-style creation of requests
+        base[with_base]: BaseRequest
+    }
+
+    const_setter! {
+        /// Sets the value of the `inc` field
+        ///
+        /// Allows builder-style creation of requests
+        inc: bool
+    }
+
+    const_setter! {
+        /// Sets the value of the `extra` field
+        ///
+        /// Allows builder-style creation of requests
+        extra: bool
+    }
+
+    /// Constructs a new `SpecialLevelRequest` to retrieve the current daily level, or the current
+    /// weekly demon if `weekly` is `true`
+    ///
+    /// Uses the same default values as [`LevelRequest::new`]
+    pub const fn new(weekly: bool) -> SpecialLevelRequest {
+        SpecialLevelRequest {
+            base: GD_21,
+            weekly,
+            inc: true,
+            extra: false,
+        }
+    }
+}
+
+impl DailyLevelRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter! {
+        /// Sets the value of the `weekly` field
+        ///
+        /// Allows builder-style creation of requests
+        weekly: bool
+    }
+
+    /// Constructs a new `DailyLevelRequest` for the current daily level, or the current weekly
+    /// demon if `weekly` is `true`
+    pub const fn new(weekly: bool) -> DailyLevelRequest {
+        DailyLevelRequest { base: GD_21, weekly }
+    }
+
+    /// The [`SpecialLevelRequest`] retrieving the level this request retrieves the daily
+    /// information of
+    pub const fn level_request(&self) -> SpecialLevelRequest {
+        SpecialLevelRequest::new(self.weekly).with_base(self.base)
+    }
+}
+
+impl MapPacksRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(page: u32);
+
+    /// Constructs a new `MapPacksRequest` for the first page of map packs
+    pub const fn new() -> MapPacksRequest {
+        MapPacksRequest { base: GD_21, page: 0 }
+    }
+}
+
+impl GauntletsRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    /// Constructs a new `GauntletsRequest`
+    pub const fn new() -> GauntletsRequest {
+        GauntletsRequest { base: GD_21 }
+    }
+}
+
 impl LevelsRequest {
     const_setter!(with_base, base, BaseRequest);
 
@@ -487,6 +735,30 @@ impl LevelsRequest {
         self.search(id.to_string())
     }
 
+    /// Makes this request retrieve the levels with the given IDs, the way the Geometry Dash client
+    /// retrieves the levels of a map pack or gauntlet
+    pub fn map_pack(mut self, level_ids: &[u64]) -> Self {
+        self.search_string = level_ids.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+        self.request_type = LevelRequestType::MapPack;
+        self
+    }
+
+    /// Makes this request retrieve the levels made by the accounts with the given account IDs, e.g.
+    /// the ones retrieved via a [`FriendListRequest`](crate::api::request::FriendListRequest)
+    pub fn followed(mut self, account_ids: &[u64]) -> Self {
+        self.followed = account_ids.to_vec();
+        self.request_type = LevelRequestType::Followed;
+        self
+    }
+
+    /// Makes this request retrieve the levels made by the friends of the account making the
+    /// request, whose account IDs are given
+    pub fn friends(mut self, account_ids: &[u64]) -> Self {
+        self.followed = account_ids.to_vec();
+        self.request_type = LevelRequestType::Friends;
+        self
+    }
+
     pub fn with_length(mut self, length: LevelLength) -> Self {
         self.lengths.push(length);
         self
@@ -503,6 +775,22 @@ impl LevelsRequest {
     }
 }
 
+impl UploadLevelRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(unlisted: bool);
+
+    /// Constructs a new `UploadLevelRequest` uploading the given level, which is uploaded as a new
+    /// level if its ID is `0`
+    pub const fn new(level: Level<Option<u64>, u64>) -> UploadLevelRequest {
+        UploadLevelRequest {
+            base: GD_21,
+            level,
+            unlisted: false,
+        }
+    }
+}
+
 impl Default for LevelRequestType {
     fn default() -> LevelRequestType {
         LevelRequestType::Featured
@@ -545,6 +833,28 @@ impl PaginatableRequest for LevelRequest {
     }
 }
 
+impl Request for SpecialLevelRequest {
+    type Result = Level<Option<u64>, u64>;
+}
+
+impl Request for DailyLevelRequest {
+    type Result = DailyLevel;
+}
+
+impl Request for MapPacksRequest {
+    type Result = Vec<MapPack>;
+}
+
+impl PaginatableRequest for MapPacksRequest {
+    fn next(&mut self) {
+        self.page += 1;
+    }
+}
+
+impl Request for GauntletsRequest {
+    type Result = Vec<Gauntlet>;
+}
+
 impl Request for LevelsRequest {
     type Result = Vec<PartialLevel<Option<u64>, u64>>;
 }
@@ -555,12 +865,40 @@ impl PaginatableRequest for LevelsRequest {
     }
 }
 
+impl Request for UploadLevelRequest {
+    type Result = u64;
+}
+
 impl Display for LevelRequest {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "LevelRequest({})", self.level_id)
     }
 }
 
+impl Display for SpecialLevelRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "SpecialLevelRequest(weekly={})", self.weekly)
+    }
+}
+
+impl Display for DailyLevelRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "DailyLevelRequest(weekly={})", self.weekly)
+    }
+}
+
+impl Display for MapPacksRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "MapPacksRequest(page={})", self.page)
+    }
+}
+
+impl Display for GauntletsRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "GauntletsRequest")
+    }
+}
+
 impl Display for LevelsRequest {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         match self.request_type {
@@ -569,3 +907,9 @@ impl Display for LevelsRequest {
         }
     }
 }
+
+impl Display for UploadLevelRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "UploadLevelRequest({}, {})", self.level.base.level_id, self.level.base.name)
+    }
+}
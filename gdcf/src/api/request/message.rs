@@ -0,0 +1,250 @@
+//! Module containing request definitions for retrieving and sending private messages
+
+use crate::api::request::{BaseRequest, PaginatableRequest, Request, GD_21};
+use gdcf_model::message::Message;
+use std::{
+    fmt::{Display, Error, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// Struct modelled after a request to `getGJMessages20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve the messages an account received
+/// or sent. It can only be made by an authenticated client. The messages in the response don't
+/// contain their bodies, those have to be retrieved via [`DownloadMessageRequest`]s.
+#[derive(Debug, Clone, Copy)]
+pub struct MessagesRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The account id of the account the client making the request is authenticated as.
+    ///
+    /// Since the messages retrieved depend on the account making the request, this field is part
+    /// of the request's cache key.
+    ///
+    /// ## GD Internals:
+    /// This value is sent as the `accountID` of the client's credentials, it is not part of the
+    /// request itself
+    pub account_id: u64,
+
+    /// Unknown, probably related to pagination
+    ///
+    /// ## GD Internals:
+    /// This field is called `total` in the boomlings API
+    pub total: u32,
+
+    /// The page of messages to retrieve. The first page is page `0`
+    ///
+    /// ## GD Internals:
+    /// This field is called `page` in the boomlings API
+    pub page: u32,
+
+    /// Whether to retrieve the messages the account sent, as opposed to the ones it received
+    ///
+    /// ## GD Internals:
+    /// This field is called `getSent` in the boomlings API and is `1` for sent messages and `0`
+    /// otherwise
+    pub sent: bool,
+}
+
+impl MessagesRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(total: u32);
+
+    const_setter!(page: u32);
+
+    pub const fn new(account_id: u64) -> MessagesRequest {
+        MessagesRequest {
+            base: GD_21,
+            account_id,
+            total: 0,
+            page: 0,
+            sent: false,
+        }
+    }
+
+    pub const fn sent(mut self) -> Self {
+        self.sent = true;
+        self
+    }
+
+    pub const fn received(mut self) -> Self {
+        self.sent = false;
+        self
+    }
+}
+
+impl Display for MessagesRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.sent {
+            write!(f, "MessagesRequest(sent by {}, page {})", self.account_id, self.page)
+        } else {
+            write!(f, "MessagesRequest(received by {}, page {})", self.account_id, self.page)
+        }
+    }
+}
+
+impl Hash for MessagesRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.account_id.hash(state);
+        self.total.hash(state);
+        self.page.hash(state);
+        self.sent.hash(state);
+    }
+}
+
+impl Request for MessagesRequest {
+    type Result = Vec<Message>;
+}
+
+impl PaginatableRequest for MessagesRequest {
+    fn next(&mut self) {
+        self.page += 1;
+    }
+}
+
+/// Struct modelled after a request to `downloadGJMessage20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve a single message, including its
+/// body. It can only be made by an authenticated client that either sent or received the message.
+#[derive(Debug, Clone, Copy)]
+pub struct DownloadMessageRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The ID of the message to retrieve
+    ///
+    /// ## GD Internals:
+    /// This field is called `messageID` in the boomlings API
+    pub message_id: u64,
+
+    /// Whether the account making the request sent the message, as opposed to having received it
+    ///
+    /// ## GD Internals:
+    /// This field is called `isSender` in the boomlings API and is `1` for sent messages and `0`
+    /// otherwise
+    pub is_sender: bool,
+}
+
+impl DownloadMessageRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(is_sender: bool);
+
+    pub const fn new(message_id: u64) -> DownloadMessageRequest {
+        DownloadMessageRequest {
+            base: GD_21,
+            message_id,
+            is_sender: false,
+        }
+    }
+}
+
+impl Display for DownloadMessageRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "DownloadMessageRequest({})", self.message_id)
+    }
+}
+
+impl Hash for DownloadMessageRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.message_id.hash(state);
+        self.is_sender.hash(state);
+    }
+}
+
+impl Into<DownloadMessageRequest> for u64 {
+    fn into(self) -> DownloadMessageRequest {
+        DownloadMessageRequest::new(self)
+    }
+}
+
+impl Into<DownloadMessageRequest> for &Message {
+    fn into(self) -> DownloadMessageRequest {
+        DownloadMessageRequest::new(self.message_id).is_sender(self.is_sender)
+    }
+}
+
+impl Request for DownloadMessageRequest {
+    type Result = Message;
+}
+
+impl PaginatableRequest for DownloadMessageRequest {
+    fn next(&mut self) {
+        self.message_id += 1;
+    }
+}
+
+/// Struct modelled after a request to `uploadGJMessage20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to send a private message to another account.
+/// It can only be made by an authenticated client, as the message is sent from the client's
+/// account. Once the message has been sent, all cached pages of the account's sent messages are
+/// invalidated.
+#[derive(Debug, Clone)]
+pub struct SendMessageRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The account id of the account the client making the request is authenticated as.
+    ///
+    /// Used to determine which cached message pages to invalidate.
+    ///
+    /// ## GD Internals:
+    /// This value is sent as the `accountID` of the client's credentials, it is not part of the
+    /// request itself
+    pub account_id: u64,
+
+    /// The account id of the account to send the message to
+    ///
+    /// ## GD Internals:
+    /// This field is called `toAccountID` in the boomlings API
+    pub recipient_id: u64,
+
+    /// The subject of the message
+    ///
+    /// ## GD Internals:
+    /// This field is called `subject` in the boomlings API. The subject is base64 encoded
+    pub subject: String,
+
+    /// The body of the message
+    ///
+    /// ## GD Internals:
+    /// This field is called `body` in the boomlings API. The body is XOR-encrypted with key
+    /// `14251` and then base64 encoded
+    pub body: String,
+}
+
+impl SendMessageRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub fn new(account_id: u64, recipient_id: u64, subject: impl Into<String>, body: impl Into<String>) -> Self {
+        SendMessageRequest {
+            base: GD_21,
+            account_id,
+            recipient_id,
+            subject: subject.into(),
+            body: body.into(),
+        }
+    }
+}
+
+impl Display for SendMessageRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "SendMessageRequest({} to {}, {})", self.account_id, self.recipient_id, self.subject)
+    }
+}
+
+impl Hash for SendMessageRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.account_id.hash(state);
+        self.recipient_id.hash(state);
+        self.subject.hash(state);
+        self.body.hash(state);
+    }
+}
+
+impl Request for SendMessageRequest {
+    type Result = ();
+}
@@ -10,15 +10,29 @@
 //! remote types.
 
 pub use self::{
-    comment::{LevelCommentsRequest, ProfileCommentsRequest},
-    level::{LevelRequest, LevelRequestType, LevelsRequest, SearchFilters, SongFilter},
-    user::{UserRequest, UserSearchRequest},
+    account::{Credentials, LoginRequest},
+    comment::{LevelCommentsRequest, ProfileCommentsRequest, UploadLevelCommentRequest, UploadProfileCommentRequest},
+    level::{
+        DailyLevelRequest, GauntletsRequest, LevelRequest, LevelRequestType, LevelsRequest, MapPacksRequest, SearchFilters, SongFilter,
+        SpecialLevelRequest, UploadLevelRequest,
+    },
+    message::{DownloadMessageRequest, MessagesRequest, SendMessageRequest},
+    rate::{LikeItemRequest, LikedItem, RateDemonRequest, RateStarsRequest},
+    song::SongInfoRequest,
+    user::{
+        BlockedUsersRequest, FriendListRequest, FriendRequestsRequest, LeaderboardRequest, LeaderboardType, LevelScoresRequest,
+        LevelScoresType, UserRequest, UserSearchRequest,
+    },
 };
 use gdcf_model::GameVersion;
 use std::{fmt::Debug, hash::Hash};
 
+pub mod account;
 pub mod comment;
 pub mod level;
+pub mod message;
+pub mod rate;
+pub mod song;
 pub mod user;
 
 /// A `BaseRequest` instance that has all its fields set to the
@@ -81,7 +95,10 @@ impl Default for BaseRequest {
 
 /// Trait for types that are meant to be requests whose results can be cached
 /// by GDCF.
-pub trait Request: Debug + Send + Sync + 'static {
+///
+/// Two requests that hash to the same value are assumed to be identical, which GDCF uses to avoid
+/// making the same request multiple times concurrently.
+pub trait Request: Debug + Hash + Send + Sync + 'static {
     /// The type of object returned by this request.
     ///
     /// For requests that return multiple types of objects (like [`LevelsRequest`], which returns
@@ -96,7 +113,48 @@ pub trait Request: Debug + Send + Sync + 'static {
 /// also requests like [`LevelRequest`] can be seen as paginatable (and does in fact implement this
 /// trait) because we can interpret a level with some level ID `n` to be the `n-`th page of the
 /// request.
-pub trait PaginatableRequest: Request {
+pub trait PaginatableRequest: Request + Clone {
     /// Modifies this request in-place to be a request for the next page
     fn next(&mut self);
 }
+
+/// Information about the position of a page within all pages of a [`PaginatableRequest`], as
+/// provided by the servers alongside some paginated responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pagination {
+    /// The total amount of objects across all pages
+    pub total: u32,
+
+    /// The index of the first object on this page
+    pub offset: u32,
+
+    /// The maximum amount of objects on a single page, or `0` if the servers didn't provide it
+    pub page_size: u32,
+}
+
+impl Pagination {
+    /// The total amount of pages, or [`None`] if the servers didn't tell us their page size
+    pub fn total_pages(&self) -> Option<u32> {
+        if self.page_size == 0 {
+            None
+        } else {
+            Some((self.total + self.page_size - 1) / self.page_size)
+        }
+    }
+
+    /// The zero-based index of this page, or [`None`] if the servers didn't tell us their page
+    /// size
+    pub fn page(&self) -> Option<u32> {
+        if self.page_size == 0 {
+            None
+        } else {
+            Some(self.offset / self.page_size)
+        }
+    }
+
+    /// Whether there are no pages after this one. Always `false` if the servers didn't tell us
+    /// their page size
+    pub fn is_last_page(&self) -> bool {
+        self.page_size != 0 && self.offset.saturating_add(self.page_size) >= self.total
+    }
+}
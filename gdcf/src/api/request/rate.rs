@@ -0,0 +1,225 @@
+//! Module containing request definitions for liking and rating levels and comments
+
+use crate::api::request::{BaseRequest, Request, GD_21};
+use gdcf_model::level::DemonRating;
+use std::{
+    fmt::{Display, Error, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// The secret String the servers use to identify valid clients on moderation related endpoints
+pub const MOD_SECRET: &str = "Wmfp3879gc3";
+
+/// Enum modelling the different kinds of items that can be liked
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum LikedItem {
+    /// A level, given by its ID
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `1` in the `type` field of the request
+    Level(u64),
+
+    /// A comment on a level
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `2` in the `type` field of the request. The level ID
+    /// is sent as the `special` field
+    LevelComment {
+        /// The ID of the comment
+        comment_id: u64,
+
+        /// The ID of the level the comment was posted on
+        level_id: u64,
+    },
+
+    /// A comment on some account's profile
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `3` in the `type` field of the request. The account
+    /// ID is sent as the `special` field
+    ProfileComment {
+        /// The ID of the comment
+        comment_id: u64,
+
+        /// The account ID of the account on whose profile the comment was posted
+        account_id: u64,
+    },
+}
+
+impl LikedItem {
+    /// The ID of the liked item itself
+    pub fn item_id(self) -> u64 {
+        match self {
+            LikedItem::Level(level_id) => level_id,
+            LikedItem::LevelComment { comment_id, .. } | LikedItem::ProfileComment { comment_id, .. } => comment_id,
+        }
+    }
+}
+
+impl Display for LikedItem {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            LikedItem::Level(level_id) => write!(f, "level {}", level_id),
+            LikedItem::LevelComment { comment_id, level_id } => write!(f, "comment {} on level {}", comment_id, level_id),
+            LikedItem::ProfileComment { comment_id, account_id } => write!(f, "comment {} on profile {}", comment_id, account_id),
+        }
+    }
+}
+
+/// Struct modelled after a request to `likeGJItem211.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to like or dislike levels and comments. It can
+/// only be made by an authenticated client. Once the request succeeded, the cached data containing
+/// the item's like count is invalidated.
+#[derive(Debug, Clone, Copy)]
+pub struct LikeItemRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The item to like or dislike
+    ///
+    /// ## GD Internals:
+    /// The item's ID is called `itemID` in the boomlings API
+    pub item: LikedItem,
+
+    /// Whether to like the item, as opposed to disliking it
+    ///
+    /// ## GD Internals:
+    /// This field is called `like` in the boomlings API and is `1` for likes and `0` for dislikes
+    pub like: bool,
+}
+
+impl LikeItemRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(like: bool);
+
+    pub const fn new(item: LikedItem) -> Self {
+        LikeItemRequest {
+            base: GD_21,
+            item,
+            like: true,
+        }
+    }
+}
+
+impl Display for LikeItemRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.like {
+            write!(f, "LikeItemRequest(like {})", self.item)
+        } else {
+            write!(f, "LikeItemRequest(dislike {})", self.item)
+        }
+    }
+}
+
+impl Hash for LikeItemRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.item.hash(state);
+        self.like.hash(state);
+    }
+}
+
+impl Request for LikeItemRequest {
+    type Result = ();
+}
+
+/// Struct modelled after a request to `rateGJStars211.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to suggest a star rating for a level. It can
+/// only be made by an authenticated client.
+#[derive(Debug, Clone, Copy)]
+pub struct RateStarsRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The ID of the level to rate
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    pub level_id: u64,
+
+    /// The amount of stars to suggest, between 1 and 10
+    pub stars: u8,
+}
+
+impl RateStarsRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub const fn new(level_id: u64, stars: u8) -> Self {
+        RateStarsRequest {
+            base: GD_21,
+            level_id,
+            stars,
+        }
+    }
+}
+
+impl Display for RateStarsRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "RateStarsRequest({}, {})", self.level_id, self.stars)
+    }
+}
+
+impl Hash for RateStarsRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.level_id.hash(state);
+        self.stars.hash(state);
+    }
+}
+
+impl Request for RateStarsRequest {
+    type Result = ();
+}
+
+/// Struct modelled after a request to `rateGJDemon21.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to vote on the difficulty of a demon level. It
+/// can only be made by an authenticated client.
+#[derive(Debug, Clone, Copy)]
+pub struct RateDemonRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The ID of the level to rate
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    pub level_id: u64,
+
+    /// The demon difficulty to vote for
+    ///
+    /// ## GD Internals:
+    /// This field is called `rating` in the boomlings API, with values ranging from `1` (easy
+    /// demon) to `5` (extreme demon)
+    pub rating: DemonRating,
+}
+
+impl RateDemonRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub const fn new(level_id: u64, rating: DemonRating) -> Self {
+        RateDemonRequest {
+            base: BaseRequest::new(GD_21.game_version, GD_21.binary_version, MOD_SECRET),
+            level_id,
+            rating,
+        }
+    }
+}
+
+impl Display for RateDemonRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "RateDemonRequest({}, {:?})", self.level_id, self.rating)
+    }
+}
+
+impl Hash for RateDemonRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.level_id.hash(state);
+        self.rating.hash(state);
+    }
+}
+
+impl Request for RateDemonRequest {
+    type Result = ();
+}
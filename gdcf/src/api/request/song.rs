@@ -0,0 +1,59 @@
+//! Module containing request definitions for retrieving newgrounds songs
+
+use crate::api::request::{BaseRequest, Request, GD_21};
+use gdcf_model::song::NewgroundsSong;
+use std::{
+    fmt::{Display, Error, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// Struct modelled after a request to `getGJSongInfo.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve information about a single
+/// newgrounds song. Songs that have been banned from being used in Geometry Dash are not provided.
+///
+/// The results of this request are cached the same way as the songs contained in
+/// [`LevelsRequest`](crate::api::request::LevelsRequest) responses, so looking up a
+/// [`NewgroundsSongKey`](crate::cache::NewgroundsSongKey) also finds songs retrieved via this
+/// request, and vice versa.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SongInfoRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The newgrounds ID of the song to retrieve
+    ///
+    /// ## GD Internals:
+    /// This field is called `songID` in the boomlings API
+    pub song_id: u64,
+}
+
+impl SongInfoRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub const fn new(song_id: u64) -> Self {
+        SongInfoRequest { base: GD_21, song_id }
+    }
+}
+
+impl Into<SongInfoRequest> for u64 {
+    fn into(self) -> SongInfoRequest {
+        SongInfoRequest::new(self)
+    }
+}
+
+impl Hash for SongInfoRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.song_id.hash(state)
+    }
+}
+
+impl Display for SongInfoRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "SongInfoRequest({})", self.song_id)
+    }
+}
+
+impl Request for SongInfoRequest {
+    type Result = NewgroundsSong;
+}
@@ -1,7 +1,7 @@
 //! Module containing request definitions for retrieving users
 
 use crate::api::request::{BaseRequest, PaginatableRequest, Request, GD_21};
-use gdcf_model::user::{Creator, SearchedUser, User};
+use gdcf_model::user::{Creator, FriendRequest, LeaderboardEntry, LevelScore, ListedUser, SearchedUser, User};
 use std::{
     fmt::{Display, Error, Formatter},
     hash::{Hash, Hasher},
@@ -177,3 +177,415 @@ impl PaginatableRequest for UserSearchRequest {
         self.page += 1;
     }
 }
+
+/// Struct modelled after a request to `getGJScores20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve the leaderboards. The
+/// [`LeaderboardType::Relative`] and [`LeaderboardType::Friends`] leaderboards are relative to the
+/// account making the request, so they can only be retrieved by a client authenticated as the
+/// account they were constructed for.
+#[derive(Debug, Clone, Copy)]
+pub struct LeaderboardRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The leaderboard to retrieve
+    ///
+    /// ## GD Internals:
+    /// This field is called `type` in the boomlings API
+    pub leaderboard_type: LeaderboardType,
+
+    /// The amount of entries to retrieve
+    ///
+    /// ## GD Internals:
+    /// This field is called `count` in the boomlings API. The official client always requests `100`
+    /// entries.
+    pub count: u32,
+}
+
+/// Enum containing the various leaderboards a [`LeaderboardRequest`] can retrieve
+///
+/// The leaderboards depending on the account making the request contain the account id of the
+/// account the client making the request is authenticated as. Since it is part of the request's
+/// cache key, the leaderboards of different accounts are cached separately.
+///
+/// ## GD Internals:
+/// These values are sent as the strings `"top"`, `"creators"`, `"relative"` and `"friends"`
+/// respectively. The account id is sent as the `accountID` of the client's credentials, it is not
+/// part of the request itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LeaderboardType {
+    /// The global top players, ranked by stars
+    Top,
+
+    /// The global top creators, ranked by creator points
+    Creators,
+
+    /// The players ranked around the account with the given id
+    Relative(u64),
+
+    /// The friends of the account with the given id, ranked by stars
+    Friends(u64),
+}
+
+impl Default for LeaderboardType {
+    fn default() -> LeaderboardType {
+        LeaderboardType::Top
+    }
+}
+
+impl LeaderboardRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(leaderboard_type: LeaderboardType);
+
+    const_setter!(count: u32);
+
+    pub const fn new(leaderboard_type: LeaderboardType) -> Self {
+        LeaderboardRequest {
+            base: GD_21,
+            leaderboard_type,
+            count: 100,
+        }
+    }
+
+    /// Constructs a new `LeaderboardRequest` for the players ranked around the account with the
+    /// given id. Can only be made by a client authenticated as that account.
+    pub const fn relative(account_id: u64) -> Self {
+        LeaderboardRequest::new(LeaderboardType::Relative(account_id))
+    }
+
+    /// Constructs a new `LeaderboardRequest` for the friends of the account with the given id. Can
+    /// only be made by a client authenticated as that account.
+    pub const fn friends(account_id: u64) -> Self {
+        LeaderboardRequest::new(LeaderboardType::Friends(account_id))
+    }
+}
+
+impl Default for LeaderboardRequest {
+    fn default() -> Self {
+        LeaderboardRequest::new(LeaderboardType::default())
+    }
+}
+
+impl Into<LeaderboardRequest> for LeaderboardType {
+    fn into(self) -> LeaderboardRequest {
+        LeaderboardRequest::new(self)
+    }
+}
+
+/// Manual `Hash` impl that doesn't hash `base`.
+impl Hash for LeaderboardRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.leaderboard_type.hash(state);
+        self.count.hash(state);
+    }
+}
+
+impl Display for LeaderboardRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "LeaderboardRequest({:?}, count={})", self.leaderboard_type, self.count)
+    }
+}
+
+impl Request for LeaderboardRequest {
+    type Result = Vec<LeaderboardEntry>;
+}
+
+/// Struct modelled after a request to `getGJLevelScores211.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve the leaderboard of a single level.
+/// The servers only answer this request if it is made by an authenticated client.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LevelScoresRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The ID of the level whose leaderboard to retrieve
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    pub level_id: u64,
+
+    /// The leaderboard of the level to retrieve
+    ///
+    /// ## GD Internals:
+    /// This field is called `type` in the boomlings API
+    pub leaderboard_type: LevelScoresType,
+}
+
+/// Enum containing the various leaderboards of a level a [`LevelScoresRequest`] can retrieve
+///
+/// The friends leaderboard contains the account id of the account the client making the request
+/// is authenticated as. Since it is part of the request's cache key, the friends leaderboards of
+/// different accounts are cached separately.
+///
+/// ## GD Internals:
+/// These values are sent as `0`, `1` and `2` respectively. The account id is sent as the
+/// `accountID` of the client's credentials, it is not part of the request itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LevelScoresType {
+    /// The scores of the friends of the account with the given id
+    Friends(u64),
+
+    /// The best scores of all time
+    Top,
+
+    /// The best scores set within the last week
+    Week,
+}
+
+impl Default for LevelScoresType {
+    fn default() -> LevelScoresType {
+        LevelScoresType::Top
+    }
+}
+
+impl From<LevelScoresType> for i32 {
+    fn from(score_type: LevelScoresType) -> Self {
+        match score_type {
+            LevelScoresType::Friends(_) => 0,
+            LevelScoresType::Top => 1,
+            LevelScoresType::Week => 2,
+        }
+    }
+}
+
+impl LevelScoresRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(level_id: u64);
+
+    const_setter!(leaderboard_type: LevelScoresType);
+
+    pub const fn new(level_id: u64) -> Self {
+        LevelScoresRequest {
+            base: GD_21,
+            level_id,
+            leaderboard_type: LevelScoresType::Top,
+        }
+    }
+
+    /// Constructs a new `LevelScoresRequest` for the scores the friends of the account with the
+    /// given id set on the given level. Can only be made by a client authenticated as that account.
+    pub const fn friends(level_id: u64, account_id: u64) -> Self {
+        LevelScoresRequest::new(level_id).leaderboard_type(LevelScoresType::Friends(account_id))
+    }
+}
+
+impl Into<LevelScoresRequest> for u64 {
+    fn into(self) -> LevelScoresRequest {
+        LevelScoresRequest::new(self)
+    }
+}
+
+/// Manual `Hash` impl that doesn't hash `base`.
+impl Hash for LevelScoresRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.level_id.hash(state);
+        self.leaderboard_type.hash(state);
+    }
+}
+
+impl Display for LevelScoresRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "LevelScoresRequest({}, {:?})", self.level_id, self.leaderboard_type)
+    }
+}
+
+impl Request for LevelScoresRequest {
+    type Result = Vec<LevelScore>;
+}
+
+/// Struct modelled after a request to `getGJUserList20.php` retrieving an account's friend list.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve the friend list of the account
+/// making the request, so it can only be made by an authenticated client. The account IDs of the
+/// retrieved users can be passed to [`LevelsRequest::friends`](crate::api::request::LevelsRequest::friends)
+/// to search for levels made by friends.
+#[derive(Debug, Clone, Copy)]
+pub struct FriendListRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The account id of the account the client making the request is authenticated as.
+    ///
+    /// Since the friend list retrieved depends on the account making the request, this field is
+    /// part of the request's cache key.
+    ///
+    /// ## GD Internals:
+    /// This value is sent as the `accountID` of the client's credentials, it is not part of the
+    /// request itself
+    pub account_id: u64,
+}
+
+impl FriendListRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub const fn new(account_id: u64) -> Self {
+        FriendListRequest { base: GD_21, account_id }
+    }
+}
+
+impl Into<FriendListRequest> for u64 {
+    fn into(self) -> FriendListRequest {
+        FriendListRequest::new(self)
+    }
+}
+
+impl Hash for FriendListRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.account_id.hash(state)
+    }
+}
+
+impl Display for FriendListRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "FriendListRequest({})", self.account_id)
+    }
+}
+
+impl Request for FriendListRequest {
+    type Result = Vec<ListedUser>;
+}
+
+/// Struct modelled after a request to `getGJUserList20.php` retrieving the users an account
+/// blocked.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve the users the account making the
+/// request blocked, so it can only be made by an authenticated client.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockedUsersRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The account id of the account the client making the request is authenticated as.
+    ///
+    /// Since the list retrieved depends on the account making the request, this field is part of
+    /// the request's cache key.
+    ///
+    /// ## GD Internals:
+    /// This value is sent as the `accountID` of the client's credentials, it is not part of the
+    /// request itself
+    pub account_id: u64,
+}
+
+impl BlockedUsersRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    pub const fn new(account_id: u64) -> Self {
+        BlockedUsersRequest { base: GD_21, account_id }
+    }
+}
+
+impl Hash for BlockedUsersRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.account_id.hash(state)
+    }
+}
+
+impl Display for BlockedUsersRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "BlockedUsersRequest({})", self.account_id)
+    }
+}
+
+impl Request for BlockedUsersRequest {
+    type Result = Vec<ListedUser>;
+}
+
+/// Struct modelled after a request to `getGJFriendRequests20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve the pending friend requests an
+/// account received or sent. It can only be made by an authenticated client.
+#[derive(Debug, Clone, Copy)]
+pub struct FriendRequestsRequest {
+    /// The base request data
+    pub base: BaseRequest,
+
+    /// The account id of the account the client making the request is authenticated as.
+    ///
+    /// Since the friend requests retrieved depend on the account making the request, this field is
+    /// part of the request's cache key.
+    ///
+    /// ## GD Internals:
+    /// This value is sent as the `accountID` of the client's credentials, it is not part of the
+    /// request itself
+    pub account_id: u64,
+
+    /// Unknown, probably related to pagination
+    ///
+    /// ## GD Internals:
+    /// This field is called `total` in the boomlings API
+    pub total: u32,
+
+    /// The page of friend requests to retrieve. The first page is page `0`
+    ///
+    /// ## GD Internals:
+    /// This field is called `page` in the boomlings API
+    pub page: u32,
+
+    /// Whether to retrieve the friend requests the account sent, as opposed to the ones it received
+    ///
+    /// ## GD Internals:
+    /// This field is called `getSent` in the boomlings API and is `1` for outgoing requests and `0`
+    /// otherwise
+    pub outgoing: bool,
+}
+
+impl FriendRequestsRequest {
+    const_setter!(with_base, base, BaseRequest);
+
+    const_setter!(total: u32);
+
+    const_setter!(page: u32);
+
+    pub const fn new(account_id: u64) -> Self {
+        FriendRequestsRequest {
+            base: GD_21,
+            account_id,
+            total: 0,
+            page: 0,
+            outgoing: false,
+        }
+    }
+
+    pub const fn incoming(mut self) -> Self {
+        self.outgoing = false;
+        self
+    }
+
+    pub const fn outgoing(mut self) -> Self {
+        self.outgoing = true;
+        self
+    }
+}
+
+impl Hash for FriendRequestsRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.account_id.hash(state);
+        self.total.hash(state);
+        self.page.hash(state);
+        self.outgoing.hash(state);
+    }
+}
+
+impl Display for FriendRequestsRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.outgoing {
+            write!(f, "FriendRequestsRequest(sent by {}, page {})", self.account_id, self.page)
+        } else {
+            write!(f, "FriendRequestsRequest(received by {}, page {})", self.account_id, self.page)
+        }
+    }
+}
+
+impl Request for FriendRequestsRequest {
+    type Result = Vec<FriendRequest>;
+}
+
+impl PaginatableRequest for FriendRequestsRequest {
+    fn next(&mut self) {
+        self.page += 1;
+    }
+}
@@ -0,0 +1,521 @@
+//! Module containing a [`Cache`] implementation that keeps everything in memory
+//!
+//! The [`MemoryCache`] is intended for tests and small tools that do not need to persist anything
+//! between runs. Nothing is ever written to disk.
+
+use crate::{
+    api::request::{
+        BlockedUsersRequest, DailyLevelRequest, DownloadMessageRequest, FriendListRequest, FriendRequestsRequest, GauntletsRequest,
+        LeaderboardRequest, LevelCommentsRequest, LevelRequest, LevelScoresRequest, LevelsRequest, LikeItemRequest, LikedItem,
+        MapPacksRequest, MessagesRequest, ProfileCommentsRequest, RateDemonRequest, RateStarsRequest, SendMessageRequest,
+        SongInfoRequest, SpecialLevelRequest, UploadLevelCommentRequest, UploadLevelRequest, UploadProfileCommentRequest, UserRequest,
+        UserSearchRequest,
+    },
+    cache::{Cache, CacheEntry, CacheEntryMeta, CreatorKey, Invalidate, Lookup, NewgroundsSongKey, Store},
+    error::CacheError,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use failure::Fail;
+use gdcf_model::{
+    comment::{CommentUser, LevelComment, ProfileComment},
+    level::{DailyLevel, Gauntlet, Level, MapPack, PartialLevel},
+    message::Message,
+    song::NewgroundsSong,
+    user::{Creator, FriendRequest, LeaderboardEntry, LevelScore, ListedUser, SearchedUser, User},
+};
+use log::{debug, warn};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+/// The [`CacheEntryMeta`] used by the [`MemoryCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    cached_at: NaiveDateTime,
+    expired: bool,
+    absent: bool,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Entry cached at {} (expired: {}), absent: {}",
+            self.cached_at, self.expired, self.absent
+        )
+    }
+}
+
+impl CacheEntryMeta for Entry {
+    fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    fn is_absent(&self) -> bool {
+        self.absent
+    }
+
+    fn cached_at(&self) -> NaiveDateTime {
+        self.cached_at
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    /// Some thread panicked while it was accessing the cache, leaving it in a potentially
+    /// inconsistent state
+    #[fail(display = "The lock guarding the in-memory cache was poisoned")]
+    Poisoned,
+}
+
+impl CacheError for Error {}
+
+#[derive(Debug)]
+struct StoredObject<T> {
+    /// The stored object, or [`None`] if the object was marked as absent
+    object: Option<T>,
+    cached_at: NaiveDateTime,
+
+    /// Value of the [`Tables`]'s clock when this object was last accessed. Used for LRU eviction
+    last_used: u64,
+}
+
+#[derive(Debug)]
+struct Table<T> {
+    objects: HashMap<u64, StoredObject<T>>,
+
+    /// Maps group keys (e.g. the ID of the level some comment pages belong to) to the keys of the
+    /// objects in that group, so they can be invalidated together
+    groups: HashMap<u64, HashSet<u64>>,
+}
+
+impl<T> Default for Table<T> {
+    fn default() -> Self {
+        Table {
+            objects: HashMap::new(),
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Table<T> {
+    fn invalidate_group(&mut self, group: u64) {
+        for key in self.groups.remove(&group).unwrap_or_default() {
+            self.objects.remove(&key);
+        }
+    }
+
+    /// Removes all stored objects matching the given predicate. Objects marked as absent are kept.
+    fn invalidate_where(&mut self, predicate: impl Fn(&T) -> bool) {
+        self.objects
+            .retain(|_, stored| !stored.object.as_ref().map_or(false, |object| predicate(object)))
+    }
+}
+
+trait Evict {
+    fn len(&self) -> usize;
+
+    /// Returns the `last_used` value and the key of the least recently used object in this table
+    fn least_recently_used(&self) -> Option<(u64, u64)>;
+
+    fn remove(&mut self, key: u64);
+}
+
+impl<T> Evict for Table<T> {
+    fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    fn least_recently_used(&self) -> Option<(u64, u64)> {
+        self.objects.iter().map(|(key, stored)| (stored.last_used, *key)).min()
+    }
+
+    fn remove(&mut self, key: u64) {
+        self.objects.remove(&key);
+
+        for keys in self.groups.values_mut() {
+            keys.remove(&key);
+        }
+
+        self.groups.retain(|_, keys| !keys.is_empty());
+    }
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    clock: u64,
+
+    levels: Table<Level<Option<u64>, u64>>,
+    level_lists: Table<Vec<PartialLevel<Option<u64>, u64>>>,
+    users: Table<User>,
+    searched_users: Table<SearchedUser>,
+    level_comments: Table<Vec<LevelComment<Option<CommentUser>>>>,
+    profile_comments: Table<Vec<ProfileComment>>,
+    daily_levels: Table<DailyLevel>,
+    map_packs: Table<Vec<MapPack>>,
+    gauntlets: Table<Vec<Gauntlet>>,
+    leaderboards: Table<Vec<LeaderboardEntry>>,
+    level_scores: Table<Vec<LevelScore>>,
+    messages: Table<Vec<Message>>,
+    downloaded_messages: Table<Message>,
+    friend_lists: Table<Vec<ListedUser>>,
+    blocked_users: Table<Vec<ListedUser>>,
+    friend_requests: Table<Vec<FriendRequest>>,
+    creators: Table<Creator>,
+    songs: Table<NewgroundsSong>,
+}
+
+impl Tables {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn tables(&mut self) -> [&mut dyn Evict; 18] {
+        [
+            &mut self.levels,
+            &mut self.level_lists,
+            &mut self.users,
+            &mut self.searched_users,
+            &mut self.level_comments,
+            &mut self.profile_comments,
+            &mut self.daily_levels,
+            &mut self.map_packs,
+            &mut self.gauntlets,
+            &mut self.leaderboards,
+            &mut self.level_scores,
+            &mut self.messages,
+            &mut self.downloaded_messages,
+            &mut self.friend_lists,
+            &mut self.blocked_users,
+            &mut self.friend_requests,
+            &mut self.creators,
+            &mut self.songs,
+        ]
+    }
+
+    fn len(&mut self) -> usize {
+        self.tables().iter().map(|table| table.len()).sum()
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let tables = self.tables();
+
+        let victim = tables
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, table)| table.least_recently_used().map(|(last_used, key)| (last_used, idx, key)))
+            .min();
+
+        if let Some((_, idx, key)) = victim {
+            tables[idx].remove(key)
+        }
+    }
+}
+
+/// A [`Cache`] keeping all its data in hash maps
+///
+/// Clones of a [`MemoryCache`] share their contents, just like clones of a database backed cache
+/// would share the same database.
+#[derive(Debug, Clone)]
+pub struct MemoryCache {
+    tables: Arc<Mutex<Tables>>,
+    expire_after: Duration,
+    absent_expire_after: Duration,
+    capacity: Option<usize>,
+}
+
+impl MemoryCache {
+    /// Constructs a new, empty [`MemoryCache`] whose entries expire after the given duration.
+    ///
+    /// Entries marking objects as absent expire after the same duration, and the amount of stored
+    /// objects is unbounded.
+    pub fn new(expire_after: Duration) -> Self {
+        MemoryCache {
+            tables: Arc::new(Mutex::new(Tables::default())),
+            expire_after,
+            absent_expire_after: expire_after,
+            capacity: None,
+        }
+    }
+
+    /// Sets the duration after which entries marking objects as absent expire
+    pub fn absent_expire_after(mut self, absent_expire_after: Duration) -> Self {
+        self.absent_expire_after = absent_expire_after;
+        self
+    }
+
+    /// Bounds the amount of objects this cache holds.
+    ///
+    /// Once more than `capacity` objects (of any type) are stored, the least recently used ones are
+    /// evicted.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    fn entry(&self, cached_at: NaiveDateTime, absent: bool) -> Entry {
+        let expire_after = if absent { self.absent_expire_after } else { self.expire_after };
+
+        Entry {
+            cached_at,
+            expired: Utc::now().naive_utc() - cached_at > expire_after,
+            absent,
+        }
+    }
+
+    fn lookup_in<T: Clone>(&self, key: u64, table: impl FnOnce(&mut Tables) -> &mut Table<T>) -> Result<CacheEntry<T, Entry>, Error> {
+        let mut tables = self.tables.lock().map_err(|_| Error::Poisoned)?;
+        let now = tables.tick();
+
+        match table(&mut tables).objects.get_mut(&key) {
+            None => Ok(CacheEntry::Missing),
+            Some(stored) => {
+                stored.last_used = now;
+
+                let entry = self.entry(stored.cached_at, stored.object.is_none());
+
+                match stored.object {
+                    None => Ok(CacheEntry::MarkedAbsent(entry)),
+                    Some(ref object) => Ok(CacheEntry::Cached(object.clone(), entry)),
+                }
+            },
+        }
+    }
+
+    fn store_in<T>(
+        &self, key: u64, group: Option<u64>, object: Option<T>, table: impl FnOnce(&mut Tables) -> &mut Table<T>,
+    ) -> Result<Entry, Error> {
+        let mut tables = self.tables.lock().map_err(|_| Error::Poisoned)?;
+        let now = tables.tick();
+        let cached_at = Utc::now().naive_utc();
+        let absent = object.is_none();
+        let table = table(&mut tables);
+
+        table.objects.insert(key, StoredObject {
+            object,
+            cached_at,
+            last_used: now,
+        });
+
+        if let Some(group) = group {
+            table.groups.entry(group).or_default().insert(key);
+        }
+
+        if let Some(capacity) = self.capacity {
+            while tables.len() > capacity {
+                tables.evict_least_recently_used()
+            }
+        }
+
+        Ok(Entry {
+            cached_at,
+            expired: false,
+            absent,
+        })
+    }
+
+    fn invalidate_in<T>(&self, group: u64, table: impl FnOnce(&mut Tables) -> &mut Table<T>) -> Result<(), Error> {
+        let mut tables = self.tables.lock().map_err(|_| Error::Poisoned)?;
+
+        table(&mut tables).invalidate_group(group);
+
+        Ok(())
+    }
+
+    /// Removes the given level, and all level lists containing it, from the cache
+    fn invalidate_level(&self, level_id: u64) -> Result<(), Error> {
+        debug!("Invalidating cached data of level {}", level_id);
+
+        let mut tables = self.tables.lock().map_err(|_| Error::Poisoned)?;
+
+        // Levels aren't grouped, since the same level can be part of arbitrarily many lists, so we
+        // have to look through all of them
+        tables.levels.invalidate_where(|level| level.base.level_id == level_id);
+        tables
+            .level_lists
+            .invalidate_where(|levels| levels.iter().any(|level| level.level_id == level_id));
+
+        Ok(())
+    }
+}
+
+impl Cache for MemoryCache {
+    type CacheEntryMeta = Entry;
+    type Err = Error;
+}
+
+fn hash(value: impl Hash) -> u64 {
+    let mut state = DefaultHasher::new();
+
+    value.hash(&mut state);
+
+    state.finish()
+}
+
+macro_rules! memory_cache {
+    ($key_type: ty, $table: ident, $key: ident => $hashed: expr) => {
+        memory_cache!($key_type, $table, $key => $hashed, group => None);
+    };
+
+    ($key_type: ty, $table: ident, $key: ident => $hashed: expr, group => $group: expr) => {
+        impl Lookup<$key_type> for MemoryCache {
+            fn lookup(&self, $key: &$key_type) -> Result<CacheEntry<<$key_type as crate::cache::Key>::Result, Entry>, Error> {
+                self.lookup_in(hash($hashed), |tables| &mut tables.$table)
+            }
+        }
+
+        impl Store<$key_type> for MemoryCache {
+            fn store(&mut self, object: &<$key_type as crate::cache::Key>::Result, $key: &$key_type) -> Result<Entry, Error> {
+                debug!("Storing {} under key {}", stringify!($key_type), $key);
+
+                self.store_in(hash($hashed), $group, Some(object.clone()), |tables| &mut tables.$table)
+            }
+
+            fn mark_absent(&mut self, $key: &$key_type) -> Result<Entry, Error> {
+                warn!("Marking {} with key {} as absent!", stringify!($key_type), $key);
+
+                self.store_in(hash($hashed), $group, None, |tables| &mut tables.$table)
+            }
+        }
+    };
+}
+
+memory_cache!(LevelRequest, levels, key => key);
+// The daily level and weekly demon share their table with all other levels, but are stored under
+// the hash of their special request, not under their level ID
+memory_cache!(SpecialLevelRequest, levels, key => key);
+memory_cache!(LevelsRequest, level_lists, key => key);
+memory_cache!(UserRequest, users, key => key);
+memory_cache!(UserSearchRequest, searched_users, key => key);
+memory_cache!(LevelCommentsRequest, level_comments, key => key, group => Some(key.level_id));
+memory_cache!(ProfileCommentsRequest, profile_comments, key => key, group => Some(key.account_id));
+memory_cache!(MapPacksRequest, map_packs, key => key);
+memory_cache!(GauntletsRequest, gauntlets, key => key);
+memory_cache!(LeaderboardRequest, leaderboards, key => key);
+memory_cache!(LevelScoresRequest, level_scores, key => key);
+// Only the sent messages of an account change when it sends a message
+memory_cache!(MessagesRequest, messages, key => key, group => if key.sent { Some(key.account_id) } else { None });
+memory_cache!(DownloadMessageRequest, downloaded_messages, key => key);
+memory_cache!(FriendListRequest, friend_lists, key => key);
+memory_cache!(BlockedUsersRequest, blocked_users, key => key);
+memory_cache!(FriendRequestsRequest, friend_requests, key => key);
+memory_cache!(CreatorKey, creators, key => key.0);
+memory_cache!(NewgroundsSongKey, songs, key => key.0);
+// Songs retrieved via `getGJSongInfo` share their table with the ones contained in level lists
+memory_cache!(SongInfoRequest, songs, key => key.song_id);
+
+// Daily levels don't expire after a fixed duration, but whenever the servers replace them
+impl Lookup<DailyLevelRequest> for MemoryCache {
+    fn lookup(&self, key: &DailyLevelRequest) -> Result<CacheEntry<DailyLevel, Entry>, Error> {
+        match self.lookup_in(hash(key), |tables| &mut tables.daily_levels)? {
+            CacheEntry::Cached(mut daily, mut entry) => {
+                let elapsed = (Utc::now().naive_utc() - entry.cached_at).num_seconds().max(0) as u32;
+
+                daily.seconds_left = daily.seconds_left.saturating_sub(elapsed);
+                entry.expired = daily.seconds_left == 0;
+
+                Ok(CacheEntry::Cached(daily, entry))
+            },
+            entry => Ok(entry),
+        }
+    }
+}
+
+impl Store<DailyLevelRequest> for MemoryCache {
+    fn store(&mut self, daily: &DailyLevel, key: &DailyLevelRequest) -> Result<Entry, Error> {
+        debug!("Storing {} under key {}", daily, key);
+
+        let previous = match self.lookup_in(hash(key), |tables| &mut tables.daily_levels)? {
+            CacheEntry::Cached(previous, _) => Some(previous.index),
+            _ => None,
+        };
+
+        // The level cached for the special ID belongs to the previous daily level
+        if previous != Some(daily.index) {
+            self.tables
+                .lock()
+                .map_err(|_| Error::Poisoned)?
+                .levels
+                .remove(hash(key.level_request()));
+        }
+
+        self.store_in(hash(key), None, Some(*daily), |tables| &mut tables.daily_levels)
+    }
+
+    fn mark_absent(&mut self, key: &DailyLevelRequest) -> Result<Entry, Error> {
+        warn!("Marking DailyLevelRequest with key {} as absent!", key);
+
+        self.store_in(hash(key), None, None, |tables| &mut tables.daily_levels)
+    }
+}
+
+impl Invalidate<UploadLevelCommentRequest> for MemoryCache {
+    fn invalidate(&mut self, request: &UploadLevelCommentRequest) -> Result<(), Error> {
+        debug!("Invalidating cached comment pages of level {}", request.level_id);
+
+        self.invalidate_in(request.level_id, |tables| &mut tables.level_comments)
+    }
+}
+
+impl Invalidate<UploadProfileCommentRequest> for MemoryCache {
+    fn invalidate(&mut self, request: &UploadProfileCommentRequest) -> Result<(), Error> {
+        debug!("Invalidating cached comment pages of account {}", request.account_id);
+
+        self.invalidate_in(request.account_id, |tables| &mut tables.profile_comments)
+    }
+}
+
+impl Invalidate<SendMessageRequest> for MemoryCache {
+    fn invalidate(&mut self, request: &SendMessageRequest) -> Result<(), Error> {
+        debug!("Invalidating cached sent messages of account {}", request.account_id);
+
+        self.invalidate_in(request.account_id, |tables| &mut tables.messages)
+    }
+}
+
+impl Invalidate<LikeItemRequest> for MemoryCache {
+    fn invalidate(&mut self, request: &LikeItemRequest) -> Result<(), Error> {
+        match request.item {
+            LikedItem::Level(level_id) => self.invalidate_level(level_id),
+            LikedItem::LevelComment { level_id, .. } => self.invalidate_in(level_id, |tables| &mut tables.level_comments),
+            LikedItem::ProfileComment { account_id, .. } => self.invalidate_in(account_id, |tables| &mut tables.profile_comments),
+        }
+    }
+}
+
+impl Invalidate<RateStarsRequest> for MemoryCache {
+    fn invalidate(&mut self, request: &RateStarsRequest) -> Result<(), Error> {
+        self.invalidate_level(request.level_id)
+    }
+}
+
+impl Invalidate<RateDemonRequest> for MemoryCache {
+    fn invalidate(&mut self, request: &RateDemonRequest) -> Result<(), Error> {
+        self.invalidate_level(request.level_id)
+    }
+}
+
+impl Invalidate<UploadLevelRequest> for MemoryCache {
+    // Only updates of existing levels make cached data outdated
+    fn invalidate(&mut self, request: &UploadLevelRequest) -> Result<(), Error> {
+        match request.level.base.level_id {
+            0 => Ok(()),
+            level_id => self.invalidate_level(level_id),
+        }
+    }
+
+    fn written(&mut self, request: &UploadLevelRequest, level_id: &u64) -> Result<(), Error> {
+        self.invalidate(request)?;
+
+        let mut level = request.level.clone();
+        level.base.level_id = *level_id;
+
+        debug!("Storing uploaded level {}", level);
+
+        self.store(&level, &LevelRequest::new(*level_id)).map(|_| ())
+    }
+}
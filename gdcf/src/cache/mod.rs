@@ -0,0 +1,204 @@
+//! Module containing cache related traits/structs
+
+use crate::{api::request::Request, error::CacheError};
+use chrono::{Duration, NaiveDateTime, Utc};
+use derive_more::Display;
+use gdcf_model::{song::NewgroundsSong, user::Creator};
+use std::fmt::{Display, Formatter};
+
+pub mod memory;
+
+pub trait Cache: Clone + Send + Sync + 'static {
+    type CacheEntryMeta: CacheEntryMeta;
+    type Err: CacheError;
+}
+
+#[derive(Debug, Display)]
+pub struct NewgroundsSongKey(pub u64);
+
+#[derive(Debug, Display)]
+pub struct CreatorKey(pub u64);
+
+pub trait Key {
+    type Result;
+}
+
+impl<R: Request> Key for R {
+    type Result = <R as Request>::Result;
+}
+
+impl Key for NewgroundsSongKey {
+    type Result = NewgroundsSong;
+}
+
+impl Key for CreatorKey {
+    type Result = Creator;
+}
+
+pub trait Lookup<K: Key>: Cache {
+    // TODO: maybe an exists method?
+    fn lookup(&self, key: &K) -> Result<CacheEntry<K::Result, Self::CacheEntryMeta>, Self::Err>;
+}
+
+pub trait Store<K: Key>: Cache {
+    fn store(&mut self, obj: &K::Result, key: &K) -> Result<Self::CacheEntryMeta, Self::Err>;
+    fn mark_absent(&mut self, key: &K) -> Result<Self::CacheEntryMeta, Self::Err>;
+}
+
+// FIXME: One they are stabilized, use a trait alias here
+pub trait CanCache<K: Key>: Lookup<K> + Store<K> {}
+
+impl<K: Key, C: Cache> CanCache<K> for C where C: Store<K> + Lookup<K> {}
+
+/// Trait for caches that know which of their entries are made outdated by a successful write
+/// request, such as posting a comment
+pub trait Invalidate<R: Request>: Cache {
+    /// Removes all entries the given request, which has just been successfully made, invalidated
+    fn invalidate(&mut self, request: &R) -> Result<(), Self::Err>;
+
+    /// Updates the cache after the given request has been successfully made and the servers
+    /// responded with the given result.
+    ///
+    /// Most write requests only make existing entries outdated, so by default this simply
+    /// [invalidates](Invalidate::invalidate) them. Requests creating new objects, such as level
+    /// uploads, can additionally store these objects here.
+    fn written(&mut self, request: &R, _result: &R::Result) -> Result<(), Self::Err> {
+        self.invalidate(request)
+    }
+}
+
+/// Struct modelling the result of some GDCF request
+#[derive(Debug, PartialEq, Clone)]
+pub enum CacheEntry<T, Meta: CacheEntryMeta> {
+    /// Variant to return if there was no entry at all in the cache regarding a specific request,
+    /// meaning the request hasn't been done yet ever
+    Missing,
+
+    /// Variant indicating that a request was already made previously, but the server indicated
+    /// returned an empty response
+    MarkedAbsent(Meta),
+
+    /// Variant indicating that a request was already made, and its results were stored.
+    Cached(T, Meta),
+
+    /// Variant indicating that the stored results of a request were outdated, but refreshing them
+    /// failed. Contains the outdated results and a description of why the refresh failed.
+    ///
+    /// Caches never produce this variant, it is only returned by GDCF if it is configured to fall
+    /// back to outdated data on errors (see [`Gdcf::stale_on_error`](crate::Gdcf::stale_on_error)).
+    Stale(T, Meta, String),
+}
+
+impl<T: Display, Meta: CacheEntryMeta + Display> Display for CacheEntry<T, Meta> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CacheEntry::Missing => write!(f, "Cache entry missing"),
+            CacheEntry::MarkedAbsent(meta) => write!(f, "{} marked as missing due to empty server response", meta),
+            CacheEntry::Cached(object, meta) => write!(f, "Cached {}, {}", object, meta),
+            CacheEntry::Stale(object, meta, reason) => write!(f, "Stale {}, {} (refresh failed: {})", object, meta, reason),
+        }
+    }
+}
+
+impl<T, Meta: CacheEntryMeta> CacheEntry<T, Meta> {
+    pub fn is_expired(&self) -> bool {
+        match self {
+            CacheEntry::Missing => true,
+            CacheEntry::MarkedAbsent(meta) | CacheEntry::Cached(_, meta) | CacheEntry::Stale(_, meta, _) => meta.is_expired(),
+        }
+    }
+
+    pub fn is_absent(&self) -> bool {
+        match self {
+            CacheEntry::Cached(..) | CacheEntry::Stale(..) => false,
+            _ => true,
+        }
+    }
+
+    /// Whether this entry contains outdated data because refreshing it failed
+    pub fn is_stale(&self) -> bool {
+        match self {
+            CacheEntry::Stale(..) => true,
+            _ => false,
+        }
+    }
+
+    /// The metadata of this entry, or [`None`] if the entry is missing
+    pub fn meta(&self) -> Option<&Meta> {
+        match self {
+            CacheEntry::Missing => None,
+            CacheEntry::MarkedAbsent(meta) | CacheEntry::Cached(_, meta) | CacheEntry::Stale(_, meta, _) => Some(meta),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> CacheEntry<U, Meta> {
+        match self {
+            CacheEntry::Missing => CacheEntry::Missing,
+            CacheEntry::MarkedAbsent(absent_meta) => CacheEntry::MarkedAbsent(absent_meta),
+            CacheEntry::Cached(object, meta) => CacheEntry::Cached(f(object), meta),
+            CacheEntry::Stale(object, meta, reason) => CacheEntry::Stale(f(object), meta, reason),
+        }
+    }
+
+    /// Changes the type of object this entry would contain. Must only be called on entries that
+    /// don't contain an object, i.e. neither on [`CacheEntry::Cached`] nor on [`CacheEntry::Stale`]
+    pub(crate) fn map_empty<U>(self) -> CacheEntry<U, Meta> {
+        self.map(|_| panic!("CacheEntry::map_empty called on entry containing an object (`Cached` or `Stale`)"))
+    }
+}
+
+pub trait CacheEntryMeta: Clone + std::fmt::Debug + Copy + Send + Sync + 'static {
+    fn is_expired(&self) -> bool;
+    fn is_absent(&self) -> bool;
+
+    /// The point in time (in UTC) at which the entry was cached
+    fn cached_at(&self) -> NaiveDateTime;
+}
+
+/// Enum determining how GDCF balances between cached data and requests made to the API when
+/// processing a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Use cached data if it is up-to-date, and only make a request if it's outdated or missing.
+    ///
+    /// This is the default policy.
+    CacheFirst,
+
+    /// Always make a request, regardless of whether cached data is up-to-date.
+    Refresh,
+
+    /// Never make any requests. Outdated data is returned as is, and missing data results in
+    /// [`CacheEntry::Missing`].
+    ///
+    /// When upgrading, optional parts of an object (such as the song or creator of a level) that
+    /// aren't cached are treated as if the servers hadn't provided them, so the cached object is
+    /// still returned.
+    CacheOnly,
+
+    /// Always make a request, but fall back to cached data (even if it's outdated) if that request
+    /// fails with an API error
+    NetworkFirst,
+
+    /// Like [`CachePolicy::CacheFirst`], but consider cached data outdated if it was cached longer
+    /// ago than the given duration, instead of using the cache's expiry settings
+    MaxAge(Duration),
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        CachePolicy::CacheFirst
+    }
+}
+
+impl CachePolicy {
+    /// Whether the cached data described by the given [`CacheEntryMeta`] needs to be refreshed
+    /// under this policy
+    pub fn needs_refresh(&self, meta: &impl CacheEntryMeta) -> bool {
+        match self {
+            CachePolicy::CacheFirst => meta.is_expired(),
+            CachePolicy::Refresh | CachePolicy::NetworkFirst => true,
+            CachePolicy::CacheOnly => false,
+            CachePolicy::MaxAge(max_age) => Utc::now().naive_utc() - meta.cached_at() > *max_age,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Module containing the various error types used by gdcf
 
 use failure::Fail;
+use std::sync::Arc;
 
 pub trait ApiError: Fail {
     fn is_no_result(&self) -> bool;
@@ -16,6 +17,26 @@ pub enum Error<A: ApiError, C: CacheError> {
     #[fail(display = "{}", _0)]
     Api(#[cause] A),
 
+    /// An identical request was being made concurrently, and failed with the contained error.
+    ///
+    /// API errors aren't necessarily cloneable, so the error is shared between everyone who waited
+    /// for that request. Whether a failed request results in this variant or in [`Error::Api`]
+    /// depends on timing, so callers interested in the API error should use [`Error::api_error`]
+    /// instead of matching on either variant.
+    #[fail(display = "{}", _0)]
+    Coalesced(Arc<A>),
+
     #[fail(display = "GDCF made an assumption about server sided data consistency, which was violated. Please open a bug report")]
     UnexpectedlyAbsent,
 }
+
+impl<A: ApiError, C: CacheError> Error<A, C> {
+    /// The API error that caused this error, if any
+    pub fn api_error(&self) -> Option<&A> {
+        match self {
+            Error::Api(api_error) => Some(api_error),
+            Error::Coalesced(api_error) => Some(api_error),
+            _ => None,
+        }
+    }
+}
@@ -2,12 +2,18 @@
 
 use futures::Future;
 
-use crate::{api::ApiClient, cache::Cache, error::Error, future::stream::GdcfStream};
+use crate::{
+    api::{request::Pagination, ApiClient},
+    cache::Cache,
+    error::Error,
+    future::stream::GdcfStream,
+};
 
 pub mod process;
 pub(crate) mod refresh;
 pub mod stream;
 pub mod upgrade;
+pub mod write;
 
 pub trait PeekableFuture: Future + Sized {
     fn peek<F: FnOnce(Self::Item) -> Result<Self::Item, Self::Error>>(self, f: F) -> Result<Self, Self::Error>;
@@ -19,7 +25,22 @@ pub trait CloneablePeekFuture: PeekableFuture {
 }
 
 pub trait StreamableFuture<A: ApiClient, C: Cache>: Future<Error = Error<A::Err, C::Err>> + Sized {
-    fn next(self) -> Result<Self, Self::Error>;
+    /// Constructs the future for the page following the one this future is for
+    fn next(&self) -> Result<Self, Self::Error>;
+
+    /// Whether the given page, which this future resolved to, indicates that there are no further
+    /// pages
+    fn is_exhausted(page: &Self::Item) -> bool;
+
+    /// The amount of objects on the given page, which this future resolved to, if pages of this
+    /// type can hold a varying amount of objects
+    fn object_count(page: &Self::Item) -> Option<usize>;
+
+    /// The pagination information the servers provided for the page this future resolved to.
+    ///
+    /// Only available after the future resolved, and only if the page was actually requested from
+    /// the servers (as opposed to being taken from the cache)
+    fn pagination(&self) -> Option<Pagination>;
 
     fn stream(self) -> GdcfStream<A, C, Self> {
         GdcfStream::new(self)
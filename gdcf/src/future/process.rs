@@ -1,15 +1,20 @@
 use futures::{Async, Future};
-use log::trace;
+use log::{trace, warn};
 
 use crate::{
     api::{
         client::MakeRequest,
-        request::{PaginatableRequest, Request},
+        request::{PaginatableRequest, Pagination, Request},
         ApiClient,
     },
-    cache::{Cache, CacheEntry, CanCache, CreatorKey, Lookup, NewgroundsSongKey, Store},
+    cache::{Cache, CacheEntry, CachePolicy, CanCache, CreatorKey, Lookup, NewgroundsSongKey, Store},
     error::Error,
-    future::{refresh::RefreshCacheFuture, upgrade::UpgradeFuture, CloneablePeekFuture, PeekableFuture, StreamableFuture},
+    future::{
+        refresh::RefreshCacheFuture,
+        stream::Page,
+        upgrade::UpgradeFuture,
+        CloneablePeekFuture, PeekableFuture, StreamableFuture,
+    },
     upgrade::Upgradable,
     Gdcf,
 };
@@ -21,7 +26,7 @@ where
     Req: Request,
 {
     gdcf: Gdcf<A, C>,
-    forces_refresh: bool,
+    policy: CachePolicy,
     state: ProcessRequestFutureState<Req, A, C>,
 }
 
@@ -47,10 +52,10 @@ where
     C: Cache + Store<CreatorKey> + Store<NewgroundsSongKey> + CanCache<Req>,
     Req: Request,
 {
-    pub(crate) fn new(gdcf: Gdcf<A, C>, request: Req, forces_refresh: bool) -> Result<Self, C::Err> {
+    pub(crate) fn new(gdcf: Gdcf<A, C>, request: Req, policy: CachePolicy) -> Result<Self, C::Err> {
         Ok(ProcessRequestFuture {
-            forces_refresh,
-            state: gdcf.process(request, forces_refresh)?,
+            policy,
+            state: gdcf.process(request, policy)?,
             gdcf,
         })
     }
@@ -61,18 +66,30 @@ where
     A: ApiClient + MakeRequest<Req>,
     C: Cache + Store<CreatorKey> + Store<NewgroundsSongKey> + CanCache<Req>,
     Req: PaginatableRequest,
+    Req::Result: Page,
 {
-    fn next(self) -> Result<Self, Self::Error> {
-        let mut request = match self.state {
-            ProcessRequestFutureState::UpToDate(_, request) => request,
-            ProcessRequestFutureState::Outdated(_, future) | ProcessRequestFutureState::Uncached(future) => future.request,
+    fn next(&self) -> Result<Self, Self::Error> {
+        let mut request = match &self.state {
+            ProcessRequestFutureState::UpToDate(_, request) => request.clone(),
+            ProcessRequestFutureState::Outdated(_, future) | ProcessRequestFutureState::Uncached(future) => future.request.clone(),
         };
         request.next();
-        Ok(ProcessRequestFuture {
-            forces_refresh: self.forces_refresh,
-            state: self.gdcf.process(request, self.forces_refresh).map_err(Error::Cache)?,
-            gdcf: self.gdcf,
-        })
+        ProcessRequestFuture::new(self.gdcf.clone(), request, self.policy).map_err(Error::Cache)
+    }
+
+    fn is_exhausted(page: &Self::Item) -> bool {
+        Req::Result::is_exhausted(page)
+    }
+
+    fn object_count(page: &Self::Item) -> Option<usize> {
+        Req::Result::object_count(page)
+    }
+
+    fn pagination(&self) -> Option<Pagination> {
+        match &self.state {
+            ProcessRequestFutureState::Outdated(_, future) | ProcessRequestFutureState::Uncached(future) => future.pagination(),
+            ProcessRequestFutureState::UpToDate(..) => None,
+        }
     }
 }
 
@@ -85,7 +102,7 @@ where
     fn peek<F: FnOnce(Self::Item) -> Result<Self::Item, Self::Error>>(self, f: F) -> Result<Self, Self::Error> {
         let ProcessRequestFuture {
             gdcf,
-            forces_refresh,
+            policy,
             state,
         } = self;
 
@@ -103,7 +120,7 @@ where
         Ok(ProcessRequestFuture {
             state,
             gdcf,
-            forces_refresh,
+            policy,
         })
     }
 
@@ -128,7 +145,19 @@ where
         match &mut self.state {
             ProcessRequestFutureState::UpToDate(None, _) => panic!("Future already polled to completion"),
             ProcessRequestFutureState::Uncached(future) => future.poll(),
-            ProcessRequestFutureState::Outdated(_, future) => future.poll(),
+            ProcessRequestFutureState::Outdated(cached, future) =>
+                match future.poll() {
+                    Err(error) if error.api_error().is_some() && self.gdcf.falls_back_to_stale(self.policy) =>
+                        match std::mem::replace(cached, CacheEntry::Missing) {
+                            CacheEntry::Cached(object, meta) | CacheEntry::Stale(object, meta, _) => {
+                                warn!("Refreshing cache entry failed: {}. Falling back to cached data", error);
+
+                                Ok(Async::Ready(CacheEntry::Stale(object, meta, error.to_string())))
+                            },
+                            _ => Err(error),
+                        },
+                    result => result,
+                },
             ProcessRequestFutureState::UpToDate(cache_entry, _) => Ok(Async::Ready(cache_entry.take().unwrap())),
         }
     }
@@ -142,7 +171,7 @@ where
 {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         fmt.debug_struct("ProcessRequestFuture")
-            .field("forces_refresh", &self.forces_refresh)
+            .field("policy", &self.policy)
             .field("state", &self.state)
             .finish()
     }
@@ -187,7 +216,7 @@ where
         A: MakeRequest<<Req::Result as Upgradable<Into>>::Request>,
         C: CanCache<<Req::Result as Upgradable<Into>>::Request> + Lookup<<Req::Result as Upgradable<Into>>::LookupKey>,
     {
-        UpgradeFuture::new(self.gdcf.clone(), self.forces_refresh, self)
+        UpgradeFuture::new(self.gdcf.clone(), self.policy, self)
     }
 }
 
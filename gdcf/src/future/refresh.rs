@@ -1,15 +1,151 @@
 use crate::{
     api::{
         client::{MakeRequest, Response},
-        request::Request,
+        request::{Pagination, Request},
         ApiClient,
     },
-    cache::{Cache, CacheEntry, CanCache, CreatorKey, NewgroundsSongKey, Store},
+    cache::{Cache, CacheEntry, CanCache, CreatorKey, Lookup, NewgroundsSongKey, Store},
     error::{ApiError, Error},
     Gdcf, Secondary,
 };
-use futures::{Async, Future};
+use futures::{
+    task::{self, Task},
+    Async, Future,
+};
 use log::{info, warn};
+use std::{
+    any::{Any, TypeId},
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::Hasher,
+    sync::{Arc, Mutex},
+};
+
+/// Identifies a request by its type and its hash
+type RequestKey = (TypeId, u64);
+
+fn request_key<R: Request>(request: &R) -> RequestKey {
+    let mut state = DefaultHasher::new();
+
+    request.hash(&mut state);
+
+    (TypeId::of::<R>(), state.finish())
+}
+
+/// How an in-flight request ended
+#[derive(Debug)]
+enum Outcome {
+    /// The request succeeded and its result has been stored in the cache. Contains the pagination
+    /// information of the response, since that isn't cached
+    Succeeded(Option<Pagination>),
+
+    /// The request failed with an API error. Contains an `Arc` of the error, boxed since the
+    /// [`InFlight`] registry isn't generic over the [`ApiClient`]
+    Failed(Box<dyn Any + Send>),
+
+    /// The request didn't complete, either because the future making it was dropped or because
+    /// its result couldn't be stored. Everyone waiting for it has to try again
+    Abandoned,
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    /// How the request ended, or [`None`] if it is still pending
+    outcome: Option<Outcome>,
+
+    /// The amount of futures that joined the request instead of making it themselves
+    followers: usize,
+
+    /// Tasks waiting for the request to complete
+    waiting: Vec<Task>,
+}
+
+/// Registry of the requests a [`Gdcf`] instance (and its clones) is currently waiting on the API
+/// for
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InFlight {
+    requests: Arc<Mutex<HashMap<RequestKey, Arc<Mutex<Slot>>>>>,
+}
+
+impl InFlight {
+    /// Registers the given request as in-flight, unless an identical request already is.
+    ///
+    /// Returns a [`Lead`] if the caller is responsible for actually making the request, or the slot
+    /// of the identical request otherwise
+    fn join<R: Request>(&self, request: &R) -> Result<Lead, Arc<Mutex<Slot>>> {
+        let key = request_key(request);
+        let mut requests = self.requests.lock().unwrap();
+
+        if let Some(slot) = requests.get(&key) {
+            slot.lock().unwrap().followers += 1;
+
+            return Err(Arc::clone(slot))
+        }
+
+        let slot = Arc::new(Mutex::new(Slot::default()));
+
+        requests.insert(key, Arc::clone(&slot));
+
+        Ok(Lead {
+            in_flight: self.clone(),
+            key,
+            slot,
+            outcome: Outcome::Abandoned,
+        })
+    }
+}
+
+/// Held by whoever is making an in-flight request. Once dropped, the request is removed from the
+/// [`InFlight`] registry and everyone waiting for it is notified.
+#[derive(Debug)]
+struct Lead {
+    in_flight: InFlight,
+    key: RequestKey,
+    slot: Arc<Mutex<Slot>>,
+    outcome: Outcome,
+}
+
+impl Lead {
+    /// Removes the request from the [`InFlight`] registry, so that no further futures can join it,
+    /// and returns how many futures already have
+    fn close(&self) -> usize {
+        let mut requests = self.in_flight.requests.lock().unwrap();
+
+        // Once closed, an identical request might have been registered by someone else
+        if requests.get(&self.key).map_or(false, |slot| Arc::ptr_eq(slot, &self.slot)) {
+            requests.remove(&self.key);
+        }
+
+        self.slot.lock().unwrap().followers
+    }
+}
+
+impl Drop for Lead {
+    fn drop(&mut self) {
+        self.close();
+
+        let mut slot = self.slot.lock().unwrap();
+
+        slot.outcome = Some(std::mem::replace(&mut self.outcome, Outcome::Abandoned));
+
+        for task in slot.waiting.drain(..) {
+            task.notify()
+        }
+    }
+}
+
+enum RefreshState<Req, A>
+where
+    Req: Request,
+    A: ApiClient + MakeRequest<Req>,
+{
+    /// We are making the request ourselves
+    Leading(<A as MakeRequest<Req>>::Future, Lead),
+
+    /// An identical request is already being made, we are waiting for it to complete
+    Following(Arc<Mutex<Slot>>),
+
+    Done,
+}
 
 pub(crate) struct RefreshCacheFuture<Req, A, C>
 where
@@ -17,8 +153,11 @@ where
     A: ApiClient + MakeRequest<Req>,
     C: Cache + Store<CreatorKey> + Store<NewgroundsSongKey> + CanCache<Req>,
 {
-    inner: <A as MakeRequest<Req>>::Future,
+    state: RefreshState<Req, A>,
+    client: A,
+    in_flight: InFlight,
     cache: C,
+    pagination: Option<Pagination>,
     pub(super) request: Req,
 }
 
@@ -40,14 +179,67 @@ where
     C: Cache + Store<CreatorKey> + Store<NewgroundsSongKey> + CanCache<Req>,
 {
     pub(crate) fn new(gdcf: &Gdcf<A, C>, request: Req) -> Self {
-        info!("Performing refresh on request {:?}", request);
-
         RefreshCacheFuture {
-            inner: gdcf.client().make(&request),
+            state: start(&gdcf.client, &gdcf.in_flight, &request),
+            client: gdcf.client(),
+            in_flight: gdcf.in_flight.clone(),
             cache: gdcf.cache(),
+            pagination: None,
             request,
         }
     }
+
+    /// The pagination information the response to our request contained, if any
+    pub(crate) fn pagination(&self) -> Option<Pagination> {
+        self.pagination
+    }
+
+    fn handle_response(&mut self, response: Response<Req::Result>) -> Result<CacheEntry<Req::Result, C::CacheEntryMeta>, C::Err> {
+        let what_we_want = match response {
+            Response::Exact(what_we_want) => what_we_want,
+            Response::More(what_we_want, excess) => {
+                for object in &excess {
+                    match object {
+                        Secondary::NewgroundsSong(song) => self.cache.store(song, &NewgroundsSongKey(song.song_id)),
+                        Secondary::Creator(creator) => self.cache.store(creator, &CreatorKey(creator.user_id)),
+                        Secondary::MissingCreator(cid) => Store::<CreatorKey>::mark_absent(&mut self.cache, &CreatorKey(*cid)),
+                        Secondary::MissingNewgroundsSong(nid) =>
+                            Store::<NewgroundsSongKey>::mark_absent(&mut self.cache, &NewgroundsSongKey(*nid)),
+                        Secondary::Pagination(pagination) => {
+                            self.pagination = Some(*pagination);
+
+                            continue
+                        },
+                    }?;
+                }
+
+                what_we_want
+            },
+        };
+
+        self.cache
+            .store(&what_we_want, &self.request)
+            .map(|entry_info| CacheEntry::Cached(what_we_want, entry_info))
+    }
+}
+
+fn start<Req, A>(client: &A, in_flight: &InFlight, request: &Req) -> RefreshState<Req, A>
+where
+    Req: Request,
+    A: ApiClient + MakeRequest<Req>,
+{
+    match in_flight.join(request) {
+        Ok(lead) => {
+            info!("Performing refresh on request {:?}", request);
+
+            RefreshState::Leading(client.make(request), lead)
+        },
+        Err(slot) => {
+            info!("Identical request to {:?} already in flight, waiting for it to complete", request);
+
+            RefreshState::Following(slot)
+        },
+    }
 }
 
 impl<Req, A, C> Future for RefreshCacheFuture<Req, A, C>
@@ -60,43 +252,88 @@ where
     type Item = CacheEntry<Req::Result, C::CacheEntryMeta>;
 
     fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
-        match self.inner.poll() {
-            Ok(Async::NotReady) => Ok(Async::NotReady),
-            Err(ref api_error) if api_error.is_no_result() => {
-                // TODO: maybe mark malformed data as absent as well
+        loop {
+            let result = match &mut self.state {
+                RefreshState::Done => panic!("Future already polled to completion"),
+                RefreshState::Following(slot) => {
+                    let slot = Arc::clone(slot);
+                    let mut slot = slot.lock().unwrap();
 
-                warn!("Request yielded no result, marking as absent");
+                    match slot.outcome {
+                        None => {
+                            slot.waiting.push(task::current());
 
-                Store::<Req>::mark_absent(&mut self.cache, &self.request)
-                    .map(|entry_info| Async::Ready(CacheEntry::MarkedAbsent(entry_info)))
-                    .map_err(Error::Cache)
-            },
-            Err(api_error) => Err(Error::Api(api_error)),
-            Ok(Async::Ready(response)) =>
-                match response {
-                    Response::Exact(what_we_want) =>
-                        self.cache
-                            .store(&what_we_want, &self.request)
-                            .map(|entry_info| Async::Ready(CacheEntry::Cached(what_we_want, entry_info)))
-                            .map_err(Error::Cache),
-                    Response::More(what_we_want, excess) => {
-                        for object in &excess {
-                            match object {
-                                Secondary::NewgroundsSong(song) => self.cache.store(song, &NewgroundsSongKey(song.song_id)),
-                                Secondary::Creator(creator) => self.cache.store(creator, &CreatorKey(creator.user_id)),
-                                Secondary::MissingCreator(cid) => Store::<CreatorKey>::mark_absent(&mut self.cache, &CreatorKey(*cid)),
-                                Secondary::MissingNewgroundsSong(nid) =>
-                                    Store::<NewgroundsSongKey>::mark_absent(&mut self.cache, &NewgroundsSongKey(*nid)),
-                            }
-                            .map_err(Error::Cache)?;
-                        }
-
-                        self.cache
-                            .store(&what_we_want, &self.request)
-                            .map(|entry_info| Async::Ready(CacheEntry::Cached(what_we_want, entry_info)))
-                            .map_err(Error::Cache)
-                    },
+                            return Ok(Async::NotReady)
+                        },
+                        Some(Outcome::Failed(ref api_error)) => {
+                            let api_error = api_error
+                                .downcast_ref::<Arc<A::Err>>()
+                                .expect("Identical request failed with error of different type");
+
+                            self.state = RefreshState::Done;
+
+                            return Err(Error::Coalesced(Arc::clone(api_error)))
+                        },
+                        // The request we waited for stored its result in the cache, so we can simply
+                        // look it up. Since cache results aren't necessarily cloneable, this is the only
+                        // way to share them
+                        Some(Outcome::Succeeded(pagination)) =>
+                            match Lookup::<Req>::lookup(&self.cache, &self.request).map_err(Error::Cache)? {
+                                CacheEntry::Missing => (),
+                                entry => {
+                                    self.pagination = pagination;
+                                    self.state = RefreshState::Done;
+
+                                    return Ok(Async::Ready(entry))
+                                },
+                            },
+                        Some(Outcome::Abandoned) => (),
+                    }
+
+                    warn!("Identical request to {:?} did not provide a result, retrying it", self.request);
+
+                    self.state = start(&self.client, &self.in_flight, &self.request);
+
+                    continue
                 },
+                RefreshState::Leading(inner, lead) =>
+                    match inner.poll() {
+                        Ok(Async::NotReady) => return Ok(Async::NotReady),
+                        Err(ref api_error) if api_error.is_no_result() => {
+                            // TODO: maybe mark malformed data as absent as well
+
+                            warn!("Request yielded no result, marking as absent");
+
+                            Store::<Req>::mark_absent(&mut self.cache, &self.request)
+                                .map(CacheEntry::MarkedAbsent)
+                                .map_err(Error::Cache)
+                        },
+                        // API errors aren't cloneable, so if other futures are waiting for this
+                        // request, we have to share the error with them
+                        Err(api_error) =>
+                            if lead.close() == 0 {
+                                Err(Error::Api(api_error))
+                            } else {
+                                let api_error = Arc::new(api_error);
+
+                                lead.outcome = Outcome::Failed(Box::new(Arc::clone(&api_error)));
+
+                                Err(Error::Coalesced(api_error))
+                            },
+                        Ok(Async::Ready(response)) => self.handle_response(response).map_err(Error::Cache),
+                    },
+            };
+
+            // Dropping the lead notifies everyone waiting for this request
+            if let RefreshState::Leading(_, ref mut lead) = self.state {
+                if result.is_ok() {
+                    lead.outcome = Outcome::Succeeded(self.pagination);
+                }
+            }
+
+            self.state = RefreshState::Done;
+
+            return result.map(Async::Ready)
         }
     }
 }
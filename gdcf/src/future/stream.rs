@@ -1,56 +1,289 @@
 use crate::{
-    api::ApiClient,
-    cache::Cache,
-    error::{ApiError, Error},
+    api::{request::Pagination, ApiClient},
+    cache::{Cache, CacheEntry, CacheEntryMeta},
+    error::ApiError,
     future::StreamableFuture,
 };
-use futures::{Async, Stream};
-use std::marker::PhantomData;
+use futures::{Async, Future, Stream};
+use gdcf_model::{
+    level::Level,
+    message::Message,
+    user::{SearchedUser, User},
+};
+use log::info;
+use std::{collections::VecDeque, marker::PhantomData};
+
+/// Trait for the results of [`PaginatableRequest`](crate::api::request::PaginatableRequest)s,
+/// deciding when a [`GdcfStream`] over them has reached its end
+pub trait Page: Sized {
+    /// Whether the given page indicates that there are no further pages
+    fn is_exhausted<Meta: CacheEntryMeta>(page: &CacheEntry<Self, Meta>) -> bool;
+
+    /// The amount of objects on the given page, if pages of this type can hold a varying amount of
+    /// objects.
+    ///
+    /// Used to detect the last page if the servers don't tell us how many objects fit onto a page
+    fn object_count<Meta: CacheEntryMeta>(_: &CacheEntry<Self, Meta>) -> Option<usize> {
+        None
+    }
+}
+
+/// Pages of objects end once we receive a page without any objects. Since the servers respond to
+/// requests for such pages with `-1`, these pages are generally marked as absent.
+impl<T> Page for Vec<T> {
+    fn is_exhausted<Meta: CacheEntryMeta>(page: &CacheEntry<Self, Meta>) -> bool {
+        match page {
+            CacheEntry::Cached(objects, _) | CacheEntry::Stale(objects, ..) => objects.is_empty(),
+            CacheEntry::MarkedAbsent(_) | CacheEntry::Missing => true,
+        }
+    }
+
+    fn object_count<Meta: CacheEntryMeta>(page: &CacheEntry<Self, Meta>) -> Option<usize> {
+        match page {
+            CacheEntry::Cached(objects, _) | CacheEntry::Stale(objects, ..) => Some(objects.len()),
+            CacheEntry::MarkedAbsent(_) | CacheEntry::Missing => None,
+        }
+    }
+}
+
+// For single objects, the "pages" are ID ranges which are allowed to have holes in them (e.g. deleted
+// levels), so we never know when we've reached the end
+impl Page for User {
+    fn is_exhausted<Meta: CacheEntryMeta>(_: &CacheEntry<Self, Meta>) -> bool {
+        false
+    }
+}
+
+impl Page for SearchedUser {
+    fn is_exhausted<Meta: CacheEntryMeta>(_: &CacheEntry<Self, Meta>) -> bool {
+        false
+    }
+}
+
+impl Page for Message {
+    fn is_exhausted<Meta: CacheEntryMeta>(_: &CacheEntry<Self, Meta>) -> bool {
+        false
+    }
+}
+
+impl<S, U> Page for Level<S, U> {
+    fn is_exhausted<Meta: CacheEntryMeta>(_: &CacheEntry<Self, Meta>) -> bool {
+        false
+    }
+}
+
+/// A page of a [`GdcfStream`] that has been requested, but not yet yielded
+struct Prefetched<F: Future> {
+    /// Kept around after it resolved, since it knows the pagination information of the page and
+    /// how to construct the future for the following page
+    future: F,
+    result: Option<Result<F::Item, F::Error>>,
+}
+
+impl<F: Future> Prefetched<F> {
+    fn new(future: F) -> Self {
+        Prefetched { future, result: None }
+    }
+
+    fn drive(&mut self) {
+        if self.result.is_none() {
+            match self.future.poll() {
+                Ok(Async::NotReady) => (),
+                Ok(Async::Ready(page)) => self.result = Some(Ok(page)),
+                Err(err) => self.result = Some(Err(err)),
+            }
+        }
+    }
+}
 
-#[derive(Debug)]
 pub struct GdcfStream<A: ApiClient, C: Cache, F: StreamableFuture<A, C>> {
-    current_future: Option<F>,
+    /// The pages we have requested but not yet yielded, in order
+    pending: VecDeque<Prefetched<F>>,
+
+    /// How many pages past the one we're currently waiting for may be in flight at once
+    prefetch: usize,
+
+    /// The most recent pagination information we received from the servers
+    pagination: Option<Pagination>,
+
+    /// The zero-based index of the page we most recently yielded, if known
+    page: Option<u32>,
+
+    /// The most objects we have seen on a single page. If the servers don't tell us how many pages
+    /// there are, a page with fewer objects than this is the last one
+    largest_page: usize,
     _phantom: PhantomData<(A, C)>,
 }
 
+impl<A: ApiClient, C: Cache, F: StreamableFuture<A, C>> std::fmt::Debug for GdcfStream<A, C, F> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("GdcfStream")
+            .field("pending", &self.pending.len())
+            .field("prefetch", &self.prefetch)
+            .field("pagination", &self.pagination)
+            .field("page", &self.page)
+            .field("largest_page", &self.largest_page)
+            .finish()
+    }
+}
+
 impl<A: ApiClient, C: Cache, F: StreamableFuture<A, C>> GdcfStream<A, C, F> {
     pub(crate) fn new(future: F) -> Self {
+        let mut pending = VecDeque::new();
+
+        pending.push_back(Prefetched::new(future));
+
         GdcfStream {
-            current_future: Some(future),
+            pending,
+            prefetch: 0,
+            pagination: None,
+            page: None,
+            largest_page: 0,
             _phantom: PhantomData,
         }
     }
+
+    /// Makes this stream request up to `pages` pages in advance, instead of only requesting a page
+    /// once the previous one has been yielded.
+    ///
+    /// Pages are still yielded in order. Once the stream learns that it has reached its end,
+    /// requests for pages past the end are cancelled.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+
+    /// The total amount of pages this stream goes over.
+    ///
+    /// Only known once a page has been requested from the servers and they included pagination
+    /// information, including their page size, in their response. Pages taken from the cache don't
+    /// contain this information.
+    pub fn total_pages(&self) -> Option<u32> {
+        self.pagination.and_then(|pagination| pagination.total_pages())
+    }
+
+    /// The total amount of objects across all pages this stream goes over.
+    ///
+    /// Only known once a page has been requested from the servers and they included pagination
+    /// information in their response
+    pub fn total_items(&self) -> Option<u32> {
+        self.pagination.map(|pagination| pagination.total)
+    }
+
+    /// How many pages follow the one we most recently yielded, according to the pagination
+    /// information we received
+    fn remaining_pages(&self) -> Option<usize> {
+        match (self.total_pages(), self.page) {
+            (Some(total_pages), Some(page)) => Some(total_pages.saturating_sub(page + 1) as usize),
+            _ => None,
+        }
+    }
+
+    /// Requests further pages until `prefetch` pages past the next one are pending, without going
+    /// past the last page
+    fn fill(&mut self) -> Result<(), F::Error> {
+        let mut wanted = self.prefetch + 1;
+
+        if let Some(remaining) = self.remaining_pages() {
+            wanted = wanted.min(remaining);
+            self.pending.truncate(remaining);
+        }
+
+        while self.pending.len() < wanted {
+            let next = match self.pending.back() {
+                Some(last) => last.future.next()?,
+                None => break,
+            };
+
+            self.pending.push_back(Prefetched::new(next))
+        }
+
+        Ok(())
+    }
 }
 
-// FIXME: figure out a way to terminate these streams if our Item is a collection type (like Vec<T>)
-// and we receive an empty collection
 impl<A: ApiClient, C: Cache, F: StreamableFuture<A, C>> Stream for GdcfStream<A, C, F> {
     type Error = F::Error;
     type Item = F::Item;
 
     fn poll(&mut self) -> Result<Async<Option<Self::Item>>, Self::Error> {
-        if let Some(ref mut current_future) = self.current_future {
-            match current_future.poll() {
-                Ok(Async::NotReady) => Ok(Async::NotReady),
+        self.fill()?;
 
-                Ok(Async::Ready(page)) => {
-                    // We cannot move out of borrowed context, which means we have to "trick" rust into allowing us to
-                    // swap out the futures by using an Option
-                    self.current_future = self.current_future.take().map(|current_future| current_future.next()).transpose()?;
+        for prefetched in &mut self.pending {
+            prefetched.drive()
+        }
 
-                    Ok(Async::Ready(Some(page)))
-                },
+        let (future, result) = match self.pending.pop_front() {
+            None => return Ok(Async::Ready(None)),
+            Some(Prefetched {
+                future,
+                result: Some(result),
+            }) => (future, result),
+            Some(polling) => {
+                self.pending.push_front(polling);
 
-                Err(Error::Api(ref err)) if err.is_no_result() => {
-                    //info!("Stream over request {} terminating due to exhaustion!", self.request);
+                return Ok(Async::NotReady)
+            },
+        };
 
-                    Ok(Async::Ready(None))
-                },
+        match result {
+            Ok(page) => {
+                if F::is_exhausted(&page) {
+                    info!("Stream terminating due to receiving an empty page");
 
-                Err(err) => Err(err),
-            }
-        } else {
-            Ok(Async::Ready(None))
+                    self.pending.clear();
+
+                    return Ok(Async::Ready(None))
+                }
+
+                // Pages taken from the cache don't tell us where they are located, but we know
+                // they directly follow the previous one
+                match future.pagination() {
+                    Some(pagination) => {
+                        self.page = pagination.page().or_else(|| self.page.map(|page| page + 1));
+                        self.pagination = Some(pagination);
+                    },
+                    None => self.page = self.page.map(|page| page + 1),
+                }
+
+                let object_count = F::object_count(&page);
+                let is_short = object_count.map_or(false, |count| count < self.largest_page);
+
+                self.largest_page = self.largest_page.max(object_count.unwrap_or(0));
+
+                match self.remaining_pages() {
+                    Some(0) => {
+                        info!("Stream terminating after reaching the last page");
+
+                        self.pending.clear();
+                    },
+                    None if is_short => {
+                        info!("Stream terminating after receiving a page shorter than the previous ones");
+
+                        self.pending.clear();
+                    },
+                    _ =>
+                        if self.pending.is_empty() {
+                            self.pending.push_back(Prefetched::new(future.next()?));
+                        },
+                }
+
+                Ok(Async::Ready(Some(page)))
+            },
+
+            Err(ref err) if err.api_error().map_or(false, ApiError::is_no_result) => {
+                info!("Stream terminating due to exhaustion");
+
+                self.pending.clear();
+
+                Ok(Async::Ready(None))
+            },
+
+            Err(err) => {
+                self.pending.clear();
+
+                Err(err)
+            },
         }
     }
 }
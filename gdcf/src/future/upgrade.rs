@@ -1,13 +1,14 @@
 use futures::{Async, Future};
 
 use crate::{
-    api::{client::MakeRequest, ApiClient},
-    cache::{Cache, CacheEntry, CanCache, CreatorKey, Lookup, NewgroundsSongKey, Store},
+    api::{client::MakeRequest, request::Pagination, ApiClient},
+    cache::{Cache, CacheEntry, CachePolicy, CanCache, CreatorKey, Lookup, NewgroundsSongKey, Store},
     error::Error,
-    future::{refresh::RefreshCacheFuture, CloneablePeekFuture, PeekableFuture, StreamableFuture},
-    upgrade::{Upgradable, UpgradeQueryFuture},
+    future::{refresh::RefreshCacheFuture, stream::Page, CloneablePeekFuture, PeekableFuture, StreamableFuture},
+    upgrade::{Upgradable, UpgradeError, UpgradeQueryFuture},
     Gdcf,
 };
+use log::{info, warn};
 use std::fmt::Debug;
 
 struct PendingUpgrade<A, C, Into, U>
@@ -19,6 +20,9 @@ where
     to_upgrade: U,
     cache_meta: C::CacheEntryMeta,
     upgrade_future: UpgradeQueryFuture<RefreshCacheFuture<U::Request, A, C>, U::Upgrade>,
+
+    /// Why refreshing some data involved in this upgrade failed, if we fell back to stale data
+    refresh_failed: Option<String>,
 }
 
 impl<A, C, Into, U> Debug for PendingUpgrade<A, C, Into, U>
@@ -33,6 +37,7 @@ where
             .field("to_upgrade", &self.to_upgrade)
             .field("cache_meta", &self.cache_meta)
             .field("upgrade_future", &self.upgrade_future)
+            .field("refresh_failed", &self.refresh_failed)
             .finish()
     }
 }
@@ -45,7 +50,7 @@ where
     U: Upgradable<Into>,
 {
     gdcf: Gdcf<A, C>,
-    forced_refresh: bool,
+    policy: CachePolicy,
     inner_future: From,
     pending_upgrade: Option<PendingUpgrade<A, C, Into, U>>, //state: UpgradeFutureState<A, C, From, Into, U>,
 }
@@ -57,11 +62,11 @@ where
     From: PeekableFuture<Item = CacheEntry<U, C::CacheEntryMeta>, Error = Error<A::Err, C::Err>>,
     U: Upgradable<Into>,
 {
-    pub(crate) fn new(gdcf: Gdcf<A, C>, forced_refresh: bool, inner_future: From) -> Self {
+    pub(crate) fn new(gdcf: Gdcf<A, C>, policy: CachePolicy, inner_future: From) -> Self {
         Self {
             gdcf,
             inner_future,
-            forced_refresh,
+            policy,
             pending_upgrade: None,
         }
     }
@@ -72,14 +77,28 @@ where
     C: Cache + CanCache<U::Request> + CanCache<CreatorKey> + CanCache<NewgroundsSongKey> + Lookup<U::LookupKey>,
     From: PeekableFuture<Item = CacheEntry<U, C::CacheEntryMeta>, Error = Error<A::Err, C::Err>> + StreamableFuture<A, C>,
     U: Upgradable<Into>,
+    Into: Page,
 {
-    fn next(self) -> Result<Self, Self::Error> {
-        Ok(Self {
+    fn next(&self) -> Result<Self, Self::Error> {
+        Ok(UpgradeFuture {
+            policy: self.policy,
+            gdcf: self.gdcf.clone(),
             inner_future: self.inner_future.next()?,
             pending_upgrade: None,
-            ..self
         })
     }
+
+    fn is_exhausted(page: &Self::Item) -> bool {
+        Into::is_exhausted(page)
+    }
+
+    fn object_count(page: &Self::Item) -> Option<usize> {
+        Into::object_count(page)
+    }
+
+    fn pagination(&self) -> Option<Pagination> {
+        self.inner_future.pagination()
+    }
 }
 
 impl<A, C, From, Into, U> UpgradeFuture<A, C, From, Into, U>
@@ -96,7 +115,7 @@ where
         C: CanCache<Into::Request> + Lookup<Into::LookupKey>,
     {
         UpgradeFuture {
-            forced_refresh: self.forced_refresh,
+            policy: self.policy,
             gdcf: self.gdcf.clone(),
             inner_future: self,
             pending_upgrade: None,
@@ -117,7 +136,7 @@ where
         C: Lookup<Into::LookupKey> + CanCache<Into::Request>,
     {
         UpgradeFuture {
-            forced_refresh: self.forced_refresh,
+            policy: self.policy,
             gdcf: self.gdcf.clone(),
             inner_future: self,
             pending_upgrade: None,
@@ -137,23 +156,54 @@ where
 
     fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
         if self.pending_upgrade.is_none() {
-            match self.inner_future.poll()? {
-                Async::Ready(CacheEntry::Cached(to_upgrade, cache_meta)) => {
-                    let upgrade_query = to_upgrade.query_upgrade(&self.gdcf.cache(), self.forced_refresh)?;
-
-                    self.pending_upgrade = Some(PendingUpgrade {
-                        to_upgrade,
-                        cache_meta,
-                        upgrade_future: upgrade_query.futurize(&self.gdcf),
-                    });
-                },
+            let (to_upgrade, cache_meta, refresh_failed) = match self.inner_future.poll()? {
+                Async::Ready(CacheEntry::Cached(to_upgrade, cache_meta)) => (to_upgrade, cache_meta, None),
+                Async::Ready(CacheEntry::Stale(to_upgrade, cache_meta, reason)) => (to_upgrade, cache_meta, Some(reason)),
                 Async::Ready(cache_entry) => return Ok(Async::Ready(cache_entry.map_empty())),
                 Async::NotReady => return Ok(Async::NotReady),
+            };
+
+            let upgrade_query = to_upgrade.query_upgrade(&self.gdcf.cache(), self.policy)?;
+
+            if self.policy == CachePolicy::CacheOnly && !upgrade_query.upgrade_cached() {
+                info!("Not all data required for upgrade is cached, but cache policy forbids making requests");
+
+                // Optional upgrade data that isn't cached is treated like data the servers didn't
+                // provide. Only if the upgrade can't do without it, there is nothing to return
+                let upgrades = match to_upgrade.process_query_result(&self.gdcf.cache(), upgrade_query.without_requests()) {
+                    Err(UpgradeError::UpgradeFailed) => return Ok(Async::Ready(CacheEntry::Missing)),
+                    upgrades => upgrades?,
+                };
+
+                return Ok(Async::Ready(CacheEntry::Cached(to_upgrade.upgrade(upgrades).0, cache_meta)))
             }
+
+            self.pending_upgrade = Some(PendingUpgrade {
+                to_upgrade,
+                cache_meta,
+                upgrade_future: upgrade_query.futurize(&self.gdcf),
+                refresh_failed,
+            });
         }
 
         if let Some(ref mut pending_upgrade) = self.pending_upgrade {
-            match pending_upgrade.upgrade_future.poll()? {
+            let stale_on_error = self.gdcf.falls_back_to_stale(self.policy);
+            let refresh_failed = &mut pending_upgrade.refresh_failed;
+
+            let poll_result = pending_upgrade.upgrade_future.poll_with(&mut |error, has_cached| {
+                match error {
+                    error if error.api_error().is_some() && stale_on_error && has_cached => {
+                        warn!("Refreshing upgrade data failed: {}. Falling back to cached data", error);
+
+                        refresh_failed.get_or_insert_with(|| error.to_string());
+
+                        Ok(())
+                    },
+                    error => Err(error),
+                }
+            })?;
+
+            match poll_result {
                 Async::NotReady => Ok(Async::NotReady),
                 Async::Ready(upgrade_query) => {
                     let pending = self.pending_upgrade.take().unwrap();
@@ -161,7 +211,10 @@ where
                     let upgrades = pending.to_upgrade.process_query_result(&self.gdcf.cache(), upgrade_query)?;
                     let upgraded = pending.to_upgrade.upgrade(upgrades).0;
 
-                    Ok(Async::Ready(CacheEntry::Cached(upgraded, pending.cache_meta)))
+                    Ok(Async::Ready(match pending.refresh_failed {
+                        Some(reason) => CacheEntry::Stale(upgraded, pending.cache_meta, reason),
+                        None => CacheEntry::Cached(upgraded, pending.cache_meta),
+                    }))
                 },
             }
         } else {
@@ -184,22 +237,28 @@ where
                 let cache = self.gdcf.cache(); // do not borrow self into the closure
 
                 self.inner_future = self.inner_future.peek(|cache_entry| {
-                    if let CacheEntry::Cached(to_upgrade, meta) = cache_entry {
-                        let upgrade_query = to_upgrade.query_upgrade(&cache, false)?;
-
-                        if upgrade_query.upgrade_cached() {
-                            let (upgraded, downgrades) = to_upgrade.upgrade(upgrade_query);
-
-                            if let CacheEntry::Cached(upgraded, meta) = f(CacheEntry::Cached(upgraded, meta))? {
-                                Ok(CacheEntry::Cached(U::downgrade(upgraded, downgrades).0, meta))
-                            } else {
-                                panic!("function passed to .peek() mutated cache entry in invalid ways")
-                            }
-                        } else {
-                            Ok(CacheEntry::Cached(to_upgrade, meta))
-                        }
-                    } else {
-                        Ok(f(cache_entry.map_empty())?.map_empty())
+                    let upgrade_query = match cache_entry {
+                        CacheEntry::Cached(ref to_upgrade, _) | CacheEntry::Stale(ref to_upgrade, ..) =>
+                            to_upgrade.query_upgrade(&cache, CachePolicy::CacheFirst)?,
+                        _ => return Ok(f(cache_entry.map_empty())?.map_empty()),
+                    };
+
+                    if !upgrade_query.upgrade_cached() {
+                        return Ok(cache_entry)
+                    }
+
+                    let mut downgrades = None;
+                    let upgraded = cache_entry.map(|to_upgrade| {
+                        let (upgraded, downgrade) = to_upgrade.upgrade(upgrade_query);
+
+                        downgrades = Some(downgrade);
+                        upgraded
+                    });
+
+                    match f(upgraded)? {
+                        upgraded @ CacheEntry::Cached(..) | upgraded @ CacheEntry::Stale(..) =>
+                            Ok(upgraded.map(|upgraded| U::downgrade(upgraded, downgrades.unwrap()).0)),
+                        _ => panic!("function passed to .peek() mutated cache entry in invalid ways"),
                     }
                 })?;
             },
@@ -216,6 +275,7 @@ where
                         to_upgrade,
                         cache_meta,
                         upgrade_future: futures.recombination(upgrades),
+                        refresh_failed: pending_upgrade.refresh_failed,
                     });
                 } else {
                     panic!("function passed to .peek() mutated cache entry in invalid ways")
@@ -242,16 +302,16 @@ where
                 let cache = self.gdcf.cache();
                 let inner_clone = self.inner_future.clone_peek()?;
 
-                if let CacheEntry::Cached(to_upgrade, meta) = inner_clone {
-                    let upgrade_query = to_upgrade.query_upgrade(&cache, false).map_err(|_| ())?;
+                let upgrade_query = match inner_clone {
+                    CacheEntry::Cached(ref to_upgrade, _) | CacheEntry::Stale(ref to_upgrade, ..) =>
+                        to_upgrade.query_upgrade(&cache, CachePolicy::CacheFirst).map_err(|_| ())?,
+                    _ => return Ok(inner_clone.map_empty()),
+                };
 
-                    if upgrade_query.upgrade_cached() {
-                        Ok(CacheEntry::Cached(to_upgrade.upgrade(upgrade_query).0, meta))
-                    } else {
-                        Err(())
-                    }
+                if upgrade_query.upgrade_cached() {
+                    Ok(inner_clone.map(|to_upgrade| to_upgrade.upgrade(upgrade_query).0))
                 } else {
-                    Ok(inner_clone.map_empty())
+                    Err(())
                 }
             },
             Some(ref upgrade) => {
@@ -280,7 +340,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("UpgradeFuture")
-            .field("forced_refresh", &self.forced_refresh)
+            .field("policy", &self.policy)
             .field("inner_future", &self.inner_future)
             .field("pending_upgrade", &self.pending_upgrade)
             .finish()
@@ -0,0 +1,77 @@
+use crate::{
+    api::{
+        client::{MakeRequest, Response},
+        request::Request,
+        ApiClient,
+    },
+    cache::Invalidate,
+    error::Error,
+};
+use futures::{Async, Future};
+use log::info;
+
+/// Future for requests that change data on the servers instead of retrieving it.
+///
+/// Their results are generally not cached. Instead, once the servers accepted the request, all
+/// cache entries it made outdated are invalidated, see [`Invalidate::written`].
+pub struct WriteFuture<Req, A, C>
+where
+    Req: Request,
+    A: ApiClient + MakeRequest<Req>,
+    C: Invalidate<Req>,
+{
+    inner: <A as MakeRequest<Req>>::Future,
+    cache: C,
+    request: Req,
+}
+
+impl<Req, A, C> std::fmt::Debug for WriteFuture<Req, A, C>
+where
+    Req: Request + std::fmt::Debug,
+    A: ApiClient + MakeRequest<Req>,
+    C: Invalidate<Req>,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("WriteFuture").field("request", &self.request).finish()
+    }
+}
+
+impl<Req, A, C> WriteFuture<Req, A, C>
+where
+    Req: Request,
+    A: ApiClient + MakeRequest<Req>,
+    C: Invalidate<Req>,
+{
+    pub(crate) fn new(client: &A, cache: C, request: Req) -> Self {
+        info!("Performing write request {:?}", request);
+
+        WriteFuture {
+            inner: client.make(&request),
+            cache,
+            request,
+        }
+    }
+}
+
+impl<Req, A, C> Future for WriteFuture<Req, A, C>
+where
+    Req: Request,
+    A: ApiClient + MakeRequest<Req>,
+    C: Invalidate<Req>,
+{
+    type Error = Error<A::Err, C::Err>;
+    type Item = Req::Result;
+
+    fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
+        let result = match self.inner.poll().map_err(Error::Api)? {
+            Async::NotReady => return Ok(Async::NotReady),
+            Async::Ready(Response::Exact(result)) | Async::Ready(Response::More(result, _)) => result,
+        };
+
+        info!("Write request {:?} succeeded, updating affected cache entries", self.request);
+
+        self.cache.written(&self.request, &result).map_err(Error::Cache)?;
+
+        Ok(Async::Ready(result))
+    }
+}
@@ -13,13 +13,22 @@
 use crate::{
     api::{
         client::MakeRequest,
-        request::{comment::ProfileCommentsRequest, user::UserSearchRequest, LevelRequest, LevelsRequest, Request, UserRequest},
+        request::{
+            comment::{LevelCommentsRequest, ProfileCommentsRequest, UploadLevelCommentRequest, UploadProfileCommentRequest},
+            message::{DownloadMessageRequest, MessagesRequest, SendMessageRequest},
+            rate::{LikeItemRequest, RateDemonRequest, RateStarsRequest},
+            song::SongInfoRequest,
+            user::{BlockedUsersRequest, FriendListRequest, FriendRequestsRequest, UserSearchRequest},
+            DailyLevelRequest, GauntletsRequest, LeaderboardRequest, LevelRequest, LevelScoresRequest, LevelsRequest, MapPacksRequest,
+            Pagination, Request, UploadLevelRequest, UserRequest,
+        },
         ApiClient,
     },
-    cache::{Cache, CacheEntry, CanCache, CreatorKey, NewgroundsSongKey, Store},
+    cache::{Cache, CacheEntry, CachePolicy, CanCache, CreatorKey, Invalidate, NewgroundsSongKey, Store},
     future::{
         process::{ProcessRequestFuture, ProcessRequestFutureState},
-        refresh::RefreshCacheFuture,
+        refresh::{InFlight, RefreshCacheFuture},
+        write::WriteFuture,
     },
 };
 pub use error::Error;
@@ -42,6 +51,10 @@ pub enum Secondary {
     Creator(Creator),
     MissingCreator(u64),
     MissingNewgroundsSong(u64),
+
+    /// Where the page the request was for is located within all pages. Not cached, but surfaced to
+    /// [`GdcfStream`](crate::future::stream::GdcfStream)s
+    Pagination(Pagination),
 }
 
 impl From<NewgroundsSong> for Secondary {
@@ -63,6 +76,12 @@ impl std::fmt::Display for Secondary {
             Secondary::Creator(inner) => inner.fmt(f),
             Secondary::MissingCreator(cid) => write!(f, "Creator object missing server-sided: {}", cid),
             Secondary::MissingNewgroundsSong(nid) => write!(f, "Newgrounds song object missing server-sided: {}", nid),
+            Secondary::Pagination(pagination) =>
+                match (pagination.page(), pagination.total_pages()) {
+                    (Some(page), Some(total_pages)) =>
+                        write!(f, "Page {} of {} ({} objects in total)", page + 1, total_pages, pagination.total),
+                    _ => write!(f, "Page of unknown size ({} objects in total)", pagination.total),
+                },
         }
     }
 }
@@ -75,6 +94,11 @@ where
 {
     client: A,
     cache: C,
+
+    /// The requests currently being made by this [`Gdcf`] instance or one of its clones
+    in_flight: InFlight,
+
+    stale_on_error: bool,
 }
 
 impl<A, C> Gdcf<A, C>
@@ -83,7 +107,27 @@ where
     C: Cache,
 {
     pub fn new(client: A, cache: C) -> Gdcf<A, C> {
-        Gdcf { client, cache }
+        Gdcf {
+            client,
+            cache,
+            in_flight: InFlight::default(),
+            stale_on_error: false,
+        }
+    }
+
+    /// Sets whether requests should resolve to outdated cached data if refreshing that data fails
+    /// with an API error.
+    ///
+    /// Such data is returned as [`CacheEntry::Stale`], together with the reason the refresh failed.
+    /// This applies to every step of an upgrade chain. Independently of this setting, requests
+    /// made with [`CachePolicy::NetworkFirst`] always fall back to cached data.
+    pub fn stale_on_error(mut self, stale_on_error: bool) -> Self {
+        self.stale_on_error = stale_on_error;
+        self
+    }
+
+    pub(crate) fn falls_back_to_stale(&self, policy: CachePolicy) -> bool {
+        self.stale_on_error || policy == CachePolicy::NetworkFirst
     }
 
     pub fn cache(&self) -> C {
@@ -100,7 +144,7 @@ where
     A: ApiClient,
     C: Cache + Store<CreatorKey> + Store<NewgroundsSongKey>,
 {
-    fn process<R>(&self, request: R, force_refresh: bool) -> Result<ProcessRequestFutureState<R, A, C>, C::Err>
+    fn process<R>(&self, request: R, policy: CachePolicy) -> Result<ProcessRequestFutureState<R, A, C>, C::Err>
     where
         R: Request,
         A: MakeRequest<R>,
@@ -109,28 +153,29 @@ where
         info!("Processing request {:?}", request);
 
         let cached = match self.cache.lookup(&request)? {
-            CacheEntry::Missing => {
-                info!("No cache entry for request {:?}", request);
+            CacheEntry::Missing =>
+                if policy == CachePolicy::CacheOnly {
+                    info!("No cache entry for request {:?}, but cache policy forbids making requests", request);
 
-                None
-            },
-            entry =>
-                if entry.is_expired() {
-                    trace!("Cache entry is {:?}", entry);
-                    info!("Cache entry for request {:?} is expired!", request);
+                    return Ok(ProcessRequestFutureState::UpToDate(Some(CacheEntry::Missing), request))
+                } else {
+                    info!("No cache entry for request {:?}", request);
 
-                    Some(entry)
-                } else if force_refresh {
-                    trace!("Cache entry is {:?}", entry);
-                    info!("Cache entry is up-to-date, but request forces refresh!");
+                    None
+                },
+            entry => {
+                trace!("Cache entry is {:?}", entry);
+
+                if entry.meta().map_or(false, |meta| policy.needs_refresh(meta)) {
+                    info!("Cache entry for request {:?} needs to be refreshed under policy {:?}!", request, policy);
 
                     Some(entry)
                 } else {
-                    trace!("Cache entry is {:?}", entry);
                     info!("Cached entry for request {:?} is up-to-date!", request);
 
                     return Ok(ProcessRequestFutureState::UpToDate(Some(entry), request))
-                },
+                }
+            },
         };
 
         let future = RefreshCacheFuture::new(self, request);
@@ -162,20 +207,37 @@ where
     /// `Song` can currently be one of the following:
     /// + [`u64`] - The custom song is provided only as its newgrounds ID. Causes no additional
     /// requests
-    /// + [`NewgroundsSong`] - Causes an additional [`LevelsRequest`] to be made to
-    /// retrieve the custom song (only if the level actually uses a custom song though)
-    ///
-    /// Note that a call of `Gdcf::level<NewgroundsSong, Creator>` will **not** issue the same
-    /// `LevelsRequest` twice - GDCF will recognize the cache to be up-to-date when it attempts the
-    /// second one and uses the cached value (or at least it will if you set cache-expiry to
-    /// anything larger than 0 seconds - but then again why would you use GDCF if you don't use the
-    /// cache)
-    pub fn level(&self, request: impl Into<LevelRequest>, force_refresh: bool) -> Result<ProcessRequestFuture<LevelRequest, A, C>, C::Err>
+    /// + [`NewgroundsSong`] - Causes an additional [`SongInfoRequest`] to be made to
+    /// retrieve the custom song (only if the level actually uses a custom song and the song isn't
+    /// cached yet though)
+    pub fn level(&self, request: impl Into<LevelRequest>, policy: CachePolicy) -> Result<ProcessRequestFuture<LevelRequest, A, C>, C::Err>
     where
         A: MakeRequest<LevelRequest>,
         C: CanCache<LevelRequest>,
     {
-        ProcessRequestFuture::new(self.clone(), request.into(), force_refresh)
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`DailyLevelRequest`]
+    ///
+    /// The resulting [`DailyLevel`] can be upgraded into a [`Level`] via
+    /// [`ProcessRequestFuture::upgrade`], which causes an additional [`SpecialLevelRequest`] to be
+    /// made.
+    ///
+    /// Cache entries for daily levels expire exactly when the servers replace the daily level, no
+    /// matter how the cache's expiry is configured otherwise.
+    ///
+    /// [`SpecialLevelRequest`]: crate::api::request::SpecialLevelRequest
+    pub fn daily_level(
+        &self,
+        request: impl Into<DailyLevelRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<DailyLevelRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<DailyLevelRequest>,
+        C: CanCache<DailyLevelRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
     }
 
     /// Processes the given [`LevelsRequest`]
@@ -196,45 +258,319 @@ where
     pub fn levels(
         &self,
         request: impl Into<LevelsRequest>,
-        force_refresh: bool,
+        policy: CachePolicy,
     ) -> Result<ProcessRequestFuture<LevelsRequest, A, C>, C::Err>
     where
         A: MakeRequest<LevelsRequest>,
         C: CanCache<LevelsRequest>,
     {
-        ProcessRequestFuture::new(self.clone(), request.into(), force_refresh)
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`MapPacksRequest`]
+    ///
+    /// The resulting [`MapPack`]s can be upgraded into the [`PartialLevel`]s they contain via
+    /// [`ProcessRequestFuture::upgrade`], which causes an additional [`LevelsRequest`] per map pack
+    /// to be made.
+    pub fn map_packs(
+        &self,
+        request: impl Into<MapPacksRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<MapPacksRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<MapPacksRequest>,
+        C: CanCache<MapPacksRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`GauntletsRequest`]
+    ///
+    /// Just like [`MapPack`]s, the resulting [`Gauntlet`]s can be upgraded into the
+    /// [`PartialLevel`]s they contain.
+    pub fn gauntlets(
+        &self,
+        request: impl Into<GauntletsRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<GauntletsRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<GauntletsRequest>,
+        C: CanCache<GauntletsRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`SongInfoRequest`]
+    ///
+    /// Songs that have been banned from being used in Geometry Dash are marked as absent in the
+    /// cache.
+    pub fn song(
+        &self,
+        request: impl Into<SongInfoRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<SongInfoRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<SongInfoRequest>,
+        C: CanCache<SongInfoRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
     }
 
     /// Processes the given [`UserRequest`]
-    pub fn user(&self, request: impl Into<UserRequest>, force_refresh: bool) -> Result<ProcessRequestFuture<UserRequest, A, C>, C::Err>
+    pub fn user(&self, request: impl Into<UserRequest>, policy: CachePolicy) -> Result<ProcessRequestFuture<UserRequest, A, C>, C::Err>
     where
         A: MakeRequest<UserRequest>,
         C: CanCache<UserRequest>,
     {
-        ProcessRequestFuture::new(self.clone(), request.into(), force_refresh)
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
     }
 
     pub fn search_user(
         &self,
         request: impl Into<UserSearchRequest>,
-        force_refresh: bool,
+        policy: CachePolicy,
     ) -> Result<ProcessRequestFuture<UserSearchRequest, A, C>, C::Err>
     where
         A: MakeRequest<UserSearchRequest>,
         C: CanCache<UserSearchRequest>,
     {
-        ProcessRequestFuture::new(self.clone(), request.into(), force_refresh)
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`LeaderboardRequest`]
+    ///
+    /// The resulting [`LeaderboardEntry`]s can be upgraded into the [`User`]s they belong to.
+    pub fn leaderboard(
+        &self,
+        request: impl Into<LeaderboardRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<LeaderboardRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<LeaderboardRequest>,
+        C: CanCache<LeaderboardRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`LevelScoresRequest`]
+    ///
+    /// Just like [`LeaderboardEntry`]s, the resulting [`LevelScore`]s can be upgraded into the
+    /// [`User`]s who set them.
+    pub fn level_scores(
+        &self,
+        request: impl Into<LevelScoresRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<LevelScoresRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<LevelScoresRequest>,
+        C: CanCache<LevelScoresRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`LevelCommentsRequest`]
+    ///
+    /// The `User` type parameter of the returned [`LevelComment`]s can currently be one of the
+    /// following:
+    /// + [`Option<CommentUser>`] - The partial user data provided by the comment itself. Causes no
+    /// additional requests
+    /// + [`Option<User>`] - Causes up to one additional [`UserRequest`] per comment to retrieve
+    /// the commenter's profile. Comments not associated with an account get `None`.
+    pub fn level_comments(
+        &self,
+        request: impl Into<LevelCommentsRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<LevelCommentsRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<LevelCommentsRequest>,
+        C: CanCache<LevelCommentsRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
     }
 
     pub fn profile_comments(
         &self,
         request: impl Into<ProfileCommentsRequest>,
-        force_refresh: bool,
+        policy: CachePolicy,
     ) -> Result<ProcessRequestFuture<ProfileCommentsRequest, A, C>, C::Err>
     where
         A: MakeRequest<ProfileCommentsRequest>,
         C: CanCache<ProfileCommentsRequest>,
     {
-        ProcessRequestFuture::new(self.clone(), request.into(), force_refresh)
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`MessagesRequest`]
+    ///
+    /// The client needs to be authenticated as the account the request was made for. The
+    /// resulting [`Message`](gdcf_model::message::Message)s don't contain their bodies, use
+    /// [`Gdcf::message`] to retrieve those.
+    pub fn messages(
+        &self,
+        request: impl Into<MessagesRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<MessagesRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<MessagesRequest>,
+        C: CanCache<MessagesRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`DownloadMessageRequest`]
+    ///
+    /// The client needs to be authenticated as either the sender or the recipient of the message.
+    pub fn message(
+        &self,
+        request: impl Into<DownloadMessageRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<DownloadMessageRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<DownloadMessageRequest>,
+        C: CanCache<DownloadMessageRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`FriendListRequest`]
+    ///
+    /// The client needs to be authenticated as the account the request was made for. The account
+    /// IDs of the resulting [`ListedUser`](gdcf_model::user::ListedUser)s can be used to build
+    /// [`LevelsRequest`]s for the levels of the account's friends.
+    pub fn friend_list(
+        &self,
+        request: impl Into<FriendListRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<FriendListRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<FriendListRequest>,
+        C: CanCache<FriendListRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+
+    /// Processes the given [`BlockedUsersRequest`]
+    ///
+    /// The client needs to be authenticated as the account the request was made for.
+    pub fn blocked_users(
+        &self,
+        request: impl Into<BlockedUsersRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<BlockedUsersRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<BlockedUsersRequest>,
+        C: CanCache<BlockedUsersRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
     }
-}
\ No newline at end of file
+
+    /// Processes the given [`FriendRequestsRequest`]
+    ///
+    /// The client needs to be authenticated as the account the request was made for.
+    pub fn friend_requests(
+        &self,
+        request: impl Into<FriendRequestsRequest>,
+        policy: CachePolicy,
+    ) -> Result<ProcessRequestFuture<FriendRequestsRequest, A, C>, C::Err>
+    where
+        A: MakeRequest<FriendRequestsRequest>,
+        C: CanCache<FriendRequestsRequest>,
+    {
+        ProcessRequestFuture::new(self.clone(), request.into(), policy)
+    }
+}
+
+impl<A, C> Gdcf<A, C>
+where
+    A: ApiClient,
+    C: Cache,
+{
+    /// Posts the comment described by the given [`UploadLevelCommentRequest`]
+    ///
+    /// The client needs to be authenticated. Once the comment has been posted, the cached comment
+    /// pages of the level are invalidated, so that the next [`LevelCommentsRequest`] for the level
+    /// includes the new comment.
+    pub fn upload_level_comment(&self, request: UploadLevelCommentRequest) -> WriteFuture<UploadLevelCommentRequest, A, C>
+    where
+        A: MakeRequest<UploadLevelCommentRequest>,
+        C: Invalidate<UploadLevelCommentRequest>,
+    {
+        WriteFuture::new(&self.client, self.cache(), request)
+    }
+
+    /// Posts the comment described by the given [`UploadProfileCommentRequest`]
+    ///
+    /// The client needs to be authenticated as the account the request was made for. Once the
+    /// comment has been posted, the cached comment pages of that account are invalidated.
+    pub fn upload_profile_comment(&self, request: UploadProfileCommentRequest) -> WriteFuture<UploadProfileCommentRequest, A, C>
+    where
+        A: MakeRequest<UploadProfileCommentRequest>,
+        C: Invalidate<UploadProfileCommentRequest>,
+    {
+        WriteFuture::new(&self.client, self.cache(), request)
+    }
+
+    /// Likes or dislikes the item described by the given [`LikeItemRequest`]
+    ///
+    /// The client needs to be authenticated. Once the request succeeded, the cached data containing
+    /// the item's like count is invalidated: for levels, these are the level itself and all cached
+    /// level lists containing it, for comments all cached comment pages of the level or profile the
+    /// comment was posted on.
+    pub fn like_item(&self, request: LikeItemRequest) -> WriteFuture<LikeItemRequest, A, C>
+    where
+        A: MakeRequest<LikeItemRequest>,
+        C: Invalidate<LikeItemRequest>,
+    {
+        WriteFuture::new(&self.client, self.cache(), request)
+    }
+
+    /// Suggests a star rating for a level via the given [`RateStarsRequest`]
+    ///
+    /// The client needs to be authenticated. Since the rating might change the level's difficulty,
+    /// the cached data of the level is invalidated just like when liking it.
+    pub fn rate_stars(&self, request: RateStarsRequest) -> WriteFuture<RateStarsRequest, A, C>
+    where
+        A: MakeRequest<RateStarsRequest>,
+        C: Invalidate<RateStarsRequest>,
+    {
+        WriteFuture::new(&self.client, self.cache(), request)
+    }
+
+    /// Votes on the difficulty of a demon via the given [`RateDemonRequest`]
+    ///
+    /// The client needs to be authenticated. The cached data of the level is invalidated just like
+    /// for [`Gdcf::rate_stars`].
+    pub fn rate_demon(&self, request: RateDemonRequest) -> WriteFuture<RateDemonRequest, A, C>
+    where
+        A: MakeRequest<RateDemonRequest>,
+        C: Invalidate<RateDemonRequest>,
+    {
+        WriteFuture::new(&self.client, self.cache(), request)
+    }
+
+    /// Sends the private message described by the given [`SendMessageRequest`]
+    ///
+    /// The client needs to be authenticated as the account the request was made for. Once the
+    /// message has been sent, the cached pages of that account's sent messages are invalidated.
+    pub fn send_message(&self, request: SendMessageRequest) -> WriteFuture<SendMessageRequest, A, C>
+    where
+        A: MakeRequest<SendMessageRequest>,
+        C: Invalidate<SendMessageRequest>,
+    {
+        WriteFuture::new(&self.client, self.cache(), request)
+    }
+
+    /// Uploads the level contained in the given [`UploadLevelRequest`], resolving to the ID the
+    /// servers assigned to it
+    ///
+    /// The client needs to be authenticated. Once the level has been uploaded, any cached data of a
+    /// previous version of it is invalidated, and the uploaded level is stored as the result of a
+    /// [`LevelRequest`] for its new ID.
+    pub fn upload_level(&self, request: UploadLevelRequest) -> WriteFuture<UploadLevelRequest, A, C>
+    where
+        A: MakeRequest<UploadLevelRequest>,
+        C: Invalidate<UploadLevelRequest>,
+    {
+        WriteFuture::new(&self.client, self.cache(), request)
+    }
+}
@@ -63,19 +63,17 @@ macro_rules! const_setter {
 }
 
 macro_rules! query_upgrade {
-    ($cache: expr, $cache_request: expr, $refresh_request: expr, $force_refresh: expr) => {{
-        use crate::cache::CacheEntryMeta;
-
+    ($cache: expr, $cache_request: expr, $refresh_request: expr, $policy: expr) => {{
         match $cache.lookup(&$cache_request)? {
             CacheEntry::Missing => Ok(UpgradeQuery::One(Some($refresh_request), None)),
             CacheEntry::MarkedAbsent(meta) =>
-                if meta.is_expired() || $force_refresh {
+                if $policy.needs_refresh(&meta) {
                     Ok(UpgradeQuery::One(Some($refresh_request), None))
                 } else {
                     Err(UpgradeError::UpgradeFailed)
                 },
-            CacheEntry::Cached(user, meta) =>
-                if meta.is_expired() || $force_refresh {
+            CacheEntry::Cached(user, meta) | CacheEntry::Stale(user, meta, _) =>
+                if $policy.needs_refresh(&meta) {
                     Ok(UpgradeQuery::One(Some($refresh_request), Some(user)))
                 } else {
                     Ok(UpgradeQuery::One(None, Some(user)))
@@ -85,19 +83,17 @@ macro_rules! query_upgrade {
 }
 
 macro_rules! query_upgrade_option {
-    ($cache: expr, $cache_request: expr, $refresh_request: expr, $force_refresh: expr) => {{
-        use crate::cache::CacheEntryMeta;
-
+    ($cache: expr, $cache_request: expr, $refresh_request: expr, $policy: expr) => {{
         match $cache.lookup(&$cache_request)? {
             CacheEntry::Missing => Ok(UpgradeQuery::One(Some($refresh_request), None)),
             CacheEntry::MarkedAbsent(meta) =>
-                if meta.is_expired() || $force_refresh {
+                if $policy.needs_refresh(&meta) {
                     Ok(UpgradeQuery::One(Some($refresh_request), None))
                 } else {
                     Ok(UpgradeQuery::One(None, Some(None)))
                 },
-            CacheEntry::Cached(user, meta) =>
-                if meta.is_expired() || $force_refresh {
+            CacheEntry::Cached(user, meta) | CacheEntry::Stale(user, meta, _) =>
+                if $policy.needs_refresh(&meta) {
                     Ok(UpgradeQuery::One(Some($refresh_request), Some(Some(user))))
                 } else {
                     Ok(UpgradeQuery::One(None, Some(Some(user))))
@@ -105,3 +101,52 @@ macro_rules! query_upgrade_option {
         }
     }};
 }
+
+/// Implements [`Upgradable`](crate::upgrade::Upgradable) for objects that are upgraded by replacing
+/// them with the result of a single request, and downgraded by putting the original object back.
+///
+/// The closure-like argument constructs the request from a reference to the object being
+/// upgraded.
+macro_rules! replacing_upgrade {
+    ($from: ty => $upgrade: ty, $request: ty, |$this: ident| $make_request: expr) => {
+        impl Upgradable<$upgrade> for $from {
+            type From = $from;
+            type LookupKey = $request;
+            type Request = $request;
+            type Upgrade = $upgrade;
+
+            fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
+                &self,
+                cache: &C,
+                policy: CachePolicy,
+            ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
+                let $this = self;
+
+                query_upgrade!(cache, $make_request, $make_request, policy)
+            }
+
+            fn process_query_result<C: Cache + Lookup<Self::LookupKey>>(
+                &self,
+                _cache: &C,
+                resolved_query: UpgradeQuery<CacheEntry<$upgrade, C::CacheEntryMeta>, Self::Upgrade>,
+            ) -> Result<UpgradeQuery<(), Self::Upgrade>, UpgradeError<C::Err>> {
+                match resolved_query.one() {
+                    (None, Some(upgrade)) => Ok(UpgradeQuery::One(None, Some(upgrade))),
+                    (Some(CacheEntry::Cached(upgrade, _)), _) => Ok(UpgradeQuery::One(None, Some(upgrade))),
+                    _ => Err(UpgradeError::UpgradeFailed),
+                }
+            }
+
+            fn upgrade<State>(self, upgrade: UpgradeQuery<State, Self::Upgrade>) -> ($upgrade, UpgradeQuery<State, Self::From>) {
+                (upgrade.one().1.unwrap(), UpgradeQuery::One(None, Some(self)))
+            }
+
+            fn downgrade<State>(
+                upgraded: $upgrade,
+                downgrade: UpgradeQuery<State, Self::From>,
+            ) -> (Self, UpgradeQuery<State, Self::Upgrade>) {
+                (downgrade.one().1.unwrap(), UpgradeQuery::One(None, Some(upgraded)))
+            }
+        }
+    };
+}
@@ -0,0 +1,87 @@
+use crate::{
+    api::request::UserRequest,
+    cache::{Cache, CacheEntry, CachePolicy, Lookup},
+    upgrade::{Upgradable, UpgradeError, UpgradeQuery},
+};
+use gdcf_model::{
+    comment::{CommentUser, LevelComment},
+    user::User,
+};
+
+impl Upgradable<LevelComment<Option<User>>> for LevelComment<Option<CommentUser>> {
+    type From = Option<CommentUser>;
+    type LookupKey = UserRequest;
+    type Request = UserRequest;
+    type Upgrade = Option<User>;
+
+    fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
+        &self,
+        cache: &C,
+        policy: CachePolicy,
+    ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
+        match self.user.as_ref().and_then(|user| user.account_id) {
+            Some(account_id) => query_upgrade_option!(cache, UserRequest::new(account_id), UserRequest::new(account_id), policy),
+            None => Ok(UpgradeQuery::One(None, Some(None))),
+        }
+    }
+
+    fn process_query_result<C: Cache + Lookup<Self::LookupKey>>(
+        &self,
+        _cache: &C,
+        resolved_query: UpgradeQuery<CacheEntry<User, C::CacheEntryMeta>, Self::Upgrade>,
+    ) -> Result<UpgradeQuery<(), Self::Upgrade>, UpgradeError<C::Err>> {
+        match resolved_query.one() {
+            (None, Some(user)) => Ok(UpgradeQuery::One(None, Some(user))),
+            (Some(CacheEntry::Cached(user, _)), _) => Ok(UpgradeQuery::One(None, Some(Some(user)))),
+            (Some(_), _) => Ok(UpgradeQuery::One(None, Some(None))),
+            _ => Err(UpgradeError::UpgradeFailed),
+        }
+    }
+
+    fn upgrade<State>(self, upgrade: UpgradeQuery<State, Self::Upgrade>) -> (LevelComment<Option<User>>, UpgradeQuery<State, Self::From>) {
+        let (comment, comment_user) = change_level_comment_user(self, upgrade.one().1.unwrap());
+
+        (comment, UpgradeQuery::One(None, Some(comment_user)))
+    }
+
+    fn downgrade<State>(
+        upgraded: LevelComment<Option<User>>,
+        downgrade: UpgradeQuery<State, Self::From>,
+    ) -> (Self, UpgradeQuery<State, Self::Upgrade>) {
+        let (comment, user) = change_level_comment_user(upgraded, downgrade.one().1.unwrap());
+
+        (comment, UpgradeQuery::One(None, Some(user)))
+    }
+}
+
+fn change_level_comment_user<OldUser, NewUser>(comment: LevelComment<OldUser>, new_user: NewUser) -> (LevelComment<NewUser>, OldUser) {
+    let LevelComment {
+        user,
+        content,
+        user_id,
+        likes,
+        comment_id,
+        is_flagged_spam,
+        time_since_post,
+        progress,
+        is_elder_mod,
+        special_color,
+    } = comment;
+
+    (
+        LevelComment {
+            user: new_user,
+
+            content,
+            user_id,
+            likes,
+            comment_id,
+            is_flagged_spam,
+            time_since_post,
+            progress,
+            is_elder_mod,
+            special_color,
+        },
+        user,
+    )
+}
@@ -1,10 +1,10 @@
 use crate::{
-    api::request::{LevelRequest, LevelRequestType, LevelsRequest, Request, SearchFilters, UserRequest},
-    cache::{Cache, CacheEntry, CreatorKey, Lookup, NewgroundsSongKey},
+    api::request::{LevelRequest, LevelRequestType, LevelsRequest, Request, SongInfoRequest, SpecialLevelRequest, UserRequest},
+    cache::{Cache, CacheEntry, CachePolicy, CreatorKey, Lookup, NewgroundsSongKey},
     upgrade::{Upgradable, UpgradeError, UpgradeQuery},
 };
 use gdcf_model::{
-    level::{Level, PartialLevel},
+    level::{DailyLevel, Gauntlet, Level, MapPack, PartialLevel},
     song::NewgroundsSong,
     user::{Creator, User},
 };
@@ -18,13 +18,13 @@ impl<Song, User> Upgradable<Level<Song, User>> for PartialLevel<Song, User> {
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         query_upgrade!(
             cache,
             LevelRequest::new(self.level_id),
             LevelRequest::new(self.level_id),
-            ignored_cached
+            policy
         )
     }
 
@@ -74,43 +74,42 @@ impl<Song, User> Upgradable<Level<Song, User>> for PartialLevel<Song, User> {
     }
 }
 
+replacing_upgrade!(DailyLevel => Level<Option<u64>, u64>, SpecialLevelRequest, |daily| SpecialLevelRequest::new(daily.weekly));
+replacing_upgrade!(MapPack => Vec<PartialLevel<Option<u64>, u64>>, LevelsRequest, |pack| {
+    LevelsRequest::default().map_pack(&pack.level_ids)
+});
+replacing_upgrade!(Gauntlet => Vec<PartialLevel<Option<u64>, u64>>, LevelsRequest, |gauntlet| {
+    LevelsRequest::default().map_pack(&gauntlet.level_ids)
+});
+
 impl<User> Upgradable<Level<Option<NewgroundsSong>, User>> for Level<Option<u64>, User> {
     type From = Option<u64>;
     type LookupKey = NewgroundsSongKey;
-    type Request = LevelsRequest;
+    type Request = SongInfoRequest;
     type Upgrade = Option<NewgroundsSong>;
 
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         match self.base.custom_song {
             Some(song_id) =>
-                query_upgrade_option!(
-                    cache,
-                    NewgroundsSongKey(song_id),
-                    LevelsRequest::default()
-                        .filter(SearchFilters::default().custom_song(song_id))
-                        .request_type(LevelRequestType::MostLiked),
-                    ignored_cached
-                ),
+                query_upgrade_option!(cache, NewgroundsSongKey(song_id), SongInfoRequest::new(song_id), policy),
             None => Ok(UpgradeQuery::One(None, Some(None))),
         }
     }
 
     fn process_query_result<C: Cache + Lookup<Self::LookupKey>>(
         &self,
-        cache: &C,
+        _cache: &C,
         resolved_query: UpgradeQuery<CacheEntry<<Self::Request as Request>::Result, C::CacheEntryMeta>, Self::Upgrade>,
     ) -> Result<UpgradeQuery<(), Self::Upgrade>, UpgradeError<C::Err>> {
         match resolved_query.one() {
             (None, Some(newgrounds_song)) => Ok(UpgradeQuery::One(None, Some(newgrounds_song))),
-            (Some(_), _) =>
-                match cache.lookup(&NewgroundsSongKey(self.base.custom_song.unwrap()))? {
-                    CacheEntry::Cached(song, _) => Ok(UpgradeQuery::One(None, Some(Some(song)))),
-                    _ => Ok(UpgradeQuery::One(None, Some(None))),
-                },
+            (Some(CacheEntry::Cached(song, _)), _) => Ok(UpgradeQuery::One(None, Some(Some(song)))),
+            // Songs that are banned from being used in Geometry Dash are marked as absent
+            (Some(_), _) => Ok(UpgradeQuery::One(None, Some(None))),
             _ => Err(UpgradeError::UpgradeFailed),
         }
     }
@@ -137,40 +136,31 @@ impl<User> Upgradable<Level<Option<NewgroundsSong>, User>> for Level<Option<u64>
 impl<User> Upgradable<PartialLevel<Option<NewgroundsSong>, User>> for PartialLevel<Option<u64>, User> {
     type From = Option<u64>;
     type LookupKey = NewgroundsSongKey;
-    type Request = LevelsRequest;
+    type Request = SongInfoRequest;
     type Upgrade = Option<NewgroundsSong>;
 
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         match self.custom_song {
             Some(song_id) =>
-                query_upgrade_option!(
-                    cache,
-                    NewgroundsSongKey(song_id),
-                    LevelsRequest::default()
-                        .filter(SearchFilters::default().custom_song(song_id))
-                        .request_type(LevelRequestType::MostLiked),
-                    ignored_cached
-                ),
+                query_upgrade_option!(cache, NewgroundsSongKey(song_id), SongInfoRequest::new(song_id), policy),
             None => Ok(UpgradeQuery::One(None, Some(None))),
         }
     }
 
     fn process_query_result<C: Cache + Lookup<Self::LookupKey>>(
         &self,
-        cache: &C,
+        _cache: &C,
         resolved_query: UpgradeQuery<CacheEntry<<Self::Request as Request>::Result, C::CacheEntryMeta>, Self::Upgrade>,
     ) -> Result<UpgradeQuery<(), Self::Upgrade>, UpgradeError<C::Err>> {
         match resolved_query.one() {
             (None, Some(newgrounds_song)) => Ok(UpgradeQuery::One(None, Some(newgrounds_song))),
-            (Some(_), _) =>
-                match cache.lookup(&NewgroundsSongKey(self.custom_song.unwrap()))? {
-                    CacheEntry::Cached(song, _) => Ok(UpgradeQuery::One(None, Some(Some(song)))),
-                    _ => Ok(UpgradeQuery::One(None, Some(None))),
-                },
+            (Some(CacheEntry::Cached(song, _)), _) => Ok(UpgradeQuery::One(None, Some(Some(song)))),
+            // Songs that are banned from being used in Geometry Dash are marked as absent
+            (Some(_), _) => Ok(UpgradeQuery::One(None, Some(None))),
             _ => Err(UpgradeError::UpgradeFailed),
         }
     }
@@ -203,7 +193,7 @@ impl<Song> Upgradable<Level<Song, Option<Creator>>> for Level<Song, u64> {
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         query_upgrade_option!(
             cache,
@@ -211,7 +201,7 @@ impl<Song> Upgradable<Level<Song, Option<Creator>>> for Level<Song, u64> {
             LevelsRequest::default()
                 .search(self.base.creator.to_string())
                 .request_type(LevelRequestType::User),
-            ignored_cached
+            policy
         )
     }
 
@@ -259,7 +249,7 @@ impl<Song> Upgradable<PartialLevel<Song, Option<Creator>>> for PartialLevel<Song
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         query_upgrade_option!(
             cache,
@@ -267,7 +257,7 @@ impl<Song> Upgradable<PartialLevel<Song, Option<Creator>>> for PartialLevel<Song
             LevelsRequest::default()
                 .search(self.creator.to_string())
                 .request_type(LevelRequestType::User),
-            ignored_cached
+            policy
         )
     }
 
@@ -315,10 +305,10 @@ impl<Song> Upgradable<Level<Song, Option<User>>> for Level<Song, Option<Creator>
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         match self.base.creator.as_ref().and_then(|creator| creator.account_id) {
-            Some(account_id) => query_upgrade_option!(cache, UserRequest::new(account_id), UserRequest::new(account_id), ignored_cached),
+            Some(account_id) => query_upgrade_option!(cache, UserRequest::new(account_id), UserRequest::new(account_id), policy),
             None => Ok(UpgradeQuery::One(None, Some(None))),
         }
     }
@@ -361,10 +351,10 @@ impl<Song> Upgradable<PartialLevel<Song, Option<User>>> for PartialLevel<Song, O
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         match self.creator.as_ref().and_then(|creator| creator.account_id) {
-            Some(account_id) => query_upgrade_option!(cache, UserRequest::new(account_id), UserRequest::new(account_id), ignored_cached),
+            Some(account_id) => query_upgrade_option!(cache, UserRequest::new(account_id), UserRequest::new(account_id), policy),
             None => Ok(UpgradeQuery::One(None, Some(None))),
         }
     }
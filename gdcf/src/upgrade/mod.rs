@@ -2,7 +2,7 @@
 
 use crate::{
     api::{client::MakeRequest, request::Request},
-    cache::{Cache, CacheEntry, CanCache, CreatorKey, Key, Lookup, NewgroundsSongKey, Store},
+    cache::{Cache, CacheEntry, CacheEntryMeta, CachePolicy, CanCache, CreatorKey, Key, Lookup, NewgroundsSongKey, Store},
     error::{ApiError, CacheError, Error},
     future::refresh::RefreshCacheFuture,
     Gdcf,
@@ -10,6 +10,7 @@ use crate::{
 use futures::{Async, Future};
 use std::fmt::Debug;
 
+pub mod comment;
 pub mod level;
 pub mod user;
 
@@ -30,6 +31,15 @@ impl<R, S> UpgradeQuery<R, S> {
         }
     }
 
+    /// Resolves all requests in this query to [`CacheEntry::Missing`] without making them
+    pub(crate) fn without_requests<T, Meta: CacheEntryMeta>(self) -> UpgradeQuery<CacheEntry<T, Meta>, S> {
+        match self {
+            UpgradeQuery::One(request, upgrade) => UpgradeQuery::One(request.map(|_| CacheEntry::Missing), upgrade),
+            UpgradeQuery::Many(inner_queries) =>
+                UpgradeQuery::Many(inner_queries.into_iter().map(UpgradeQuery::without_requests).collect()),
+        }
+    }
+
     fn one(self) -> (Option<R>, Option<S>) {
         match self {
             UpgradeQuery::One(r, s) => (r, s),
@@ -206,11 +216,30 @@ impl<F: Future, S> Future for UpgradeQueryFuture<F, S> {
     type Item = UpgradeQuery<F::Item, S>;
 
     fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
+        self.poll_with(&mut |error, _| Err(error))
+    }
+}
+
+impl<F: Future, S> UpgradeQueryFuture<F, S> {
+    /// Polls this future, giving `recover` a chance to handle errors of the pending requests.
+    ///
+    /// `recover` is passed the error and whether (possibly outdated) upgrade data is cached for the
+    /// failed request. If it returns `Ok(())`, the request is treated as if it was never made, and
+    /// the cached upgrade data is used.
+    pub(crate) fn poll_with<H>(&mut self, recover: &mut H) -> Result<Async<UpgradeQuery<F::Item, S>>, F::Error>
+    where
+        H: FnMut(F::Error, bool) -> Result<(), F::Error>,
+    {
         match self {
             UpgradeQueryFuture::One(Some(FutureState::Pending(future)), data) =>
-                match future.poll()? {
-                    Async::Ready(future_result) => Ok(Async::Ready(UpgradeQuery::One(Some(future_result), data.take()))),
-                    Async::NotReady => Ok(Async::NotReady),
+                match future.poll() {
+                    Ok(Async::Ready(future_result)) => Ok(Async::Ready(UpgradeQuery::One(Some(future_result), data.take()))),
+                    Ok(Async::NotReady) => Ok(Async::NotReady),
+                    Err(error) => {
+                        recover(error, data.is_some())?;
+
+                        Ok(Async::Ready(UpgradeQuery::One(None, data.take())))
+                    },
                 },
             UpgradeQueryFuture::One(Some(FutureState::Done(_)), _) => unreachable!(), /* can be constructed, but we don't poll this */
             // anymore! (see below)
@@ -221,7 +250,7 @@ impl<F: Future, S> Future for UpgradeQueryFuture<F, S> {
                 for i in 0..inner.len() {
                     match &mut inner[i] {
                         FutureState::Pending(future) =>
-                            match future.poll()? {
+                            match future.poll_with(recover)? {
                                 Async::NotReady => {
                                     all_done = false;
                                 },
@@ -310,13 +339,14 @@ pub trait Upgradable<Into>: Sized {
     /// Note that it is not enough to just return the request here and have GDCF check if the result
     /// of that request has already been cached, as certain objects can be retrieved by different
     /// requests. For instance, the song for a level could already be cached because another level
-    /// in cache uses the same song, yet the request to retrieve the song (a [`LevelsRequest`]) has
+    /// in cache uses the same song, yet the request to retrieve the song (a [`SongInfoRequest`]) has
     /// never been made.
     ///
     /// ## Parameters:
     /// + `cache`: The cache to look in
-    /// + `ignore_cached`: Whether cached data should be ignored, meaning that requests should be
-    /// produced for all upgrade data, no matter whether its cached or not
+    /// + `policy`: The [`CachePolicy`] deciding which cached upgrade data is to be refreshed. Under
+    /// [`CachePolicy::Refresh`], requests should be produced for all upgrade data, no matter whether
+    /// its cached or not
     ///
     /// ## A note on the return value
     /// Implementations of this method have to handle the cache appropriately themselves. This means
@@ -327,7 +357,7 @@ pub trait Upgradable<Into>: Sized {
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignored_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>>;
 
     fn process_query_result<C: Cache + Lookup<Self::LookupKey>>(
@@ -352,7 +382,7 @@ where
     fn query_upgrade<C: Cache + Lookup<Self::LookupKey>>(
         &self,
         cache: &C,
-        ignore_cached: bool,
+        policy: CachePolicy,
     ) -> Result<UpgradeQuery<Self::Request, Self::Upgrade>, UpgradeError<C::Err>> {
         // Alright, so we have to jump through some hoops to get this work sadly. ApiClients can process
         // lists of requests, however we still need to keep track of which request corresponds to which
@@ -362,7 +392,7 @@ where
         let mut queries = Vec::new();
 
         for to_query in self.iter() {
-            let query = to_query.query_upgrade(cache, ignore_cached)?;
+            let query = to_query.query_upgrade(cache, policy)?;
 
             queries.push(query);
         }
@@ -0,0 +1,133 @@
+//! Tests for the coalescing of identical requests made at the same time
+
+use futures::{executor, Future};
+use gdcf::{
+    api::{
+        mock::MockError,
+        request::{LevelRequest, LevelsRequest, Pagination},
+    },
+    cache::{CacheEntry, CachePolicy},
+    error::Error,
+    future::StreamableFuture,
+    Secondary,
+};
+
+mod common;
+
+#[test]
+fn identical_requests_are_made_once() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    let leader = gdcf.level(request.clone(), CachePolicy::Refresh).unwrap();
+    let follower = gdcf.level(request.clone(), CachePolicy::Refresh).unwrap();
+
+    match leader.join(follower).wait().unwrap() {
+        (CacheEntry::Cached(first, _), CacheEntry::Cached(second, _)) => {
+            assert_eq!(first, common::level(1, 5, None));
+            assert_eq!(second, common::level(1, 5, None));
+        },
+        entries => panic!("Expected cached levels, got {:?}", entries),
+    }
+
+    assert_eq!(client.calls(&request), 1);
+}
+
+#[test]
+fn different_requests_are_not_coalesced() {
+    let (gdcf, client) = common::gdcf();
+
+    client.respond(&LevelRequest::new(1), common::level(1, 5, None));
+    client.respond(&LevelRequest::new(2), common::level(2, 5, None));
+
+    let first = gdcf.level(1u64, CachePolicy::Refresh).unwrap();
+    let second = gdcf.level(2u64, CachePolicy::Refresh).unwrap();
+
+    first.join(second).wait().unwrap();
+
+    assert_eq!(client.calls(&LevelRequest::new(1)), 1);
+    assert_eq!(client.calls(&LevelRequest::new(2)), 1);
+}
+
+#[test]
+fn followers_receive_pagination() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelsRequest::default();
+    let pagination = Pagination {
+        total: 30,
+        offset: 0,
+        page_size: 10,
+    };
+
+    client.respond_more(&request, vec![common::partial_level(1, 5, None)], vec![
+        Secondary::Pagination(pagination),
+    ]);
+
+    let mut leader = gdcf.levels(request.clone(), CachePolicy::Refresh).unwrap();
+    let mut follower = gdcf.levels(request.clone(), CachePolicy::Refresh).unwrap();
+
+    executor::spawn(&mut leader).wait_future().unwrap();
+    executor::spawn(&mut follower).wait_future().unwrap();
+
+    assert_eq!(leader.pagination(), Some(pagination));
+    assert_eq!(follower.pagination(), Some(pagination));
+    assert_eq!(client.calls(&request), 1);
+}
+
+#[test]
+fn followers_share_api_error() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.fail(&request, MockError::Custom(String::from("Server on fire")));
+
+    let futures = vec![
+        gdcf.level(request.clone(), CachePolicy::Refresh).unwrap(),
+        gdcf.level(request.clone(), CachePolicy::Refresh).unwrap(),
+        gdcf.level(request.clone(), CachePolicy::Refresh).unwrap(),
+    ];
+
+    for future in futures {
+        match future.wait() {
+            Err(Error::Coalesced(ref api_error)) => assert_eq!(**api_error, MockError::Custom(String::from("Server on fire"))),
+            result => panic!("Expected shared API error, got {:?}", result),
+        }
+    }
+
+    assert_eq!(client.calls(&request), 1);
+}
+
+#[test]
+fn followers_retry_if_leader_is_dropped() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    let leader = gdcf.level(request.clone(), CachePolicy::Refresh).unwrap();
+    let follower = gdcf.level(request.clone(), CachePolicy::Refresh).unwrap();
+
+    drop(leader);
+
+    match follower.wait().unwrap() {
+        CacheEntry::Cached(level, _) => assert_eq!(level, common::level(1, 5, None)),
+        entry => panic!("Expected cached level, got {:?}", entry),
+    }
+
+    assert_eq!(client.calls(&request), 2);
+}
+
+#[test]
+fn completed_request_is_not_joined() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    gdcf.level(request.clone(), CachePolicy::Refresh).unwrap().wait().unwrap();
+    gdcf.level(request.clone(), CachePolicy::Refresh).unwrap().wait().unwrap();
+
+    assert_eq!(client.calls(&request), 2);
+}
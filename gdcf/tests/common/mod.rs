@@ -0,0 +1,98 @@
+//! Fixtures shared between the integration tests
+//!
+//! Every test file only uses some of these, so not all of them are used in every test crate
+
+#![allow(dead_code)]
+
+use chrono::Duration;
+use gdcf::{
+    api::mock::MockClient,
+    cache::memory::MemoryCache,
+    Gdcf,
+};
+use gdcf_model::{
+    level::{Featured, Level, LevelLength, LevelRating, PartialLevel, Password},
+    song::NewgroundsSong,
+    user::Creator,
+    GameVersion,
+};
+
+/// Constructs a [`Gdcf`] instance whose cache entries are up-to-date for an hour, returning it
+/// together with a handle to its [`MockClient`]
+pub fn gdcf() -> (Gdcf<MockClient, MemoryCache>, MockClient) {
+    gdcf_expiring_after(Duration::hours(1))
+}
+
+/// Constructs a [`Gdcf`] instance whose cache entries are outdated as soon as they're stored
+pub fn outdated_gdcf() -> (Gdcf<MockClient, MemoryCache>, MockClient) {
+    gdcf_expiring_after(Duration::seconds(-1))
+}
+
+fn gdcf_expiring_after(expire_after: Duration) -> (Gdcf<MockClient, MemoryCache>, MockClient) {
+    let client = MockClient::new();
+
+    (Gdcf::new(client.clone(), MemoryCache::new(expire_after)), client)
+}
+
+pub fn partial_level(level_id: u64, creator: u64, custom_song: Option<u64>) -> PartialLevel<Option<u64>, u64> {
+    PartialLevel {
+        level_id,
+        name: format!("Level {}", level_id),
+        description: None,
+        version: 1,
+        creator,
+        difficulty: LevelRating::NotAvailable,
+        downloads: 0,
+        main_song: None,
+        gd_version: GameVersion::Unknown,
+        likes: 0,
+        length: LevelLength::Tiny,
+        stars: 0,
+        featured: Featured::NotFeatured,
+        copy_of: None,
+        index_31: None,
+        custom_song,
+        coin_amount: 0,
+        coins_verified: false,
+        stars_requested: None,
+        index_40: None,
+        is_epic: false,
+        index_43: String::new(),
+        object_amount: None,
+        index_46: None,
+        index_47: None,
+    }
+}
+
+pub fn level(level_id: u64, creator: u64, custom_song: Option<u64>) -> Level<Option<u64>, u64> {
+    Level {
+        base: partial_level(level_id, creator, custom_song),
+        level_data: Vec::new(),
+        password: Password::NoCopy,
+        time_since_upload: String::new(),
+        time_since_update: String::new(),
+        index_36: String::new(),
+    }
+}
+
+pub fn song(song_id: u64) -> NewgroundsSong {
+    NewgroundsSong {
+        song_id,
+        name: format!("Song {}", song_id),
+        index_3: 0,
+        artist: String::from("Artist"),
+        filesize: 1.0,
+        index_6: None,
+        index_7: None,
+        index_8: String::new(),
+        link: String::new(),
+    }
+}
+
+pub fn creator(user_id: u64) -> Creator {
+    Creator {
+        user_id,
+        name: format!("Creator {}", user_id),
+        account_id: Some(user_id),
+    }
+}
@@ -0,0 +1,114 @@
+//! Tests for how GDCF decides between answering requests from its cache and making them
+
+use futures::Future;
+use gdcf::{
+    api::{
+        mock::MockError,
+        request::{LevelRequest, UserRequest},
+    },
+    cache::{CacheEntry, CachePolicy},
+    error::Error,
+};
+
+mod common;
+
+#[test]
+fn uncached_request_is_made_once() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    for _ in 0..3 {
+        match gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap() {
+            CacheEntry::Cached(level, _) => assert_eq!(level, common::level(1, 5, None)),
+            entry => panic!("Expected cached level, got {:?}", entry),
+        }
+    }
+
+    assert_eq!(client.calls(&request), 1);
+}
+
+#[test]
+fn outdated_entry_is_refreshed() {
+    let (gdcf, client) = common::outdated_gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    let mut updated = common::level(1, 5, None);
+    updated.base.version = 2;
+
+    client.respond(&request, updated.clone());
+
+    match gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap() {
+        CacheEntry::Cached(level, _) => assert_eq!(level, updated),
+        entry => panic!("Expected refreshed level, got {:?}", entry),
+    }
+
+    assert_eq!(client.calls(&request), 2);
+}
+
+#[test]
+fn refresh_ignores_up_to_date_entry() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+    gdcf.level(request.clone(), CachePolicy::Refresh).unwrap().wait().unwrap();
+
+    assert_eq!(client.calls(&request), 2);
+}
+
+#[test]
+fn cache_only_never_makes_requests() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    match gdcf.level(request.clone(), CachePolicy::CacheOnly).unwrap().wait().unwrap() {
+        CacheEntry::Missing => (),
+        entry => panic!("Expected missing entry, got {:?}", entry),
+    }
+
+    assert_eq!(client.total_calls(), 0);
+}
+
+#[test]
+fn no_result_is_marked_absent() {
+    let (gdcf, client) = common::gdcf();
+    let request = UserRequest::new(1);
+
+    client.fail(&request, MockError::NoResult);
+
+    for _ in 0..2 {
+        match gdcf.user(request, CachePolicy::CacheFirst).unwrap().wait().unwrap() {
+            CacheEntry::MarkedAbsent(_) => (),
+            entry => panic!("Expected absent entry, got {:?}", entry),
+        }
+    }
+
+    assert_eq!(client.calls(&request), 1);
+}
+
+#[test]
+fn api_errors_are_not_cached() {
+    let (gdcf, client) = common::gdcf();
+    let request = UserRequest::new(1);
+
+    client.fail(&request, MockError::Custom(String::from("Server on fire")));
+
+    for _ in 0..2 {
+        match gdcf.user(request, CachePolicy::CacheFirst).unwrap().wait() {
+            Err(Error::Api(MockError::Custom(_))) => (),
+            result => panic!("Expected API error, got {:?}", result),
+        }
+    }
+
+    assert_eq!(client.calls(&request), 2);
+}
@@ -0,0 +1,184 @@
+//! Tests for falling back to outdated cached data when refreshing it fails
+
+use futures::Future;
+use gdcf::{
+    api::{
+        mock::MockError,
+        request::{LevelRequest, SongInfoRequest},
+    },
+    cache::{CacheEntry, CachePolicy},
+    error::Error,
+};
+use gdcf_model::{level::Level, song::NewgroundsSong};
+
+mod common;
+
+fn server_on_fire() -> MockError {
+    MockError::Custom(String::from("Server on fire"))
+}
+
+#[test]
+fn stale_on_error_falls_back_to_outdated_entry() {
+    let (gdcf, client) = common::outdated_gdcf();
+    let gdcf = gdcf.stale_on_error(true);
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    client.fail(&request, server_on_fire());
+
+    match gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap() {
+        CacheEntry::Stale(level, _, reason) => {
+            assert_eq!(level, common::level(1, 5, None));
+            assert_eq!(reason, "Server on fire");
+        },
+        entry => panic!("Expected stale level, got {:?}", entry),
+    }
+
+    assert_eq!(client.calls(&request), 2);
+}
+
+#[test]
+fn errors_are_returned_without_stale_on_error() {
+    let (gdcf, client) = common::outdated_gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    client.fail(&request, server_on_fire());
+
+    match gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait() {
+        Err(Error::Api(api_error)) => assert_eq!(api_error, server_on_fire()),
+        result => panic!("Expected API error, got {:?}", result),
+    }
+}
+
+#[test]
+fn network_first_always_falls_back() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    client.fail(&request, server_on_fire());
+
+    match gdcf.level(request.clone(), CachePolicy::NetworkFirst).unwrap().wait().unwrap() {
+        CacheEntry::Stale(level, ..) => assert_eq!(level, common::level(1, 5, None)),
+        entry => panic!("Expected stale level, got {:?}", entry),
+    }
+
+    assert_eq!(client.calls(&request), 2);
+}
+
+#[test]
+fn nothing_to_fall_back_to() {
+    let (gdcf, client) = common::gdcf();
+    let gdcf = gdcf.stale_on_error(true);
+    let request = LevelRequest::new(1);
+
+    client.fail(&request, server_on_fire());
+
+    match gdcf.level(request.clone(), CachePolicy::NetworkFirst).unwrap().wait() {
+        Err(Error::Api(api_error)) => assert_eq!(api_error, server_on_fire()),
+        result => panic!("Expected API error, got {:?}", result),
+    }
+}
+
+#[test]
+fn coalesced_requests_fall_back() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, None));
+
+    gdcf.level(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    client.fail(&request, server_on_fire());
+
+    let leader = gdcf.level(request.clone(), CachePolicy::NetworkFirst).unwrap();
+    let follower = gdcf.level(request.clone(), CachePolicy::NetworkFirst).unwrap();
+
+    match leader.join(follower).wait().unwrap() {
+        (CacheEntry::Stale(..), CacheEntry::Stale(..)) => (),
+        entries => panic!("Expected stale levels, got {:?}", entries),
+    }
+
+    assert_eq!(client.calls(&request), 2);
+}
+
+#[test]
+fn stale_entries_are_upgraded() {
+    let (gdcf, client) = common::outdated_gdcf();
+    let gdcf = gdcf.stale_on_error(true);
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, Some(10)));
+    client.respond(&SongInfoRequest::new(10), common::song(10));
+
+    gdcf
+        .level(request.clone(), CachePolicy::CacheFirst)
+        .unwrap()
+        .upgrade::<Level<Option<NewgroundsSong>, u64>>()
+        .wait()
+        .unwrap();
+
+    client.fail(&request, server_on_fire());
+
+    let entry = gdcf
+        .level(request.clone(), CachePolicy::CacheFirst)
+        .unwrap()
+        .upgrade::<Level<Option<NewgroundsSong>, u64>>()
+        .wait()
+        .unwrap();
+
+    match entry {
+        CacheEntry::Stale(level, _, reason) => {
+            assert_eq!(level.base.custom_song, Some(common::song(10)));
+            assert_eq!(reason, "Server on fire");
+        },
+        entry => panic!("Expected stale level, got {:?}", entry),
+    }
+}
+
+#[test]
+fn failing_upgrade_data_refresh_falls_back() {
+    let (gdcf, client) = common::outdated_gdcf();
+    let gdcf = gdcf.stale_on_error(true);
+    let request = LevelRequest::new(1);
+
+    client.respond(&request, common::level(1, 5, Some(10)));
+    client.respond(&SongInfoRequest::new(10), common::song(10));
+
+    gdcf
+        .level(request.clone(), CachePolicy::CacheFirst)
+        .unwrap()
+        .upgrade::<Level<Option<NewgroundsSong>, u64>>()
+        .wait()
+        .unwrap();
+
+    client.fail(&SongInfoRequest::new(10), server_on_fire());
+
+    let entry = gdcf
+        .level(request.clone(), CachePolicy::CacheFirst)
+        .unwrap()
+        .upgrade::<Level<Option<NewgroundsSong>, u64>>()
+        .wait()
+        .unwrap();
+
+    match entry {
+        CacheEntry::Stale(level, _, reason) => {
+            assert_eq!(level.base.custom_song, Some(common::song(10)));
+            assert_eq!(reason, "Server on fire");
+        },
+        entry => panic!("Expected stale level, got {:?}", entry),
+    }
+
+    assert_eq!(client.calls(&request), 2);
+    assert_eq!(client.calls(&SongInfoRequest::new(10)), 2);
+}
@@ -0,0 +1,230 @@
+//! Tests for streams over paginated requests
+
+use futures::{Future, Stream};
+use gdcf::{
+    api::{
+        mock::{MockClient, MockError},
+        request::{LevelsRequest, Pagination},
+    },
+    cache::{memory::Entry, CacheEntry, CachePolicy},
+    future::StreamableFuture,
+    Secondary,
+};
+use gdcf_model::level::PartialLevel;
+
+mod common;
+
+fn page(page: u32) -> LevelsRequest {
+    LevelsRequest::default().page(page)
+}
+
+/// Registers a page containing `len` levels, with level IDs counting up across pages of ten
+fn respond_page(client: &MockClient, index: u32, len: u64, pagination: Option<Pagination>) {
+    let levels = (0..len)
+        .map(|offset| common::partial_level(u64::from(index) * 10 + offset, 5, None))
+        .collect();
+
+    match pagination {
+        Some(pagination) => client.respond_more(&page(index), levels, vec![Secondary::Pagination(pagination)]),
+        None => client.respond(&page(index), levels),
+    }
+}
+
+#[test]
+fn stream_stops_at_last_page() {
+    let (gdcf, client) = common::gdcf();
+
+    for index in 0..3 {
+        let pagination = Pagination {
+            total: 25,
+            offset: index * 10,
+            page_size: 10,
+        };
+
+        respond_page(&client, index, if index == 2 { 5 } else { 10 }, Some(pagination));
+    }
+
+    let mut stream = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream();
+    let pages = stream.by_ref().collect().wait().unwrap();
+
+    assert_eq!(pages.len(), 3);
+    assert_eq!(stream.total_pages(), Some(3));
+    assert_eq!(stream.total_items(), Some(25));
+    assert_eq!(client.calls(&page(3)), 0);
+    assert_eq!(client.total_calls(), 3);
+}
+
+#[test]
+fn stream_stops_on_empty_page() {
+    let (gdcf, client) = common::gdcf();
+
+    respond_page(&client, 0, 10, None);
+    respond_page(&client, 1, 10, None);
+    respond_page(&client, 2, 0, None);
+
+    let pages = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream().collect().wait().unwrap();
+
+    assert_eq!(pages.len(), 2);
+    assert_eq!(client.total_calls(), 3);
+}
+
+#[test]
+fn stream_stops_on_no_result() {
+    let (gdcf, client) = common::gdcf();
+
+    respond_page(&client, 0, 10, None);
+    client.fail(&page(1), MockError::NoResult);
+
+    let pages = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream().collect().wait().unwrap();
+
+    assert_eq!(pages.len(), 1);
+    assert_eq!(client.total_calls(), 2);
+}
+
+#[test]
+fn stream_with_unknown_page_size_stops_on_short_page() {
+    let (gdcf, client) = common::gdcf();
+
+    for (index, len) in [10, 10, 4].iter().enumerate() {
+        let pagination = Pagination {
+            total: 9999,
+            offset: index as u32 * 10,
+            page_size: 0,
+        };
+
+        respond_page(&client, index as u32, *len, Some(pagination));
+    }
+
+    let mut stream = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream();
+    let pages = stream.by_ref().collect().wait().unwrap();
+
+    assert_eq!(pages.len(), 3);
+    assert_eq!(stream.total_pages(), None);
+    assert_eq!(client.calls(&page(3)), 0);
+}
+
+#[test]
+fn unknown_page_size() {
+    let pagination = Pagination {
+        total: 30,
+        offset: 10,
+        page_size: 0,
+    };
+
+    assert_eq!(pagination.total_pages(), None);
+    assert_eq!(pagination.page(), None);
+    assert!(!pagination.is_last_page());
+}
+
+#[test]
+fn cached_pages_are_streamed_without_requests() {
+    let (gdcf, client) = common::gdcf();
+
+    respond_page(&client, 0, 10, None);
+    respond_page(&client, 1, 3, None);
+
+    let first = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream().collect().wait().unwrap();
+
+    client.reset_calls();
+
+    let second = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream().collect().wait().unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(second.len(), 2);
+    assert_eq!(client.total_calls(), 0);
+}
+
+fn first_level_id(entry: &CacheEntry<Vec<PartialLevel<Option<u64>, u64>>, Entry>) -> u64 {
+    match entry {
+        CacheEntry::Cached(levels, _) => levels[0].level_id,
+        entry => panic!("Expected cached page, got {:?}", entry),
+    }
+}
+
+fn respond_pages(client: &MockClient, total: u32, pages: u32) {
+    for index in 0..pages {
+        let pagination = Pagination {
+            total,
+            offset: index * 10,
+            page_size: 10,
+        };
+
+        respond_page(client, index, 10, Some(pagination));
+    }
+}
+
+#[test]
+fn prefetching_requests_pages_in_advance() {
+    let (gdcf, client) = common::gdcf();
+
+    respond_pages(&client, 100, 10);
+
+    let mut stream = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream().prefetch(2);
+    let first = stream.by_ref().take(1).collect().wait().unwrap();
+
+    assert_eq!(first.len(), 1);
+    assert_eq!(client.calls(&page(1)), 1);
+    assert_eq!(client.calls(&page(2)), 1);
+    assert_eq!(client.calls(&page(3)), 0);
+}
+
+#[test]
+fn prefetched_pages_are_yielded_in_order() {
+    let (gdcf, client) = common::gdcf();
+
+    respond_pages(&client, 50, 5);
+
+    let pages = gdcf
+        .levels(page(0), CachePolicy::CacheFirst)
+        .unwrap()
+        .stream()
+        .prefetch(3)
+        .collect()
+        .wait()
+        .unwrap();
+
+    assert_eq!(pages.iter().map(first_level_id).collect::<Vec<_>>(), vec![0, 10, 20, 30, 40]);
+    assert_eq!(client.total_calls(), 5);
+}
+
+#[test]
+fn prefetched_pages_past_the_end_are_cancelled() {
+    let (gdcf, client) = common::gdcf();
+
+    // The servers would answer requests for pages past the end, but the stream must not yield them
+    respond_pages(&client, 20, 5);
+
+    let pages = gdcf
+        .levels(page(0), CachePolicy::CacheFirst)
+        .unwrap()
+        .stream()
+        .prefetch(3)
+        .collect()
+        .wait()
+        .unwrap();
+
+    assert_eq!(pages.iter().map(first_level_id).collect::<Vec<_>>(), vec![0, 10]);
+
+    // Before the first page arrived, the stream didn't know where the end was
+    assert_eq!(client.calls(&page(3)), 1);
+    assert_eq!(client.calls(&page(4)), 0);
+}
+
+#[test]
+fn prefetching_stops_at_last_page() {
+    let (gdcf, client) = common::gdcf();
+
+    respond_pages(&client, 30, 5);
+
+    let mut stream = gdcf.levels(page(0), CachePolicy::CacheFirst).unwrap().stream();
+    let first = stream.by_ref().take(1).collect().wait().unwrap();
+
+    assert_eq!(first.len(), 1);
+
+    // Once the stream knows there are three pages, prefetching doesn't go past them
+    let rest = stream.prefetch(5).collect().wait().unwrap();
+
+    assert_eq!(rest.iter().map(first_level_id).collect::<Vec<_>>(), vec![10, 20]);
+    assert_eq!(client.calls(&page(3)), 0);
+    assert_eq!(client.total_calls(), 3);
+}
@@ -0,0 +1,191 @@
+//! Tests for upgrade chains, checking that each step makes exactly the requests it needs
+
+use futures::Future;
+use gdcf::{
+    api::request::{LevelRequest, LevelRequestType, LevelsRequest, SongInfoRequest},
+    cache::{CacheEntry, CachePolicy},
+    Secondary,
+};
+use gdcf_model::{
+    level::{Level, PartialLevel},
+    song::NewgroundsSong,
+    user::Creator,
+};
+
+mod common;
+
+fn creator_search(user_id: u64) -> LevelsRequest {
+    LevelsRequest::default()
+        .search(user_id.to_string())
+        .request_type(LevelRequestType::User)
+}
+
+#[test]
+fn level_song_upgrade() {
+    let (gdcf, client) = common::gdcf();
+
+    client.respond(&LevelRequest::new(1), common::level(1, 5, Some(10)));
+    client.respond(&SongInfoRequest::new(10), common::song(10));
+
+    for _ in 0..2 {
+        let entry = gdcf
+            .level(1u64, CachePolicy::CacheFirst)
+            .unwrap()
+            .upgrade::<Level<Option<NewgroundsSong>, u64>>()
+            .wait()
+            .unwrap();
+
+        match entry {
+            CacheEntry::Cached(level, _) => assert_eq!(level.base.custom_song, Some(common::song(10))),
+            entry => panic!("Expected cached level, got {:?}", entry),
+        }
+    }
+
+    assert_eq!(client.calls(&LevelRequest::new(1)), 1);
+    assert_eq!(client.calls(&SongInfoRequest::new(10)), 1);
+}
+
+#[test]
+fn level_without_custom_song_needs_no_song_request() {
+    let (gdcf, client) = common::gdcf();
+
+    client.respond(&LevelRequest::new(1), common::level(1, 5, None));
+
+    let entry = gdcf
+        .level(1u64, CachePolicy::CacheFirst)
+        .unwrap()
+        .upgrade::<Level<Option<NewgroundsSong>, u64>>()
+        .wait()
+        .unwrap();
+
+    match entry {
+        CacheEntry::Cached(level, _) => assert_eq!(level.base.custom_song, None),
+        entry => panic!("Expected cached level, got {:?}", entry),
+    }
+
+    assert_eq!(client.total_calls(), 1);
+}
+
+#[test]
+fn level_creator_upgrade() {
+    let (gdcf, client) = common::gdcf();
+
+    client.respond(&LevelRequest::new(1), common::level(1, 5, None));
+    client.respond_more(&creator_search(5), vec![common::partial_level(1, 5, None)], vec![
+        Secondary::Creator(common::creator(5)),
+    ]);
+
+    let entry = gdcf
+        .level(1u64, CachePolicy::CacheFirst)
+        .unwrap()
+        .upgrade::<Level<Option<u64>, Option<Creator>>>()
+        .wait()
+        .unwrap();
+
+    match entry {
+        CacheEntry::Cached(level, _) => assert_eq!(level.base.creator, Some(common::creator(5))),
+        entry => panic!("Expected cached level, got {:?}", entry),
+    }
+
+    assert_eq!(client.calls(&LevelRequest::new(1)), 1);
+    assert_eq!(client.calls(&creator_search(5)), 1);
+}
+
+#[test]
+fn partial_levels_to_full_levels() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelsRequest::default();
+
+    // The level list already contains the songs and creators of its levels, so upgrading the
+    // songs and creators must not make any further requests
+    client.respond_more(
+        &request,
+        vec![common::partial_level(1, 5, Some(10)), common::partial_level(2, 6, None)],
+        vec![
+            Secondary::NewgroundsSong(common::song(10)),
+            Secondary::Creator(common::creator(5)),
+            Secondary::Creator(common::creator(6)),
+        ],
+    );
+    client.respond(&LevelRequest::new(1), common::level(1, 5, Some(10)));
+    client.respond(&LevelRequest::new(2), common::level(2, 6, None));
+
+    for _ in 0..2 {
+        let entry = gdcf
+            .levels(request.clone(), CachePolicy::CacheFirst)
+            .unwrap()
+            .upgrade_all::<PartialLevel<Option<u64>, Option<Creator>>>()
+            .upgrade_all::<Level<Option<u64>, Option<Creator>>>()
+            .upgrade_all::<Level<Option<NewgroundsSong>, Option<Creator>>>()
+            .wait()
+            .unwrap();
+
+        match entry {
+            CacheEntry::Cached(levels, _) => {
+                assert_eq!(levels.len(), 2);
+                assert_eq!(levels[0].base.custom_song, Some(common::song(10)));
+                assert_eq!(levels[0].base.creator, Some(common::creator(5)));
+                assert_eq!(levels[1].base.custom_song, None);
+                assert_eq!(levels[1].base.creator, Some(common::creator(6)));
+            },
+            entry => panic!("Expected cached levels, got {:?}", entry),
+        }
+    }
+
+    assert_eq!(client.calls(&request), 1);
+    assert_eq!(client.calls(&LevelRequest::new(1)), 1);
+    assert_eq!(client.calls(&LevelRequest::new(2)), 1);
+    assert_eq!(client.total_calls(), 3);
+}
+
+#[test]
+fn cache_only_keeps_object_without_cached_upgrade_data() {
+    let (gdcf, client) = common::gdcf();
+
+    client.respond(&LevelRequest::new(1), common::level(1, 5, Some(10)));
+
+    gdcf.level(1u64, CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    let entry = gdcf
+        .level(1u64, CachePolicy::CacheOnly)
+        .unwrap()
+        .upgrade::<Level<Option<NewgroundsSong>, u64>>()
+        .upgrade::<Level<Option<NewgroundsSong>, Option<Creator>>>()
+        .wait()
+        .unwrap();
+
+    match entry {
+        CacheEntry::Cached(level, _) => {
+            assert_eq!(level.base.level_id, 1);
+            assert_eq!(level.base.custom_song, None);
+            assert_eq!(level.base.creator, None);
+        },
+        entry => panic!("Expected cached level, got {:?}", entry),
+    }
+
+    assert_eq!(client.total_calls(), 1);
+}
+
+#[test]
+fn cache_only_without_required_upgrade_data() {
+    let (gdcf, client) = common::gdcf();
+    let request = LevelsRequest::default();
+
+    client.respond(&request, vec![common::partial_level(1, 5, None)]);
+
+    gdcf.levels(request.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    let entry = gdcf
+        .levels(request.clone(), CachePolicy::CacheOnly)
+        .unwrap()
+        .upgrade_all::<Level<Option<u64>, u64>>()
+        .wait()
+        .unwrap();
+
+    match entry {
+        CacheEntry::Missing => (),
+        entry => panic!("Expected missing entry, got {:?}", entry),
+    }
+
+    assert_eq!(client.total_calls(), 1);
+}
@@ -0,0 +1,82 @@
+//! Tests for write requests invalidating the cached data they make outdated
+
+use futures::Future;
+use gdcf::{
+    api::{
+        mock::MockError,
+        request::{LevelCommentsRequest, LevelRequest, LevelsRequest, RateStarsRequest, UploadLevelCommentRequest},
+    },
+    cache::CachePolicy,
+    error::Error,
+};
+
+mod common;
+
+#[test]
+fn comment_upload_invalidates_comment_pages_of_level() {
+    let (gdcf, client) = common::gdcf();
+    let comments = LevelCommentsRequest::new(1);
+    let other_comments = LevelCommentsRequest::new(2);
+    let upload = UploadLevelCommentRequest::new(1, "Nice level");
+
+    client.respond(&comments, Vec::new());
+    client.respond(&other_comments, Vec::new());
+    client.respond(&upload, 100);
+
+    gdcf.level_comments(comments.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+    gdcf.level_comments(other_comments.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    assert_eq!(gdcf.upload_level_comment(upload.clone()).wait().unwrap(), 100);
+
+    gdcf.level_comments(comments.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+    gdcf.level_comments(other_comments.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    assert_eq!(client.calls(&comments), 2);
+    assert_eq!(client.calls(&other_comments), 1);
+}
+
+#[test]
+fn failed_write_does_not_invalidate() {
+    let (gdcf, client) = common::gdcf();
+    let comments = LevelCommentsRequest::new(1);
+    let upload = UploadLevelCommentRequest::new(1, "Nice level");
+
+    client.respond(&comments, Vec::new());
+    client.fail(&upload, MockError::Custom(String::from("Server on fire")));
+
+    gdcf.level_comments(comments.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    match gdcf.upload_level_comment(upload.clone()).wait() {
+        Err(Error::Api(_)) => (),
+        result => panic!("Expected API error, got {:?}", result),
+    }
+
+    gdcf.level_comments(comments.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+    assert_eq!(client.calls(&comments), 1);
+}
+
+#[test]
+fn rating_invalidates_level_and_level_lists() {
+    let (gdcf, client) = common::gdcf();
+    let levels = LevelsRequest::default();
+    let other_levels = LevelsRequest::default().page(1);
+    let rate = RateStarsRequest::new(1, 5);
+
+    client.respond(&LevelRequest::new(1), common::level(1, 5, None));
+    client.respond(&levels, vec![common::partial_level(1, 5, None)]);
+    client.respond(&other_levels, vec![common::partial_level(2, 5, None)]);
+    client.respond(&rate, ());
+
+    for _ in 0..2 {
+        gdcf.level(1u64, CachePolicy::CacheFirst).unwrap().wait().unwrap();
+        gdcf.levels(levels.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+        gdcf.levels(other_levels.clone(), CachePolicy::CacheFirst).unwrap().wait().unwrap();
+
+        gdcf.rate_stars(rate).wait().unwrap();
+    }
+
+    assert_eq!(client.calls(&LevelRequest::new(1)), 2);
+    assert_eq!(client.calls(&levels), 2);
+    assert_eq!(client.calls(&other_levels), 1);
+}
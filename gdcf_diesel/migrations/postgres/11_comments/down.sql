@@ -0,0 +1,6 @@
+DROP TABLE level_comment_list_meta;
+DROP TABLE level_comment_request_results;
+DROP TABLE level_comment;
+DROP TABLE profile_comment_list_meta;
+DROP TABLE profile_comment_request_results;
+DROP TABLE profile_comment;
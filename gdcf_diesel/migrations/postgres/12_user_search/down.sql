@@ -0,0 +1,3 @@
+DROP TABLE user_search_meta;
+DROP TABLE user_search_results;
+DROP TABLE searched_user;
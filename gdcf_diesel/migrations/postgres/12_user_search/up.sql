@@ -0,0 +1,27 @@
+CREATE TABLE searched_user (
+    user_name TEXT NOT NULL,
+    user_id BIGINT PRIMARY KEY,
+    stars INTEGER NOT NULL,
+    demons SMALLINT NOT NULL,
+    index_6 TEXT,
+    creator_points SMALLINT NOT NULL,
+    icon_index SMALLINT NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    secret_coins SMALLINT NOT NULL,
+    icon_type SMALLINT NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id BIGINT NOT NULL,
+    user_coins SMALLINT NOT NULL
+);
+
+CREATE TABLE user_search_results (
+    request_hash BIGINT PRIMARY KEY,
+    user_id BIGINT NOT NULL
+);
+
+CREATE TABLE user_search_meta (
+    request_hash BIGINT PRIMARY KEY,
+    cached_at TIMESTAMP WITHOUT TIME ZONE,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,3 @@
+DROP TABLE special_level;
+DROP TABLE daily_level_meta;
+DROP TABLE daily_level;
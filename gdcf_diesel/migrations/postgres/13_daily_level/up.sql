@@ -0,0 +1,16 @@
+CREATE TABLE daily_level (
+    daily_key BIGINT PRIMARY KEY,
+    daily_index BIGINT NOT NULL,
+    seconds_left BIGINT NOT NULL
+);
+
+CREATE TABLE daily_level_meta (
+    daily_key BIGINT PRIMARY KEY,
+    cached_at TIMESTAMP WITHOUT TIME ZONE,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE special_level (
+    level_key BIGINT PRIMARY KEY,
+    level_id BIGINT NOT NULL
+);
@@ -0,0 +1,31 @@
+CREATE TABLE leaderboard_entry (
+    rank INTEGER NOT NULL,
+    user_name TEXT NOT NULL,
+    user_id BIGINT NOT NULL,
+    stars INTEGER NOT NULL,
+    demons SMALLINT NOT NULL,
+    creator_points SMALLINT NOT NULL,
+    icon_index SMALLINT NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    secret_coins SMALLINT NOT NULL,
+    icon_type SMALLINT NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id BIGINT PRIMARY KEY,
+    user_coins SMALLINT NOT NULL,
+    diamonds INTEGER NOT NULL
+);
+
+CREATE TABLE leaderboard_request_results (
+    account_id BIGINT NOT NULL,
+    request_hash BIGINT NOT NULL,
+    rank INTEGER NOT NULL,
+    previous_rank INTEGER,
+    PRIMARY KEY (account_id, request_hash)
+);
+
+CREATE TABLE leaderboard_meta (
+    request_hash BIGINT PRIMARY KEY,
+    cached_at TIMESTAMP WITHOUT TIME ZONE,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,3 @@
+DROP TABLE level_score_meta;
+DROP TABLE level_score_request_results;
+DROP TABLE level_score;
@@ -0,0 +1,30 @@
+CREATE TABLE level_score (
+    rank INTEGER NOT NULL,
+    user_name TEXT NOT NULL,
+    user_id BIGINT NOT NULL,
+    progress SMALLINT NOT NULL,
+    icon_index SMALLINT NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    coins SMALLINT NOT NULL,
+    icon_type SMALLINT NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id BIGINT PRIMARY KEY,
+    time_since_set TEXT NOT NULL
+);
+
+CREATE TABLE level_score_request_results (
+    account_id BIGINT NOT NULL,
+    request_hash BIGINT NOT NULL,
+    rank INTEGER NOT NULL,
+    progress SMALLINT NOT NULL,
+    coins SMALLINT NOT NULL,
+    time_since_set TEXT NOT NULL,
+    PRIMARY KEY (account_id, request_hash)
+);
+
+CREATE TABLE level_score_meta (
+    request_hash BIGINT PRIMARY KEY,
+    cached_at TIMESTAMP WITHOUT TIME ZONE,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,9 @@
+CREATE TABLE level_comment_pages (
+    request_hash BIGINT PRIMARY KEY,
+    level_id BIGINT NOT NULL
+);
+
+CREATE TABLE profile_comment_pages (
+    request_hash BIGINT PRIMARY KEY,
+    account_id BIGINT NOT NULL
+);
@@ -0,0 +1,36 @@
+CREATE TABLE message (
+    message_id BIGINT PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT,
+    user_name TEXT NOT NULL,
+    age TEXT NOT NULL,
+    is_read BOOLEAN NOT NULL,
+    is_sender BOOLEAN NOT NULL
+);
+
+CREATE TABLE message_request_results (
+    message_id BIGINT NOT NULL,
+    request_hash BIGINT NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (message_id, request_hash)
+);
+
+CREATE TABLE message_list_meta (
+    request_hash BIGINT PRIMARY KEY,
+    cached_at TIMESTAMP WITHOUT TIME ZONE,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE message_pages (
+    request_hash BIGINT PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    sent BOOLEAN NOT NULL
+);
+
+CREATE TABLE message_meta (
+    message_id BIGINT PRIMARY KEY,
+    cached_at TIMESTAMP WITHOUT TIME ZONE,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,51 @@
+CREATE TABLE profile_comment (
+    comment_id TEXT PRIMARY KEY,
+    content TEXT,
+    likes INTEGER NOT NULL,
+    time_since_post TEXT NOT NULL
+);
+
+CREATE TABLE profile_comment_request_results (
+    comment_id TEXT NOT NULL,
+    request_hash INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (comment_id, request_hash)
+);
+
+CREATE TABLE profile_comment_list_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE level_comment (
+    comment_id TEXT PRIMARY KEY,
+    content TEXT,
+    user_id TEXT NOT NULL,
+    likes INTEGER NOT NULL,
+    is_flagged_spam BOOLEAN NOT NULL,
+    time_since_post TEXT NOT NULL,
+    progress INTEGER,
+    is_elder_mod BOOLEAN NOT NULL,
+    special_color INTEGER,
+    user_name TEXT,
+    user_icon_index INTEGER,
+    user_primary_color INTEGER,
+    user_secondary_color INTEGER,
+    user_icon_type INTEGER,
+    user_has_glow BOOLEAN,
+    user_account_id INTEGER
+);
+
+CREATE TABLE level_comment_request_results (
+    comment_id TEXT NOT NULL,
+    request_hash INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (comment_id, request_hash)
+);
+
+CREATE TABLE level_comment_list_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,27 @@
+CREATE TABLE searched_user (
+    user_name TEXT NOT NULL,
+    user_id INTEGER PRIMARY KEY,
+    stars INTEGER NOT NULL,
+    demons INTEGER NOT NULL,
+    index_6 TEXT,
+    creator_points INTEGER NOT NULL,
+    icon_index INTEGER NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    secret_coins INTEGER NOT NULL,
+    icon_type INTEGER NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id INTEGER NOT NULL,
+    user_coins INTEGER NOT NULL
+);
+
+CREATE TABLE user_search_results (
+    request_hash INTEGER PRIMARY KEY,
+    user_id INTEGER NOT NULL
+);
+
+CREATE TABLE user_search_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,16 @@
+CREATE TABLE daily_level (
+    daily_key INTEGER PRIMARY KEY,
+    daily_index INTEGER NOT NULL,
+    seconds_left INTEGER NOT NULL
+);
+
+CREATE TABLE daily_level_meta (
+    daily_key INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE special_level (
+    level_key INTEGER PRIMARY KEY,
+    level_id INTEGER NOT NULL
+);
@@ -0,0 +1,3 @@
+DROP TABLE leaderboard_meta;
+DROP TABLE leaderboard_request_results;
+DROP TABLE leaderboard_entry;
@@ -0,0 +1,31 @@
+CREATE TABLE leaderboard_entry (
+    rank INTEGER NOT NULL,
+    user_name TEXT NOT NULL,
+    user_id INTEGER NOT NULL,
+    stars INTEGER NOT NULL,
+    demons INTEGER NOT NULL,
+    creator_points INTEGER NOT NULL,
+    icon_index INTEGER NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    secret_coins INTEGER NOT NULL,
+    icon_type INTEGER NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id INTEGER PRIMARY KEY,
+    user_coins INTEGER NOT NULL,
+    diamonds INTEGER NOT NULL
+);
+
+CREATE TABLE leaderboard_request_results (
+    account_id INTEGER NOT NULL,
+    request_hash INTEGER NOT NULL,
+    rank INTEGER NOT NULL,
+    previous_rank INTEGER,
+    PRIMARY KEY (account_id, request_hash)
+);
+
+CREATE TABLE leaderboard_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,30 @@
+CREATE TABLE level_score (
+    rank INTEGER NOT NULL,
+    user_name TEXT NOT NULL,
+    user_id INTEGER NOT NULL,
+    progress INTEGER NOT NULL,
+    icon_index INTEGER NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    coins INTEGER NOT NULL,
+    icon_type INTEGER NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id INTEGER PRIMARY KEY,
+    time_since_set TEXT NOT NULL
+);
+
+CREATE TABLE level_score_request_results (
+    account_id INTEGER NOT NULL,
+    request_hash INTEGER NOT NULL,
+    rank INTEGER NOT NULL,
+    progress INTEGER NOT NULL,
+    coins INTEGER NOT NULL,
+    time_since_set TEXT NOT NULL,
+    PRIMARY KEY (account_id, request_hash)
+);
+
+CREATE TABLE level_score_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
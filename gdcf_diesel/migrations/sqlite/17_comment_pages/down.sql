@@ -0,0 +1,2 @@
+DROP TABLE level_comment_pages;
+DROP TABLE profile_comment_pages;
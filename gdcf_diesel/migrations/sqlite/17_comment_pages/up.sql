@@ -0,0 +1,9 @@
+CREATE TABLE level_comment_pages (
+    request_hash INTEGER PRIMARY KEY,
+    level_id INTEGER NOT NULL
+);
+
+CREATE TABLE profile_comment_pages (
+    request_hash INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL
+);
@@ -0,0 +1,5 @@
+DROP TABLE message_meta;
+DROP TABLE message_pages;
+DROP TABLE message_list_meta;
+DROP TABLE message_request_results;
+DROP TABLE message;
@@ -0,0 +1,36 @@
+CREATE TABLE message (
+    message_id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT,
+    user_name TEXT NOT NULL,
+    age TEXT NOT NULL,
+    is_read BOOLEAN NOT NULL,
+    is_sender BOOLEAN NOT NULL
+);
+
+CREATE TABLE message_request_results (
+    message_id INTEGER NOT NULL,
+    request_hash INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (message_id, request_hash)
+);
+
+CREATE TABLE message_list_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE message_pages (
+    request_hash INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL,
+    sent BOOLEAN NOT NULL
+);
+
+CREATE TABLE message_meta (
+    message_id INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
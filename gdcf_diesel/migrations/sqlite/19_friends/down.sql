@@ -0,0 +1,6 @@
+DROP TABLE friend_request_meta;
+DROP TABLE friend_request_request_results;
+DROP TABLE friend_request;
+DROP TABLE listed_user_meta;
+DROP TABLE listed_user_request_results;
+DROP TABLE listed_user;
@@ -0,0 +1,53 @@
+CREATE TABLE listed_user (
+    user_name TEXT NOT NULL,
+    user_id INTEGER NOT NULL,
+    icon_index INTEGER NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    icon_type INTEGER NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id INTEGER PRIMARY KEY,
+    index_18 TEXT,
+    is_new BOOLEAN NOT NULL
+);
+
+CREATE TABLE listed_user_request_results (
+    account_id INTEGER NOT NULL,
+    request_hash INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (account_id, request_hash)
+);
+
+CREATE TABLE listed_user_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE friend_request (
+    user_name TEXT NOT NULL,
+    user_id INTEGER NOT NULL,
+    icon_index INTEGER NOT NULL,
+    primary_color INTEGER NOT NULL,
+    secondary_color INTEGER NOT NULL,
+    icon_type INTEGER NOT NULL,
+    has_glow BOOLEAN NOT NULL,
+    account_id INTEGER NOT NULL,
+    request_id INTEGER PRIMARY KEY,
+    message TEXT,
+    time_since_sent TEXT NOT NULL,
+    is_new BOOLEAN NOT NULL
+);
+
+CREATE TABLE friend_request_request_results (
+    request_id INTEGER NOT NULL,
+    request_hash INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (request_id, request_hash)
+);
+
+CREATE TABLE friend_request_meta (
+    request_hash INTEGER PRIMARY KEY,
+    cached_at INTEGER,
+    absent BOOL NOT NULL DEFAULT FALSE
+);
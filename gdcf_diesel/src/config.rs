@@ -0,0 +1,96 @@
+use chrono::Duration;
+
+/// Configuration of how long the different kinds of objects stored in a [`Cache`](crate::Cache)
+/// stay valid before they are considered expired.
+///
+/// Every kind of object can be given its own expiry. Objects that rarely change (like full level
+/// data or newgrounds songs) can be kept around for a long time, while things like level lists can
+/// be refreshed every minute or so.
+///
+/// Entries written by [`Store::mark_absent`](gdcf::cache::Store::mark_absent) use their own,
+/// separate expiry, no matter for which kind of object they were written.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheConfig {
+    pub(crate) level_expiry: Duration,
+    pub(crate) levels_expiry: Duration,
+    pub(crate) user_expiry: Duration,
+    pub(crate) song_expiry: Duration,
+    pub(crate) creator_expiry: Duration,
+    pub(crate) comment_expiry: Duration,
+    pub(crate) message_expiry: Duration,
+    pub(crate) absent_expiry: Duration,
+}
+
+impl CacheConfig {
+    /// Constructs a new [`CacheConfig`] that uses the given expiry for all kinds of objects
+    pub fn new(expire_after: Duration) -> Self {
+        CacheConfig {
+            level_expiry: expire_after,
+            levels_expiry: expire_after,
+            user_expiry: expire_after,
+            song_expiry: expire_after,
+            creator_expiry: expire_after,
+            comment_expiry: expire_after,
+            message_expiry: expire_after,
+            absent_expiry: expire_after,
+        }
+    }
+
+    /// Sets the expiry of full levels, as retrieved by a
+    /// [`LevelRequest`](gdcf::api::request::LevelRequest)
+    pub fn level_expiry(mut self, expiry: Duration) -> Self {
+        self.level_expiry = expiry;
+        self
+    }
+
+    /// Sets the expiry of the results of [`LevelsRequest`](gdcf::api::request::LevelsRequest)s,
+    /// including the partial levels contained in them, as well as of map packs and gauntlets
+    pub fn levels_expiry(mut self, expiry: Duration) -> Self {
+        self.levels_expiry = expiry;
+        self
+    }
+
+    /// Sets the expiry of user profiles, user search results and leaderboards, including the
+    /// leaderboards of single levels, as well as of friend lists, friend requests and lists of
+    /// blocked users
+    pub fn user_expiry(mut self, expiry: Duration) -> Self {
+        self.user_expiry = expiry;
+        self
+    }
+
+    /// Sets the expiry of newgrounds songs
+    pub fn song_expiry(mut self, expiry: Duration) -> Self {
+        self.song_expiry = expiry;
+        self
+    }
+
+    /// Sets the expiry of level creators
+    pub fn creator_expiry(mut self, expiry: Duration) -> Self {
+        self.creator_expiry = expiry;
+        self
+    }
+
+    /// Sets the expiry of pages of level and profile comments
+    pub fn comment_expiry(mut self, expiry: Duration) -> Self {
+        self.comment_expiry = expiry;
+        self
+    }
+
+    /// Sets the expiry of pages of private messages, as well as of single downloaded messages
+    pub fn message_expiry(mut self, expiry: Duration) -> Self {
+        self.message_expiry = expiry;
+        self
+    }
+
+    /// Sets the expiry of entries that mark some object as absent
+    pub fn absent_expiry(mut self, expiry: Duration) -> Self {
+        self.absent_expiry = expiry;
+        self
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig::new(Duration::minutes(60))
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{key::DatabaseKey, meta::Entry, Cache};
+use chrono::Utc;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use gdcf::{
+    api::request::DailyLevelRequest,
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::level::DailyLevel;
+use log::{debug, warn};
+
+// Table storing the most recently retrieved daily level and weekly demon, keyed by whether they're
+// the weekly demon
+table! {
+    daily_level (daily_key) {
+        daily_key -> Int8,
+        daily_index -> Int8,
+        seconds_left -> Int8,
+    }
+}
+
+meta_table!(daily_level_meta, daily_key);
+
+impl Lookup<DailyLevelRequest> for Cache {
+    fn lookup(&self, key: &DailyLevelRequest) -> Result<CacheEntry<DailyLevel, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(daily_level_meta::table
+            .filter(daily_level_meta::daily_key.eq(key.database_key()))
+            .get_result(&connection));
+
+        let mut entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let (index, seconds_left): (i64, i64) = handle_missing!(daily_level::table
+            .filter(daily_level::daily_key.eq(key.database_key()))
+            .select((daily_level::daily_index, daily_level::seconds_left))
+            .get_result(&connection));
+
+        // Daily levels don't expire after a fixed duration, but whenever the servers replace them
+        let seconds_left = (seconds_left - (Utc::now().naive_utc() - entry.cached_at).num_seconds()).max(0);
+
+        entry.expired = seconds_left == 0;
+
+        Ok(CacheEntry::Cached(
+            DailyLevel {
+                index: index as u32,
+                seconds_left: seconds_left as u32,
+                weekly: key.weekly,
+            },
+            entry,
+        ))
+    }
+}
+
+impl Store<DailyLevelRequest> for Cache {
+    fn store(&mut self, daily: &DailyLevel, key: &DailyLevelRequest) -> Result<Entry, Self::Err> {
+        use crate::level::level_meta;
+
+        debug!("Storing {} under key {}", daily, key);
+
+        let connection = self.pool.get()?;
+
+        let previous: Option<i64> = daily_level::table
+            .filter(daily_level::daily_key.eq(key.database_key()))
+            .select(daily_level::daily_index)
+            .get_result(&connection)
+            .optional()?;
+
+        // The level cached for the special ID belongs to the previous daily level
+        if previous != Some(i64::from(daily.index)) {
+            diesel::delete(level_meta::table.filter(level_meta::level_id.eq(key.level_request().database_key()))).execute(&connection)?;
+        }
+
+        diesel::delete(daily_level::table.filter(daily_level::daily_key.eq(key.database_key()))).execute(&connection)?;
+        diesel::insert_into(daily_level::table)
+            .values((
+                daily_level::daily_key.eq(key.database_key()),
+                daily_level::daily_index.eq(i64::from(daily.index)),
+                daily_level::seconds_left.eq(i64::from(daily.seconds_left)),
+            ))
+            .execute(&connection)?;
+
+        let entry = Entry::new(key.database_key());
+
+        update_entry!(self, entry, daily_level_meta::table, daily_level_meta::daily_key);
+
+        Ok(entry)
+    }
+
+    fn mark_absent(&mut self, key: &DailyLevelRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking DailyLevelRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, daily_level_meta::table, daily_level_meta::daily_key);
+
+        Ok(entry)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache};
+use diesel::{backend::Backend, deserialize::FromSqlRow, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl};
+use gdcf::{
+    api::request::FriendRequestsRequest,
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::{
+    user::{Color, FriendRequest},
+    GameMode,
+};
+use log::{debug, warn};
+
+diesel_stuff! {
+    friend_request (request_id, FriendRequest) {
+        (user_name, name, String),
+        (user_id, user_id, u64),
+        (icon_index, icon_index, u16),
+        (primary_color, primary_color, Color),
+        (secondary_color, secondary_color, Color),
+        (icon_type, icon_type, GameMode),
+        (has_glow, has_glow, bool),
+        (account_id, account_id, u64),
+        (request_id, request_id, u64),
+        (message, message, Option<String>),
+        (time_since_sent, time_since_sent, String),
+        (is_new, is_new, bool)
+    }
+}
+
+// Metadata table associating the hashes of cached requests with the friend requests the request
+// returned, in the order they were returned in
+table! {
+    friend_request_request_results (request_id, request_hash) {
+        request_id -> Int8,
+        request_hash -> Int8,
+        position -> Int4,
+    }
+}
+
+// Metadata table storing information about when a page of friend requests was cached
+meta_table!(friend_request_meta, request_hash);
+
+allow_tables_to_appear_in_same_query!(friend_request_request_results, friend_request);
+
+joinable!(friend_request_request_results -> friend_request(request_id));
+
+impl Lookup<FriendRequestsRequest> for Cache {
+    fn lookup(&self, key: &FriendRequestsRequest) -> Result<CacheEntry<Vec<FriendRequest>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(friend_request_meta::table
+            .filter(friend_request_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let requests: Vec<_> = handle_missing!(friend_request::table
+            .inner_join(friend_request_request_results::table.on(friend_request::request_id.eq(friend_request_request_results::request_id)))
+            .filter(friend_request_request_results::request_hash.eq(key.database_key()))
+            .order(friend_request_request_results::position)
+            .select(friend_request::all_columns)
+            .load(&connection))
+        .into_iter()
+        .map(|row: Wrapped<_>| row.0)
+        .collect();
+
+        Ok(CacheEntry::Cached(requests, entry))
+    }
+}
+
+impl Store<FriendRequestsRequest> for Cache {
+    fn mark_absent(&mut self, key: &FriendRequestsRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of FriendRequestsRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, friend_request_meta::table, friend_request_meta::request_hash);
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, requests: &Vec<FriendRequest>, key: &FriendRequestsRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of FriendRequestsRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        diesel::delete(friend_request_request_results::table)
+            .filter(friend_request_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for (position, request) in requests.iter().enumerate() {
+            upsert!(self, request, friend_request::table, friend_request::request_id);
+
+            diesel::insert_into(friend_request_request_results::table)
+                .values((
+                    friend_request_request_results::request_id.eq(request.request_id as i64),
+                    friend_request_request_results::request_hash.eq(db_key),
+                    friend_request_request_results::position.eq(position as i32),
+                ))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, friend_request_meta::table, friend_request_meta::request_hash);
+
+        Ok(entry)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, insertable::Insertable, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::GauntletsRequest,
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::level::Gauntlet;
+use log::{debug, warn};
+
+diesel_stuff! {
+    gauntlet (gauntlet_id, Gauntlet) {
+        (gauntlet_id, gauntlet_id, u64),
+        (level_ids, level_ids, Vec<u64>)
+    }
+}
+
+// Table associating the hashes of cached requests with the gauntlets the request returned, in the
+// order they were returned in
+table! {
+    gauntlet_request_results (gauntlet_id, request_hash) {
+        gauntlet_id -> Int8,
+        request_hash -> Int8,
+        position -> Int4,
+    }
+}
+
+impl Insertable<gauntlet_request_results::table> for (i64, i64, i32) {
+    type Values = <(
+        diesel::dsl::Eq<gauntlet_request_results::gauntlet_id, i64>,
+        diesel::dsl::Eq<gauntlet_request_results::request_hash, i64>,
+        diesel::dsl::Eq<gauntlet_request_results::position, i32>,
+    ) as Insertable<gauntlet_request_results::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        (
+            gauntlet_request_results::gauntlet_id.eq(self.0),
+            gauntlet_request_results::request_hash.eq(self.1),
+            gauntlet_request_results::position.eq(self.2),
+        )
+            .values()
+    }
+}
+
+// Metadata table storing information about when the list of gauntlets was cached
+meta_table!(gauntlet_list_meta, request_hash);
+
+allow_tables_to_appear_in_same_query!(gauntlet_request_results, gauntlet);
+
+joinable!(gauntlet_request_results -> gauntlet(gauntlet_id));
+
+impl Lookup<GauntletsRequest> for Cache {
+    fn lookup(&self, key: &GauntletsRequest) -> Result<CacheEntry<Vec<Gauntlet>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(gauntlet_list_meta::table
+            .filter(gauntlet_list_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let gauntlets: Vec<_> = handle_missing!(gauntlet::table
+            .inner_join(gauntlet_request_results::table.on(gauntlet::gauntlet_id.eq(gauntlet_request_results::gauntlet_id)))
+            .filter(gauntlet_request_results::request_hash.eq(key.database_key()))
+            .order(gauntlet_request_results::position)
+            .select(gauntlet::all_columns)
+            .load(&connection))
+        .into_iter()
+        .map(|row: Wrapped<_>| row.0)
+        .collect();
+
+        Ok(CacheEntry::Cached(gauntlets, entry))
+    }
+}
+
+impl Store<GauntletsRequest> for Cache {
+    fn mark_absent(&mut self, key: &GauntletsRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of GauntletsRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, gauntlet_list_meta::table, gauntlet_list_meta::request_hash);
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, gauntlets: &Vec<Gauntlet>, key: &GauntletsRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of GauntletsRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        diesel::delete(gauntlet_request_results::table)
+            .filter(gauntlet_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for (position, gauntlet) in gauntlets.iter().enumerate() {
+            upsert!(self, gauntlet, gauntlet::table, gauntlet::gauntlet_id);
+
+            diesel::insert_into(gauntlet_request_results::table)
+                .values((gauntlet.gauntlet_id as i64, db_key, position as i32))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, gauntlet_list_meta::table, gauntlet_list_meta::request_hash);
+
+        Ok(entry)
+    }
+}
@@ -1,7 +1,12 @@
-use crate::level::SemiLevel;
+use crate::{config::CacheConfig, level::SemiLevel};
+use chrono::Duration;
 use derive_more::Display;
 use gdcf::{
-    api::request::{LevelRequest, LevelsRequest, UserRequest},
+    api::request::{
+        BlockedUsersRequest, DailyLevelRequest, DownloadMessageRequest, FriendListRequest, FriendRequestsRequest, GauntletsRequest,
+        LeaderboardRequest, LevelCommentsRequest, LevelRequest, LevelScoresRequest, LevelsRequest, MapPacksRequest, MessagesRequest,
+        ProfileCommentsRequest, SpecialLevelRequest, UserRequest, UserSearchRequest,
+    },
     cache::{CreatorKey, Key, NewgroundsSongKey},
 };
 use gdcf_model::level::PartialLevel;
@@ -12,12 +17,19 @@ use std::{
 
 pub(crate) trait DatabaseKey: Key {
     fn database_key(&self) -> i64;
+
+    /// The amount of time after which cache entries stored under this key expire
+    fn expire_after(&self, config: &CacheConfig) -> Duration;
 }
 
 impl DatabaseKey for CreatorKey {
     fn database_key(&self) -> i64 {
         self.0 as i64
     }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.creator_expiry
+    }
 }
 
 #[derive(Debug, Display)]
@@ -31,6 +43,10 @@ impl DatabaseKey for SemiLevelKey {
     fn database_key(&self) -> i64 {
         self.0 as i64
     }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.level_expiry
+    }
 }
 
 #[derive(Debug, Display)]
@@ -44,24 +60,64 @@ impl DatabaseKey for PartialLevelKey {
     fn database_key(&self) -> i64 {
         self.0 as i64
     }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.levels_expiry
+    }
 }
 
 impl DatabaseKey for LevelRequest {
     fn database_key(&self) -> i64 {
         self.level_id as i64
     }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.level_expiry
+    }
+}
+
+// Uses the IDs the servers assign to the daily level and weekly demon, which can never clash with
+// the ID of an actual level
+impl DatabaseKey for SpecialLevelRequest {
+    fn database_key(&self) -> i64 {
+        -1 - self.weekly as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.level_expiry
+    }
+}
+
+impl DatabaseKey for DailyLevelRequest {
+    fn database_key(&self) -> i64 {
+        self.weekly as i64
+    }
+
+    /// Daily levels expire whenever the servers replace them, so this is only used for entries
+    /// marking them as absent.
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.level_expiry
+    }
 }
 
 impl DatabaseKey for NewgroundsSongKey {
     fn database_key(&self) -> i64 {
         self.0 as i64
     }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.song_expiry
+    }
 }
 
 impl DatabaseKey for UserRequest {
     fn database_key(&self) -> i64 {
         self.user as i64
     }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.user_expiry
+    }
 }
 
 impl DatabaseKey for LevelsRequest {
@@ -77,6 +133,192 @@ impl DatabaseKey for LevelsRequest {
         self.request_type.hash(&mut state);
         self.page.hash(&mut state);
 
+        // Only hashed if set, so that the keys of requests that existed before are unchanged
+        if !self.followed.is_empty() {
+            self.followed.hash(&mut state);
+        }
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.levels_expiry
+    }
+}
+
+impl DatabaseKey for MapPacksRequest {
+    fn database_key(&self) -> i64 {
+        self.page as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.levels_expiry
+    }
+}
+
+impl DatabaseKey for GauntletsRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.levels_expiry
+    }
+}
+
+impl DatabaseKey for LeaderboardRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.user_expiry
+    }
+}
+
+impl DatabaseKey for LevelScoresRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.user_expiry
+    }
+}
+
+impl DatabaseKey for LevelCommentsRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.level_id.hash(&mut state);
+        self.sort_mode.hash(&mut state);
+        self.limit.hash(&mut state);
+        self.page.hash(&mut state);
+        self.total.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.comment_expiry
+    }
+}
+
+impl DatabaseKey for ProfileCommentsRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.account_id.hash(&mut state);
+        self.page.hash(&mut state);
+        self.total.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.comment_expiry
+    }
+}
+
+impl DatabaseKey for MessagesRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.account_id.hash(&mut state);
+        self.page.hash(&mut state);
+        self.total.hash(&mut state);
+        self.sent.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.message_expiry
+    }
+}
+
+impl DatabaseKey for DownloadMessageRequest {
+    fn database_key(&self) -> i64 {
+        self.message_id as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.message_expiry
+    }
+}
+
+// Friend lists and lists of blocked users share their tables, so their keys need to differ even
+// if they were requested for the same account
+impl DatabaseKey for FriendListRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.account_id.hash(&mut state);
+        false.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.user_expiry
+    }
+}
+
+impl DatabaseKey for BlockedUsersRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.account_id.hash(&mut state);
+        true.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.user_expiry
+    }
+}
+
+impl DatabaseKey for FriendRequestsRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        self.account_id.hash(&mut state);
+        self.page.hash(&mut state);
+        self.total.hash(&mut state);
+        self.outgoing.hash(&mut state);
+
+        state.finish() as i64
+    }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.user_expiry
+    }
+}
+
+impl DatabaseKey for UserSearchRequest {
+    fn database_key(&self) -> i64 {
+        let mut state = DefaultHasher::new();
+
+        // Usernames are case insensitive, so searching for "Stadust" and "stadust" yields the same user
+        self.search_string.to_lowercase().hash(&mut state);
+
         state.finish() as i64
     }
+
+    fn expire_after(&self, config: &CacheConfig) -> Duration {
+        config.user_expiry
+    }
 }
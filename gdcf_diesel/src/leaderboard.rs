@@ -0,0 +1,177 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache, Error};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, insertable::Insertable, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::LeaderboardRequest,
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::{
+    user::{Color, LeaderboardEntry},
+    GameMode,
+};
+use log::{debug, warn};
+use std::collections::HashMap;
+
+// The rank stored in here is the one the user had on whichever leaderboard we cached most recently.
+// The actual ranking of each leaderboard is stored in `leaderboard_request_results`.
+diesel_stuff! {
+    leaderboard_entry (account_id, LeaderboardEntry) {
+        (rank, rank, u32),
+        (user_name, name, String),
+        (user_id, user_id, u64),
+        (stars, stars, u32),
+        (demons, demons, u16),
+        (creator_points, creator_points, u16),
+        (icon_index, icon_index, u16),
+        (primary_color, primary_color, Color),
+        (secondary_color, secondary_color, Color),
+        (secret_coins, secret_coins, u8),
+        (icon_type, icon_type, GameMode),
+        (has_glow, has_glow, bool),
+        (account_id, account_id, u64),
+        (user_coins, user_coins, u16),
+        (diamonds, diamonds, u32)
+    }
+}
+
+// Table associating the hashes of cached requests with the users on the leaderboard the request
+// returned, the rank each user had on that leaderboard, and the rank they had on it before the
+// leaderboard was last refreshed (if they were on it at all).
+table! {
+    leaderboard_request_results (account_id, request_hash) {
+        account_id -> Int8,
+        request_hash -> Int8,
+        rank -> Int4,
+        previous_rank -> Nullable<Int4>,
+    }
+}
+
+impl Insertable<leaderboard_request_results::table> for (i64, i64, i32, Option<i32>) {
+    type Values = <(
+        diesel::dsl::Eq<leaderboard_request_results::account_id, i64>,
+        diesel::dsl::Eq<leaderboard_request_results::request_hash, i64>,
+        diesel::dsl::Eq<leaderboard_request_results::rank, i32>,
+        diesel::dsl::Eq<leaderboard_request_results::previous_rank, Option<i32>>,
+    ) as Insertable<leaderboard_request_results::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        (
+            leaderboard_request_results::account_id.eq(self.0),
+            leaderboard_request_results::request_hash.eq(self.1),
+            leaderboard_request_results::rank.eq(self.2),
+            leaderboard_request_results::previous_rank.eq(self.3),
+        )
+            .values()
+    }
+}
+
+// Metadata table storing information about when a leaderboard was cached
+meta_table!(leaderboard_meta, request_hash);
+
+allow_tables_to_appear_in_same_query!(leaderboard_request_results, leaderboard_entry);
+
+joinable!(leaderboard_request_results -> leaderboard_entry(account_id));
+
+impl Lookup<LeaderboardRequest> for Cache {
+    fn lookup(&self, key: &LeaderboardRequest) -> Result<CacheEntry<Vec<LeaderboardEntry>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(leaderboard_meta::table
+            .filter(leaderboard_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let entries: Vec<_> = handle_missing!(leaderboard_entry::table
+            .inner_join(
+                leaderboard_request_results::table.on(leaderboard_entry::account_id.eq(leaderboard_request_results::account_id))
+            )
+            .filter(leaderboard_request_results::request_hash.eq(key.database_key()))
+            .order(leaderboard_request_results::rank)
+            .select((leaderboard_entry::all_columns, leaderboard_request_results::rank))
+            .load(&connection))
+        .into_iter()
+        .map(|(row, rank): (Wrapped<LeaderboardEntry>, i32)| {
+            LeaderboardEntry {
+                rank: rank as u32,
+                ..row.0
+            }
+        })
+        .collect();
+
+        Ok(CacheEntry::Cached(entries, entry))
+    }
+}
+
+impl Store<LeaderboardRequest> for Cache {
+    fn mark_absent(&mut self, key: &LeaderboardRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of LeaderboardRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, leaderboard_meta::table, leaderboard_meta::request_hash);
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, entries: &Vec<LeaderboardEntry>, key: &LeaderboardRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of LeaderboardRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        let previous_ranks: HashMap<i64, i32> = leaderboard_request_results::table
+            .filter(leaderboard_request_results::request_hash.eq(db_key))
+            .select((leaderboard_request_results::account_id, leaderboard_request_results::rank))
+            .load(&conn)?
+            .into_iter()
+            .collect();
+
+        diesel::delete(leaderboard_request_results::table)
+            .filter(leaderboard_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for leaderboard_entry in entries {
+            upsert!(self, leaderboard_entry, leaderboard_entry::table, leaderboard_entry::account_id);
+
+            let account_id = leaderboard_entry.account_id as i64;
+
+            diesel::insert_into(leaderboard_request_results::table)
+                .values((account_id, db_key, leaderboard_entry.rank as i32, previous_ranks.get(&account_id).cloned()))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, leaderboard_meta::table, leaderboard_meta::request_hash);
+
+        Ok(entry)
+    }
+}
+
+impl Cache {
+    /// Retrieves the ranks the users currently cached for the given leaderboard had on it before it
+    /// was last refreshed, keyed by their account IDs
+    ///
+    /// Users that weren't on the leaderboard before the last refresh are not contained in the
+    /// returned map. If the leaderboard has only been cached once, the map is empty.
+    pub fn previous_ranks(&self, key: &LeaderboardRequest) -> Result<HashMap<u64, u32>, Error> {
+        let connection = self.pool.get()?;
+
+        let ranks: Vec<(i64, Option<i32>)> = leaderboard_request_results::table
+            .filter(leaderboard_request_results::request_hash.eq(key.database_key()))
+            .select((leaderboard_request_results::account_id, leaderboard_request_results::previous_rank))
+            .load(&connection)?;
+
+        Ok(ranks
+            .into_iter()
+            .filter_map(|(account_id, rank)| rank.map(|rank| (account_id as u64, rank as u32)))
+            .collect())
+    }
+}
@@ -1,13 +1,14 @@
 use crate::{
     key::{DatabaseKey, PartialLevelKey, SemiLevelKey},
     meta::Entry,
+    partial_level::{level_list_meta, level_request_results, partial_level_meta},
     wrap::Wrapped,
-    Cache,
+    Cache, Error,
 };
-use diesel::{backend::Backend, deserialize::FromSqlRow, ExpressionMethods, Queryable, RunQueryDsl};
+use diesel::{backend::Backend, deserialize::FromSqlRow, ExpressionMethods, QueryDsl, Queryable, RunQueryDsl};
 use gdcf::{
-    api::request::LevelRequest,
-    cache::{CacheEntry, Lookup, Store},
+    api::request::{LevelRequest, LikeItemRequest, LikedItem, RateDemonRequest, RateStarsRequest, SpecialLevelRequest, UploadLevelRequest},
+    cache::{CacheEntry, Invalidate, Lookup, Store},
 };
 use gdcf_model::level::{Level, Password};
 use log::{debug, warn};
@@ -89,16 +90,25 @@ impl Display for SemiLevel {
 
 meta_table!(level_meta, level_id);
 
+// Table mapping the special level IDs of the daily level and weekly demon to the ID of the level
+// most recently retrieved for them
+table! {
+    special_level (level_key) {
+        level_key -> Int8,
+        level_id -> Int8,
+    }
+}
+
 lookup_simply!(SemiLevelKey, level, level_meta, level_id);
 
 impl Lookup<LevelRequest> for Cache {
     fn lookup(&self, key: &LevelRequest) -> Result<CacheEntry<Level<Option<u64>, u64>, Entry>, Self::Err> {
         match self.lookup(&SemiLevelKey(key.level_id))? {
-            CacheEntry::Cached(semi_level, meta) => {
+            CacheEntry::Cached(semi_level, meta) | CacheEntry::Stale(semi_level, meta, _) => {
                 let semi_level: SemiLevel = semi_level;
 
                 match self.lookup(&PartialLevelKey(semi_level.level_id))? {
-                    CacheEntry::Cached(partial, _) =>
+                    CacheEntry::Cached(partial, _) | CacheEntry::Stale(partial, ..) =>
                         Ok(CacheEntry::Cached(
                             Level {
                                 base: partial,
@@ -142,3 +152,130 @@ impl Store<LevelRequest> for Cache {
         Ok(entry)
     }
 }
+
+// The entry returned for a special level is the one of the special ID, not the one of the level
+// itself
+impl Lookup<SpecialLevelRequest> for Cache {
+    fn lookup(&self, key: &SpecialLevelRequest) -> Result<CacheEntry<Level<Option<u64>, u64>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(level_meta::table
+            .filter(level_meta::level_id.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let level_id: i64 = handle_missing!(special_level::table
+            .filter(special_level::level_key.eq(key.database_key()))
+            .select(special_level::level_id)
+            .get_result(&connection));
+
+        match self.lookup(&LevelRequest::new(level_id as u64))? {
+            CacheEntry::Cached(level, _) | CacheEntry::Stale(level, ..) => Ok(CacheEntry::Cached(level, entry)),
+            _ => Ok(CacheEntry::Missing),
+        }
+    }
+}
+
+// Stores the level under its own ID, and remembers that it is the level the special ID currently
+// refers to
+impl Store<SpecialLevelRequest> for Cache {
+    fn store(&mut self, obj: &Level<Option<u64>, u64>, key: &SpecialLevelRequest) -> Result<Self::CacheEntryMeta, Self::Err> {
+        self.store(obj, &LevelRequest::new(obj.base.level_id))?;
+
+        debug!("Storing {} under key {}", obj, key);
+
+        let connection = self.pool.get()?;
+
+        diesel::delete(special_level::table.filter(special_level::level_key.eq(key.database_key()))).execute(&connection)?;
+        diesel::insert_into(special_level::table)
+            .values((
+                special_level::level_key.eq(key.database_key()),
+                special_level::level_id.eq(obj.base.level_id as i64),
+            ))
+            .execute(&connection)?;
+
+        let entry = Entry::new(key.database_key());
+
+        update_entry!(self, entry, level_meta::table, level_meta::level_id);
+
+        Ok(entry)
+    }
+
+    fn mark_absent(&mut self, key: &SpecialLevelRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+        update_entry!(self, entry, level_meta::table, level_meta::level_id);
+        Ok(entry)
+    }
+}
+
+impl Cache {
+    /// Removes the given level, and all level lists containing it, from the cache
+    ///
+    /// The objects themselves are kept, only their metadata is deleted, which makes them count as
+    /// missing on the next lookup
+    fn invalidate_level(&self, level_id: u64) -> Result<(), Error> {
+        debug!("Invalidating cached data of level {}", level_id);
+
+        let connection = self.pool.get()?;
+        let lists: Vec<i64> = level_request_results::table
+            .filter(level_request_results::level_id.eq(level_id as i64))
+            .select(level_request_results::request_hash)
+            .load(&connection)?;
+
+        diesel::delete(level_meta::table.filter(level_meta::level_id.eq(level_id as i64))).execute(&connection)?;
+        diesel::delete(partial_level_meta::table.filter(partial_level_meta::level_id.eq(level_id as i64))).execute(&connection)?;
+        diesel::delete(level_list_meta::table.filter(level_list_meta::request_hash.eq_any(&lists))).execute(&connection)?;
+
+        Ok(())
+    }
+}
+
+impl Invalidate<LikeItemRequest> for Cache {
+    fn invalidate(&mut self, request: &LikeItemRequest) -> Result<(), Error> {
+        match request.item {
+            LikedItem::Level(level_id) => self.invalidate_level(level_id),
+            LikedItem::LevelComment { level_id, .. } => self.invalidate_level_comments(level_id),
+            LikedItem::ProfileComment { account_id, .. } => self.invalidate_profile_comments(account_id),
+        }
+    }
+}
+
+impl Invalidate<RateStarsRequest> for Cache {
+    fn invalidate(&mut self, request: &RateStarsRequest) -> Result<(), Error> {
+        self.invalidate_level(request.level_id)
+    }
+}
+
+impl Invalidate<RateDemonRequest> for Cache {
+    fn invalidate(&mut self, request: &RateDemonRequest) -> Result<(), Error> {
+        self.invalidate_level(request.level_id)
+    }
+}
+
+impl Invalidate<UploadLevelRequest> for Cache {
+    // Only updates of existing levels make cached data outdated
+    fn invalidate(&mut self, request: &UploadLevelRequest) -> Result<(), Error> {
+        match request.level.base.level_id {
+            0 => Ok(()),
+            level_id => self.invalidate_level(level_id),
+        }
+    }
+
+    fn written(&mut self, request: &UploadLevelRequest, level_id: &u64) -> Result<(), Error> {
+        self.invalidate(request)?;
+
+        let mut level = request.level.clone();
+        level.base.level_id = *level_id;
+
+        debug!("Storing uploaded level {}", level);
+
+        self.store(&level, &LevelRequest::new(*level_id)).map(|_| ())
+    }
+}
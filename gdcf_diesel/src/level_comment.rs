@@ -0,0 +1,323 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache, Error};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, insertable::Insertable, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::{LevelCommentsRequest, UploadLevelCommentRequest},
+    cache::{CacheEntry, Invalidate, Lookup, Store},
+};
+use gdcf_model::{
+    comment::{CommentUser, LevelComment},
+    user::Color,
+    GameMode,
+};
+use log::{debug, warn};
+
+// Level comments embed the (partial) user that made them, so we cannot use the diesel_stuff! macro
+// here. The user columns are all nullable, since the comment user isn't always provided
+table! {
+    level_comment (comment_id) {
+        comment_id -> Text,
+        content -> Nullable<Text>,
+        user_id -> Text,
+        likes -> Int4,
+        is_flagged_spam -> Bool,
+        time_since_post -> Text,
+        progress -> Nullable<Int2>,
+        is_elder_mod -> Bool,
+        special_color -> Nullable<Int4>,
+        user_name -> Nullable<Text>,
+        user_icon_index -> Nullable<Int2>,
+        user_primary_color -> Nullable<Int4>,
+        user_secondary_color -> Nullable<Int4>,
+        user_icon_type -> Nullable<Int2>,
+        user_has_glow -> Nullable<Bool>,
+        user_account_id -> Nullable<Int8>,
+    }
+}
+
+type Row = (
+    String,
+    Option<String>,
+    String,
+    i32,
+    bool,
+    String,
+    Option<i16>,
+    bool,
+    Option<i32>,
+    Option<String>,
+    Option<i16>,
+    Option<i32>,
+    Option<i32>,
+    Option<i16>,
+    Option<bool>,
+    Option<i64>,
+);
+
+type SqlType = (
+    diesel::sql_types::Text,
+    diesel::sql_types::Nullable<diesel::sql_types::Text>,
+    diesel::sql_types::Text,
+    diesel::sql_types::Int4,
+    diesel::sql_types::Bool,
+    diesel::sql_types::Text,
+    diesel::sql_types::Nullable<diesel::sql_types::Int2>,
+    diesel::sql_types::Bool,
+    diesel::sql_types::Nullable<diesel::sql_types::Int4>,
+    diesel::sql_types::Nullable<diesel::sql_types::Text>,
+    diesel::sql_types::Nullable<diesel::sql_types::Int2>,
+    diesel::sql_types::Nullable<diesel::sql_types::Int4>,
+    diesel::sql_types::Nullable<diesel::sql_types::Int4>,
+    diesel::sql_types::Nullable<diesel::sql_types::Int2>,
+    diesel::sql_types::Nullable<diesel::sql_types::Bool>,
+    diesel::sql_types::Nullable<diesel::sql_types::Int8>,
+);
+
+type Values<'a> = (
+    diesel::dsl::Eq<level_comment::comment_id, &'a str>,
+    diesel::dsl::Eq<level_comment::content, Option<&'a str>>,
+    diesel::dsl::Eq<level_comment::user_id, &'a str>,
+    diesel::dsl::Eq<level_comment::likes, i32>,
+    diesel::dsl::Eq<level_comment::is_flagged_spam, bool>,
+    diesel::dsl::Eq<level_comment::time_since_post, &'a str>,
+    diesel::dsl::Eq<level_comment::progress, Option<i16>>,
+    diesel::dsl::Eq<level_comment::is_elder_mod, bool>,
+    diesel::dsl::Eq<level_comment::special_color, Option<i32>>,
+    diesel::dsl::Eq<level_comment::user_name, Option<&'a str>>,
+    diesel::dsl::Eq<level_comment::user_icon_index, Option<i16>>,
+    diesel::dsl::Eq<level_comment::user_primary_color, Option<i32>>,
+    diesel::dsl::Eq<level_comment::user_secondary_color, Option<i32>>,
+    diesel::dsl::Eq<level_comment::user_icon_type, Option<i16>>,
+    diesel::dsl::Eq<level_comment::user_has_glow, Option<bool>>,
+    diesel::dsl::Eq<level_comment::user_account_id, Option<i64>>,
+);
+
+fn values(comment: &LevelComment<Option<CommentUser>>) -> Values {
+    use level_comment::columns::*;
+
+    let user = comment.user.as_ref();
+
+    (
+        comment_id.eq(__for_values!(comment.comment_id, String)),
+        content.eq(__for_values!(comment.content, Option<String>)),
+        user_id.eq(__for_values!(comment.user_id, String)),
+        likes.eq(comment.likes),
+        is_flagged_spam.eq(comment.is_flagged_spam),
+        time_since_post.eq(__for_values!(comment.time_since_post, String)),
+        progress.eq(__for_values!(comment.progress, Option<u8>)),
+        is_elder_mod.eq(comment.is_elder_mod),
+        special_color.eq(__for_values!(comment.special_color, Option<Color>)),
+        user_name.eq(user.map(|user| &user.name[..])),
+        user_icon_index.eq(user.map(|user| __for_values!(user.icon_index, u16))),
+        user_primary_color.eq(user.map(|user| __for_values!(user.primary_color, Color))),
+        user_secondary_color.eq(user.map(|user| __for_values!(user.secondary_color, Color))),
+        user_icon_type.eq(user.map(|user| __for_values!(user.icon_type, GameMode))),
+        user_has_glow.eq(user.map(|user| user.has_glow)),
+        user_account_id.eq(user.and_then(|user| __for_values!(user.account_id, Option<u64>))),
+    )
+}
+
+impl<'a> Insertable<level_comment::table> for &'a LevelComment<Option<CommentUser>> {
+    type Values = <Values<'a> as Insertable<level_comment::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        values(self).values()
+    }
+}
+
+impl<'a> diesel::query_builder::AsChangeset for Wrapped<&'a LevelComment<Option<CommentUser>>> {
+    type Changeset = <Values<'a> as diesel::query_builder::AsChangeset>::Changeset;
+    type Target = level_comment::table;
+
+    fn as_changeset(self) -> Self::Changeset {
+        values(self.0).as_changeset()
+    }
+}
+
+impl<DB: Backend> Queryable<SqlType, DB> for Wrapped<LevelComment<Option<CommentUser>>>
+where
+    Row: FromSqlRow<SqlType, DB>,
+{
+    type Row = Row;
+
+    fn build(row: Self::Row) -> Self {
+        let user = match (row.9, row.10, row.11, row.12, row.13, row.14) {
+            (Some(name), Some(icon_index), Some(primary_color), Some(secondary_color), Some(icon_type), Some(has_glow)) =>
+                Some(CommentUser {
+                    name,
+                    icon_index: __for_queryable!(icon_index, u16),
+                    primary_color: __for_queryable!(primary_color, Color),
+                    secondary_color: __for_queryable!(secondary_color, Color),
+                    icon_type: __for_queryable!(icon_type, GameMode),
+                    has_glow,
+                    account_id: __for_queryable!(row.15, Option<u64>),
+                }),
+            _ => None,
+        };
+
+        Wrapped(LevelComment {
+            user,
+            comment_id: row.0,
+            content: row.1,
+            user_id: row.2,
+            likes: row.3,
+            is_flagged_spam: row.4,
+            time_since_post: row.5,
+            progress: __for_queryable!(row.6, Option<u8>),
+            is_elder_mod: row.7,
+            special_color: __for_queryable!(row.8, Option<Color>),
+        })
+    }
+}
+
+// Metadata table associating the hashes of cached requests with the comments the request returned,
+// in the order they were returned in
+table! {
+    level_comment_request_results (comment_id, request_hash) {
+        comment_id -> Text,
+        request_hash -> Int8,
+        position -> Int4,
+    }
+}
+
+impl<'a> Insertable<level_comment_request_results::table> for (&'a str, i64, i32) {
+    type Values = <(
+        diesel::dsl::Eq<level_comment_request_results::comment_id, &'a str>,
+        diesel::dsl::Eq<level_comment_request_results::request_hash, i64>,
+        diesel::dsl::Eq<level_comment_request_results::position, i32>,
+    ) as Insertable<level_comment_request_results::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        (
+            level_comment_request_results::comment_id.eq(self.0),
+            level_comment_request_results::request_hash.eq(self.1),
+            level_comment_request_results::position.eq(self.2),
+        )
+            .values()
+    }
+}
+
+// Metadata table storing information about when a whole page of level comments was cached
+meta_table!(level_comment_list_meta, request_hash);
+
+// Metadata table associating the hashes of cached requests with the level whose comments they
+// requested, so that all cached pages of a level can be invalidated once a comment is posted on it
+table! {
+    level_comment_pages (request_hash) {
+        request_hash -> Int8,
+        level_id -> Int8,
+    }
+}
+
+allow_tables_to_appear_in_same_query!(level_comment_request_results, level_comment);
+
+joinable!(level_comment_request_results -> level_comment(comment_id));
+
+impl Lookup<LevelCommentsRequest> for Cache {
+    fn lookup(&self, key: &LevelCommentsRequest) -> Result<CacheEntry<Vec<LevelComment<Option<CommentUser>>>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(level_comment_list_meta::table
+            .filter(level_comment_list_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let comments: Vec<_> = handle_missing!(level_comment::table
+            .inner_join(level_comment_request_results::table.on(level_comment::comment_id.eq(level_comment_request_results::comment_id)))
+            .filter(level_comment_request_results::request_hash.eq(key.database_key()))
+            .order(level_comment_request_results::position)
+            .select(level_comment::all_columns)
+            .load(&connection))
+        .into_iter()
+        .map(|row: Wrapped<_>| row.0)
+        .collect();
+
+        Ok(CacheEntry::Cached(comments, entry))
+    }
+}
+
+impl Store<LevelCommentsRequest> for Cache {
+    fn mark_absent(&mut self, key: &LevelCommentsRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of LevelCommentsRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, level_comment_list_meta::table, level_comment_list_meta::request_hash);
+        record_page(self, key)?;
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, comments: &Vec<LevelComment<Option<CommentUser>>>, key: &LevelCommentsRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of LevelCommentsRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        diesel::delete(level_comment_request_results::table)
+            .filter(level_comment_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for (position, comment) in comments.iter().enumerate() {
+            upsert!(self, comment, level_comment::table, level_comment::comment_id);
+
+            diesel::insert_into(level_comment_request_results::table)
+                .values((&comment.comment_id[..], db_key, position as i32))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, level_comment_list_meta::table, level_comment_list_meta::request_hash);
+        record_page(self, key)?;
+
+        Ok(entry)
+    }
+}
+
+fn record_page(cache: &Cache, key: &LevelCommentsRequest) -> Result<(), Error> {
+    let conn = cache.pool.get()?;
+
+    diesel::delete(level_comment_pages::table.filter(level_comment_pages::request_hash.eq(key.database_key()))).execute(&conn)?;
+    diesel::insert_into(level_comment_pages::table)
+        .values((
+            level_comment_pages::request_hash.eq(key.database_key()),
+            level_comment_pages::level_id.eq(key.level_id as i64),
+        ))
+        .execute(&conn)?;
+
+    Ok(())
+}
+
+impl Cache {
+    /// Removes all cached comment pages of the given level
+    pub(crate) fn invalidate_level_comments(&self, level_id: u64) -> Result<(), Error> {
+        debug!("Invalidating cached comment pages of level {}", level_id);
+
+        let conn = self.pool.get()?;
+        let pages: Vec<i64> = level_comment_pages::table
+            .filter(level_comment_pages::level_id.eq(level_id as i64))
+            .select(level_comment_pages::request_hash)
+            .load(&conn)?;
+
+        diesel::delete(level_comment_list_meta::table.filter(level_comment_list_meta::request_hash.eq_any(&pages))).execute(&conn)?;
+        diesel::delete(level_comment_request_results::table.filter(level_comment_request_results::request_hash.eq_any(&pages)))
+            .execute(&conn)?;
+        diesel::delete(level_comment_pages::table.filter(level_comment_pages::request_hash.eq_any(&pages))).execute(&conn)?;
+
+        Ok(())
+    }
+}
+
+impl Invalidate<UploadLevelCommentRequest> for Cache {
+    fn invalidate(&mut self, request: &UploadLevelCommentRequest) -> Result<(), Self::Err> {
+        self.invalidate_level_comments(request.level_id)
+    }
+}
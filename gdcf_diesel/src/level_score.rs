@@ -0,0 +1,157 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, insertable::Insertable, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::LevelScoresRequest,
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::{
+    user::{Color, LevelScore},
+    GameMode,
+};
+use log::{debug, warn};
+
+// The score related values stored in here are the ones of whichever level leaderboard we cached most
+// recently. The actual scores on each leaderboard are stored in `level_score_request_results`.
+diesel_stuff! {
+    level_score (account_id, LevelScore) {
+        (rank, rank, u32),
+        (user_name, name, String),
+        (user_id, user_id, u64),
+        (progress, progress, u8),
+        (icon_index, icon_index, u16),
+        (primary_color, primary_color, Color),
+        (secondary_color, secondary_color, Color),
+        (coins, coins, u8),
+        (icon_type, icon_type, GameMode),
+        (has_glow, has_glow, bool),
+        (account_id, account_id, u64),
+        (time_since_set, time_since_set, String)
+    }
+}
+
+// Table associating the hashes of cached requests with the players on the level leaderboard the
+// request returned, and the score each player has on that leaderboard
+table! {
+    level_score_request_results (account_id, request_hash) {
+        account_id -> Int8,
+        request_hash -> Int8,
+        rank -> Int4,
+        progress -> Int2,
+        coins -> Int2,
+        time_since_set -> Text,
+    }
+}
+
+impl<'a> Insertable<level_score_request_results::table> for (i64, i64, &'a LevelScore) {
+    type Values = <(
+        diesel::dsl::Eq<level_score_request_results::account_id, i64>,
+        diesel::dsl::Eq<level_score_request_results::request_hash, i64>,
+        diesel::dsl::Eq<level_score_request_results::rank, i32>,
+        diesel::dsl::Eq<level_score_request_results::progress, i16>,
+        diesel::dsl::Eq<level_score_request_results::coins, i16>,
+        diesel::dsl::Eq<level_score_request_results::time_since_set, &'a String>,
+    ) as Insertable<level_score_request_results::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        (
+            level_score_request_results::account_id.eq(self.0),
+            level_score_request_results::request_hash.eq(self.1),
+            level_score_request_results::rank.eq(self.2.rank as i32),
+            level_score_request_results::progress.eq(i16::from(self.2.progress)),
+            level_score_request_results::coins.eq(i16::from(self.2.coins)),
+            level_score_request_results::time_since_set.eq(&self.2.time_since_set),
+        )
+            .values()
+    }
+}
+
+// Metadata table storing information about when the leaderboard of a level was cached
+meta_table!(level_score_meta, request_hash);
+
+allow_tables_to_appear_in_same_query!(level_score_request_results, level_score);
+
+joinable!(level_score_request_results -> level_score(account_id));
+
+impl Lookup<LevelScoresRequest> for Cache {
+    fn lookup(&self, key: &LevelScoresRequest) -> Result<CacheEntry<Vec<LevelScore>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(level_score_meta::table
+            .filter(level_score_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let scores: Vec<_> = handle_missing!(level_score::table
+            .inner_join(level_score_request_results::table.on(level_score::account_id.eq(level_score_request_results::account_id)))
+            .filter(level_score_request_results::request_hash.eq(key.database_key()))
+            .order(level_score_request_results::rank)
+            .select((
+                level_score::all_columns,
+                level_score_request_results::rank,
+                level_score_request_results::progress,
+                level_score_request_results::coins,
+                level_score_request_results::time_since_set,
+            ))
+            .load(&connection))
+        .into_iter()
+        .map(
+            |(row, rank, progress, coins, time_since_set): (Wrapped<LevelScore>, i32, i16, i16, String)| {
+                LevelScore {
+                    rank: rank as u32,
+                    progress: progress as u8,
+                    coins: coins as u8,
+                    time_since_set,
+                    ..row.0
+                }
+            },
+        )
+        .collect();
+
+        Ok(CacheEntry::Cached(scores, entry))
+    }
+}
+
+impl Store<LevelScoresRequest> for Cache {
+    fn mark_absent(&mut self, key: &LevelScoresRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of LevelScoresRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, level_score_meta::table, level_score_meta::request_hash);
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, scores: &Vec<LevelScore>, key: &LevelScoresRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of LevelScoresRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        diesel::delete(level_score_request_results::table)
+            .filter(level_score_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for score in scores {
+            upsert!(self, score, level_score::table, level_score::account_id);
+
+            diesel::insert_into(level_score_request_results::table)
+                .values((score.account_id as i64, db_key, score))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, level_score_meta::table, level_score_meta::request_hash);
+
+        Ok(entry)
+    }
+}
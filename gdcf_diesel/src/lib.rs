@@ -5,11 +5,23 @@
 mod meta;
 #[macro_use]
 mod macros;
+mod config;
 mod creator;
+mod daily_level;
+mod friend_request;
+mod gauntlet;
 mod key;
+mod leaderboard;
 mod level;
+mod level_comment;
+mod level_score;
+mod listed_user;
+mod map_pack;
+mod message;
 mod partial_level;
 mod profile;
+mod profile_comment;
+mod searched_user;
 mod song;
 mod wrap;
 
@@ -22,7 +34,7 @@ extern crate diesel;
 extern crate diesel_migrations;
 
 use crate::{key::DatabaseKey, meta::DatabaseEntry, wrap::Wrapped};
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Utc};
 use diesel::{query_dsl::QueryDsl, r2d2::ConnectionManager, ExpressionMethods, RunQueryDsl};
 use failure::Fail;
 use gdcf::{
@@ -33,7 +45,7 @@ use gdcf_model::level::PartialLevel;
 use log::{debug, warn};
 use r2d2::Pool;
 
-pub use crate::meta::Entry;
+pub use crate::{config::CacheConfig, meta::Entry};
 
 // this means we cannot enable two features at once. Since diesel doesn't allow writing database
 // agnostic code, the alternative to this is wrapping everything in macros (like we used to do in
@@ -51,14 +63,25 @@ pub struct Cache {
     pool: Pool<ConnectionManager<PgConnection>>,
     #[cfg(feature = "sqlite")]
     pool: Pool<ConnectionManager<SqliteConnection>>,
-    expire_after: Duration,
+    config: CacheConfig,
 }
 
 impl Cache {
-    fn entry(&self, db_entry: DatabaseEntry) -> Entry {
+    /// Replaces this [`Cache`]'s [`CacheConfig`]
+    pub fn with_config(mut self, config: CacheConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    fn entry(&self, key: &impl DatabaseKey, db_entry: DatabaseEntry) -> Entry {
         let now = Utc::now();
         let then = DateTime::<Utc>::from_utc(db_entry.cached_at, Utc);
-        let expired = now - then > self.expire_after;
+        let expire_after = if db_entry.absent {
+            self.config.absent_expiry
+        } else {
+            key.expire_after(&self.config)
+        };
+        let expired = now - then > expire_after;
 
         Entry {
             expired,
@@ -73,14 +96,14 @@ impl Clone for Cache {
     fn clone(&self) -> Self {
         Cache {
             pool: self.pool.clone(),
-            expire_after: self.expire_after,
+            config: self.config,
         }
     }
 }
 
 #[cfg(feature = "pg")]
 mod postgres {
-    use super::Cache;
+    use super::{Cache, CacheConfig};
     use chrono::Duration;
     use diesel::r2d2::ConnectionManager;
     use r2d2::Pool;
@@ -91,7 +114,7 @@ mod postgres {
         pub fn postgres(database_url: impl Into<String>) -> Result<Self, r2d2::Error> {
             Ok(Cache {
                 pool: Pool::new(ConnectionManager::new(database_url.into()))?,
-                expire_after: Duration::minutes(60),
+                config: CacheConfig::new(Duration::minutes(60)),
             })
         }
 
@@ -103,7 +126,7 @@ mod postgres {
 
 #[cfg(feature = "sqlite")]
 mod sqlite {
-    use super::Cache;
+    use super::{Cache, CacheConfig};
     use chrono::Duration;
     use diesel::r2d2::ConnectionManager;
     use r2d2::Pool;
@@ -114,14 +137,14 @@ mod sqlite {
         pub fn in_memory() -> Result<Self, r2d2::Error> {
             Ok(Self {
                 pool: Pool::new(ConnectionManager::new(":memory:"))?,
-                expire_after: Duration::seconds(60),
+                config: CacheConfig::new(Duration::seconds(60)),
             })
         }
 
         pub fn sqlite(path: impl Into<String>) -> Result<Self, r2d2::Error> {
             Ok(Self {
                 pool: Pool::new(ConnectionManager::new(path.into()))?,
-                expire_after: Duration::seconds(60),
+                config: CacheConfig::new(Duration::seconds(60)),
             })
         }
 
@@ -173,7 +196,7 @@ impl Lookup<LevelsRequest> for Cache {
             .filter(level_list_meta::request_hash.eq(key.database_key()))
             .get_result(&connection));
 
-        let entry = self.entry(entry);
+        let entry = self.entry(key, entry);
 
         if entry.absent {
             return Ok(CacheEntry::MarkedAbsent(entry))
@@ -0,0 +1,144 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache, Error};
+use diesel::{backend::Backend, deserialize::FromSqlRow, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl};
+use gdcf::{
+    api::request::{BlockedUsersRequest, FriendListRequest},
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::{
+    user::{Color, ListedUser},
+    GameMode,
+};
+use log::{debug, warn};
+use std::fmt::Display;
+
+// Both friend lists and lists of blocked users are stored in here. The `is_new` flag stored is the
+// one of whichever list we cached most recently.
+diesel_stuff! {
+    listed_user (account_id, ListedUser) {
+        (user_name, name, String),
+        (user_id, user_id, u64),
+        (icon_index, icon_index, u16),
+        (primary_color, primary_color, Color),
+        (secondary_color, secondary_color, Color),
+        (icon_type, icon_type, GameMode),
+        (has_glow, has_glow, bool),
+        (account_id, account_id, u64),
+        (index_18, index_18, Option<String>),
+        (is_new, is_new, bool)
+    }
+}
+
+// Metadata table associating the hashes of cached requests with the users on the list the request
+// returned, in the order they were returned in
+table! {
+    listed_user_request_results (account_id, request_hash) {
+        account_id -> Int8,
+        request_hash -> Int8,
+        position -> Int4,
+    }
+}
+
+// Metadata table storing information about when a user list was cached
+meta_table!(listed_user_meta, request_hash);
+
+allow_tables_to_appear_in_same_query!(listed_user_request_results, listed_user);
+
+joinable!(listed_user_request_results -> listed_user(account_id));
+
+fn lookup_user_list<K: DatabaseKey + Display>(cache: &Cache, key: &K) -> Result<CacheEntry<Vec<ListedUser>, Entry>, Error> {
+    let connection = cache.pool.get()?;
+
+    let entry = handle_missing!(listed_user_meta::table
+        .filter(listed_user_meta::request_hash.eq(key.database_key()))
+        .get_result(&connection));
+
+    let entry = cache.entry(key, entry);
+
+    if entry.absent {
+        return Ok(CacheEntry::MarkedAbsent(entry))
+    }
+
+    let users: Vec<_> = handle_missing!(listed_user::table
+        .inner_join(listed_user_request_results::table.on(listed_user::account_id.eq(listed_user_request_results::account_id)))
+        .filter(listed_user_request_results::request_hash.eq(key.database_key()))
+        .order(listed_user_request_results::position)
+        .select(listed_user::all_columns)
+        .load(&connection))
+    .into_iter()
+    .map(|row: Wrapped<_>| row.0)
+    .collect();
+
+    Ok(CacheEntry::Cached(users, entry))
+}
+
+fn mark_user_list_absent<K: DatabaseKey + Display>(cache: &mut Cache, key: &K) -> Result<Entry, Error> {
+    warn!("Marking results of {} as absent!", key);
+
+    let entry = Entry::absent(key.database_key());
+
+    update_entry!(cache, entry, listed_user_meta::table, listed_user_meta::request_hash);
+
+    Ok(entry)
+}
+
+fn store_user_list<K: DatabaseKey + Display>(cache: &mut Cache, users: &[ListedUser], key: &K) -> Result<Entry, Error> {
+    debug!("Storing result of {}", key);
+
+    let db_key = key.database_key();
+
+    let conn = cache.pool.get()?;
+
+    diesel::delete(listed_user_request_results::table)
+        .filter(listed_user_request_results::request_hash.eq(db_key))
+        .execute(&conn)?;
+
+    for (position, user) in users.iter().enumerate() {
+        upsert!(cache, user, listed_user::table, listed_user::account_id);
+
+        diesel::insert_into(listed_user_request_results::table)
+            .values((
+                listed_user_request_results::account_id.eq(user.account_id as i64),
+                listed_user_request_results::request_hash.eq(db_key),
+                listed_user_request_results::position.eq(position as i32),
+            ))
+            .execute(&conn)?;
+    }
+
+    let entry = Entry::new(db_key);
+
+    update_entry!(cache, entry, listed_user_meta::table, listed_user_meta::request_hash);
+
+    Ok(entry)
+}
+
+impl Lookup<FriendListRequest> for Cache {
+    fn lookup(&self, key: &FriendListRequest) -> Result<CacheEntry<Vec<ListedUser>, Entry>, Self::Err> {
+        lookup_user_list(self, key)
+    }
+}
+
+impl Store<FriendListRequest> for Cache {
+    fn mark_absent(&mut self, key: &FriendListRequest) -> Result<Entry, Self::Err> {
+        mark_user_list_absent(self, key)
+    }
+
+    fn store(&mut self, users: &Vec<ListedUser>, key: &FriendListRequest) -> Result<Entry, Self::Err> {
+        store_user_list(self, users, key)
+    }
+}
+
+impl Lookup<BlockedUsersRequest> for Cache {
+    fn lookup(&self, key: &BlockedUsersRequest) -> Result<CacheEntry<Vec<ListedUser>, Entry>, Self::Err> {
+        lookup_user_list(self, key)
+    }
+}
+
+impl Store<BlockedUsersRequest> for Cache {
+    fn mark_absent(&mut self, key: &BlockedUsersRequest) -> Result<Entry, Self::Err> {
+        mark_user_list_absent(self, key)
+    }
+
+    fn store(&mut self, users: &Vec<ListedUser>, key: &BlockedUsersRequest) -> Result<Entry, Self::Err> {
+        store_user_list(self, users, key)
+    }
+}
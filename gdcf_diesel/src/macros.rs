@@ -40,6 +40,7 @@ macro_rules! __diesel_type {
     (String) => {Text};
     (Option<$t: ident>) => {Nullable<__diesel_type!($t)>};
     (Vec<u8>) => {Binary};
+    (Vec<u64>) => {Text};
     (LevelRating) => {Text};
     (LevelLength) => {Text};
     (Password) => {Nullable<Text>};
@@ -47,6 +48,7 @@ macro_rules! __diesel_type {
     (GameVersion) => {Int2};
     (MainSong) => {Int2};
     (ModLevel) => {Int2};
+    (GameMode) => {Int2};
     (Color) => {Int4};
 }
 
@@ -66,6 +68,7 @@ macro_rules! __ref_if_not_copy {
     (Option<String>) => {Option<&'a str>};
     (Option<$t: ident>) => {Option<__ref_if_not_copy!($t)>};
     (Vec<u8>) => {&'a [u8]};
+    (Vec<u64>) => {String};
     (LevelRating) => {String};
     (LevelLength) => {String};
     (Password) => {Option<&'a str>};
@@ -73,6 +76,7 @@ macro_rules! __ref_if_not_copy {
     (GameVersion) => {i16};
     (MainSong) => {i16};
     (ModLevel) => {i16};
+    (GameMode) => {i16};
     (Color) => {i32};
 }
 
@@ -91,6 +95,7 @@ macro_rules! __row_type {
     (String) => {String};
     (Option<$t: ident>) => {Option<__row_type!($t)>};
     (Vec<u8>) => {Vec<u8>};
+    (Vec<u64>) => {String};
     (LevelRating) => {String};
     (LevelLength) => {String};
     (Password) => {Option<String>};
@@ -98,6 +103,7 @@ macro_rules! __row_type {
     (GameVersion) => {i16};
     (MainSong) => {i16};
     (ModLevel) => {i16};
+    (GameMode) => {i16};
     (Color) => {i32};
 }
 
@@ -146,6 +152,12 @@ macro_rules! __for_queryable {
     ($value: expr, ModLevel) => {{
         ModLevel::from($value as u8)
     }};
+    ($value: expr, GameMode) => {{
+        GameMode::from($value as u8)
+    }};
+    ($value: expr, Vec<u64>) => {
+        $value.split(',').filter_map(|id| id.parse().ok()).collect()
+    };
     ($value: expr, Color) => {{
         if $value < 0 {
             Color::Unknown(-$value as u8)
@@ -212,6 +224,9 @@ macro_rules! __for_values {
     ($value: expr, Vec<u8>) => {
         &$value[..]
     };
+    ($value: expr, Vec<u64>) => {
+        $value.iter().map(u64::to_string).collect::<Vec<_>>().join(",")
+    };
     ($value: expr, LevelRating) => {
         $value.to_string()
     };
@@ -237,6 +252,10 @@ macro_rules! __for_values {
         let byte: u8 = $value.into();
         byte as i16
     }};
+    ($value: expr, GameMode) => {{
+        let byte: u8 = $value.into();
+        byte as i16
+    }};
     ($value: expr, Color) => {{
         match $value {
             Color::Unknown(idx) => -(idx as i32),
@@ -304,7 +323,7 @@ macro_rules! lookup_simply {
                     let entry = handle_missing!($meta_table::table
                         .filter($meta_table::$primary_column.eq(key.database_key()))
                         .get_result(&connection));
-                    let entry = self.entry(entry);
+                    let entry = self.entry(key, entry);
 
                     trace!("Successfully retrieved meta entry");
 
@@ -0,0 +1,126 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, insertable::Insertable, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::MapPacksRequest,
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::{
+    level::{LevelRating, MapPack},
+    user::Color,
+};
+use log::{debug, warn};
+
+diesel_stuff! {
+    map_pack (pack_id, MapPack) {
+        (pack_id, pack_id, u64),
+        (name, name, String),
+        (level_ids, level_ids, Vec<u64>),
+        (stars, stars, u8),
+        (coins, coins, u8),
+        (difficulty, difficulty, LevelRating),
+        (text_color, text_color, Color),
+        (bar_color, bar_color, Color)
+    }
+}
+
+// Table associating the hashes of cached requests with the map packs the request returned, in the
+// order they were returned in
+table! {
+    map_pack_request_results (pack_id, request_hash) {
+        pack_id -> Int8,
+        request_hash -> Int8,
+        position -> Int4,
+    }
+}
+
+impl Insertable<map_pack_request_results::table> for (i64, i64, i32) {
+    type Values = <(
+        diesel::dsl::Eq<map_pack_request_results::pack_id, i64>,
+        diesel::dsl::Eq<map_pack_request_results::request_hash, i64>,
+        diesel::dsl::Eq<map_pack_request_results::position, i32>,
+    ) as Insertable<map_pack_request_results::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        (
+            map_pack_request_results::pack_id.eq(self.0),
+            map_pack_request_results::request_hash.eq(self.1),
+            map_pack_request_results::position.eq(self.2),
+        )
+            .values()
+    }
+}
+
+// Metadata table storing information about when a whole page of map packs was cached
+meta_table!(map_pack_list_meta, request_hash);
+
+allow_tables_to_appear_in_same_query!(map_pack_request_results, map_pack);
+
+joinable!(map_pack_request_results -> map_pack(pack_id));
+
+impl Lookup<MapPacksRequest> for Cache {
+    fn lookup(&self, key: &MapPacksRequest) -> Result<CacheEntry<Vec<MapPack>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(map_pack_list_meta::table
+            .filter(map_pack_list_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let packs: Vec<_> = handle_missing!(map_pack::table
+            .inner_join(map_pack_request_results::table.on(map_pack::pack_id.eq(map_pack_request_results::pack_id)))
+            .filter(map_pack_request_results::request_hash.eq(key.database_key()))
+            .order(map_pack_request_results::position)
+            .select(map_pack::all_columns)
+            .load(&connection))
+        .into_iter()
+        .map(|row: Wrapped<_>| row.0)
+        .collect();
+
+        Ok(CacheEntry::Cached(packs, entry))
+    }
+}
+
+impl Store<MapPacksRequest> for Cache {
+    fn mark_absent(&mut self, key: &MapPacksRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of MapPacksRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, map_pack_list_meta::table, map_pack_list_meta::request_hash);
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, packs: &Vec<MapPack>, key: &MapPacksRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of MapPacksRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        diesel::delete(map_pack_request_results::table)
+            .filter(map_pack_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for (position, pack) in packs.iter().enumerate() {
+            upsert!(self, pack, map_pack::table, map_pack::pack_id);
+
+            diesel::insert_into(map_pack_request_results::table)
+                .values((pack.pack_id as i64, db_key, position as i32))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, map_pack_list_meta::table, map_pack_list_meta::request_hash);
+
+        Ok(entry)
+    }
+}
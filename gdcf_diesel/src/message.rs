@@ -0,0 +1,184 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache, Error};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, ExpressionMethods, JoinOnDsl, OptionalExtension, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::{DownloadMessageRequest, MessagesRequest, SendMessageRequest},
+    cache::{CacheEntry, Invalidate, Lookup, Store},
+};
+use gdcf_model::message::Message;
+use log::{debug, warn};
+
+diesel_stuff! {
+    message (message_id, Message) {
+        (message_id, message_id, u64),
+        (account_id, account_id, u64),
+        (user_id, user_id, u64),
+        (subject, subject, String),
+        (body, body, Option<String>),
+        (user_name, user_name, String),
+        (age, age, String),
+        (is_read, is_read, bool),
+        (is_sender, is_sender, bool)
+    }
+}
+
+// Metadata table associating the hashes of cached requests with the messages the request returned,
+// in the order they were returned in
+table! {
+    message_request_results (message_id, request_hash) {
+        message_id -> Int8,
+        request_hash -> Int8,
+        position -> Int4,
+    }
+}
+
+// Metadata table storing information about when a single message was downloaded
+meta_table!(message_meta, message_id);
+
+// Metadata table storing information about when a whole page of messages was cached
+meta_table!(message_list_meta, request_hash);
+
+// Metadata table associating the hashes of cached requests with the account whose messages they
+// requested, so that all cached pages of sent messages can be invalidated once the account sends
+// a message
+table! {
+    message_pages (request_hash) {
+        request_hash -> Int8,
+        account_id -> Int8,
+        sent -> Bool,
+    }
+}
+
+allow_tables_to_appear_in_same_query!(message_request_results, message);
+
+joinable!(message_request_results -> message(message_id));
+
+impl Lookup<MessagesRequest> for Cache {
+    fn lookup(&self, key: &MessagesRequest) -> Result<CacheEntry<Vec<Message>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(message_list_meta::table
+            .filter(message_list_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let messages: Vec<_> = handle_missing!(message::table
+            .inner_join(message_request_results::table.on(message::message_id.eq(message_request_results::message_id)))
+            .filter(message_request_results::request_hash.eq(key.database_key()))
+            .order(message_request_results::position)
+            .select(message::all_columns)
+            .load(&connection))
+        .into_iter()
+        .map(|row: Wrapped<_>| row.0)
+        .collect();
+
+        Ok(CacheEntry::Cached(messages, entry))
+    }
+}
+
+impl Store<MessagesRequest> for Cache {
+    fn mark_absent(&mut self, key: &MessagesRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of MessagesRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, message_list_meta::table, message_list_meta::request_hash);
+        record_page(self, key)?;
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, messages: &Vec<Message>, key: &MessagesRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of MessagesRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        diesel::delete(message_request_results::table)
+            .filter(message_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for (position, message) in messages.iter().enumerate() {
+            // Message lists don't contain the bodies of the messages, so we need to make sure not to
+            // overwrite the ones of messages that have already been downloaded
+            let body: Option<Option<String>> = message::table
+                .filter(message::message_id.eq(message.message_id as i64))
+                .select(message::body)
+                .get_result(&conn)
+                .optional()?;
+
+            match body {
+                Some(Some(body)) if message.body.is_none() => {
+                    let message = Message {
+                        body: Some(body),
+                        ..message.clone()
+                    };
+
+                    upsert!(self, &message, message::table, message::message_id);
+                },
+                _ => {
+                    upsert!(self, message, message::table, message::message_id);
+                },
+            }
+
+            diesel::insert_into(message_request_results::table)
+                .values((
+                    message_request_results::message_id.eq(message.message_id as i64),
+                    message_request_results::request_hash.eq(db_key),
+                    message_request_results::position.eq(position as i32),
+                ))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, message_list_meta::table, message_list_meta::request_hash);
+        record_page(self, key)?;
+
+        Ok(entry)
+    }
+}
+
+fn record_page(cache: &Cache, key: &MessagesRequest) -> Result<(), Error> {
+    let conn = cache.pool.get()?;
+
+    diesel::delete(message_pages::table.filter(message_pages::request_hash.eq(key.database_key()))).execute(&conn)?;
+    diesel::insert_into(message_pages::table)
+        .values((
+            message_pages::request_hash.eq(key.database_key()),
+            message_pages::account_id.eq(key.account_id as i64),
+            message_pages::sent.eq(key.sent),
+        ))
+        .execute(&conn)?;
+
+    Ok(())
+}
+
+store_simply!(DownloadMessageRequest, message, message_meta, message_id);
+lookup_simply!(DownloadMessageRequest, message, message_meta, message_id);
+
+impl Invalidate<SendMessageRequest> for Cache {
+    fn invalidate(&mut self, request: &SendMessageRequest) -> Result<(), Self::Err> {
+        debug!("Invalidating cached sent messages of account {}", request.account_id);
+
+        let conn = self.pool.get()?;
+        let pages: Vec<i64> = message_pages::table
+            .filter(message_pages::account_id.eq(request.account_id as i64))
+            .filter(message_pages::sent.eq(true))
+            .select(message_pages::request_hash)
+            .load(&conn)?;
+
+        diesel::delete(message_list_meta::table.filter(message_list_meta::request_hash.eq_any(&pages))).execute(&conn)?;
+        diesel::delete(message_request_results::table.filter(message_request_results::request_hash.eq_any(&pages))).execute(&conn)?;
+        diesel::delete(message_pages::table.filter(message_pages::request_hash.eq_any(&pages))).execute(&conn)?;
+
+        Ok(())
+    }
+}
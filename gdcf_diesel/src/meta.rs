@@ -66,6 +66,10 @@ impl CacheEntryMeta for Entry {
     fn is_absent(&self) -> bool {
         self.absent
     }
+
+    fn cached_at(&self) -> NaiveDateTime {
+        self.cached_at
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
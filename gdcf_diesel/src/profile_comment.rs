@@ -0,0 +1,170 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache, Error};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, insertable::Insertable, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::{ProfileCommentsRequest, UploadProfileCommentRequest},
+    cache::{CacheEntry, Invalidate, Lookup, Store},
+};
+use gdcf_model::comment::ProfileComment;
+use log::{debug, warn};
+
+diesel_stuff! {
+    profile_comment (comment_id, ProfileComment) {
+        (comment_id, comment_id, String),
+        (content, content, Option<String>),
+        (likes, likes, i32),
+        (time_since_post, time_since_post, String)
+    }
+}
+
+// Metadata table associating the hashes of cached requests with the comments the request returned,
+// in the order they were returned in
+table! {
+    profile_comment_request_results (comment_id, request_hash) {
+        comment_id -> Text,
+        request_hash -> Int8,
+        position -> Int4,
+    }
+}
+
+impl<'a> Insertable<profile_comment_request_results::table> for (&'a str, i64, i32) {
+    type Values = <(
+        diesel::dsl::Eq<profile_comment_request_results::comment_id, &'a str>,
+        diesel::dsl::Eq<profile_comment_request_results::request_hash, i64>,
+        diesel::dsl::Eq<profile_comment_request_results::position, i32>,
+    ) as Insertable<profile_comment_request_results::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        (
+            profile_comment_request_results::comment_id.eq(self.0),
+            profile_comment_request_results::request_hash.eq(self.1),
+            profile_comment_request_results::position.eq(self.2),
+        )
+            .values()
+    }
+}
+
+// Metadata table storing information about when a whole page of profile comments was cached
+meta_table!(profile_comment_list_meta, request_hash);
+
+// Metadata table associating the hashes of cached requests with the account whose comments they
+// requested, so that all cached pages of a account can be invalidated once a comment is posted on it
+table! {
+    profile_comment_pages (request_hash) {
+        request_hash -> Int8,
+        account_id -> Int8,
+    }
+}
+
+allow_tables_to_appear_in_same_query!(profile_comment_request_results, profile_comment);
+
+joinable!(profile_comment_request_results -> profile_comment(comment_id));
+
+impl Lookup<ProfileCommentsRequest> for Cache {
+    fn lookup(&self, key: &ProfileCommentsRequest) -> Result<CacheEntry<Vec<ProfileComment>, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(profile_comment_list_meta::table
+            .filter(profile_comment_list_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let comments: Vec<_> = handle_missing!(profile_comment::table
+            .inner_join(profile_comment_request_results::table.on(profile_comment::comment_id.eq(profile_comment_request_results::comment_id)))
+            .filter(profile_comment_request_results::request_hash.eq(key.database_key()))
+            .order(profile_comment_request_results::position)
+            .select(profile_comment::all_columns)
+            .load(&connection))
+        .into_iter()
+        .map(|row: Wrapped<_>| row.0)
+        .collect();
+
+        Ok(CacheEntry::Cached(comments, entry))
+    }
+}
+
+impl Store<ProfileCommentsRequest> for Cache {
+    fn mark_absent(&mut self, key: &ProfileCommentsRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of ProfileCommentsRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, profile_comment_list_meta::table, profile_comment_list_meta::request_hash);
+        record_page(self, key)?;
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, comments: &Vec<ProfileComment>, key: &ProfileCommentsRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing result of ProfileCommentsRequest with key {}", key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        diesel::delete(profile_comment_request_results::table)
+            .filter(profile_comment_request_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+
+        for (position, comment) in comments.iter().enumerate() {
+            upsert!(self, comment, profile_comment::table, profile_comment::comment_id);
+
+            diesel::insert_into(profile_comment_request_results::table)
+                .values((&comment.comment_id[..], db_key, position as i32))
+                .execute(&conn)?;
+        }
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, profile_comment_list_meta::table, profile_comment_list_meta::request_hash);
+        record_page(self, key)?;
+
+        Ok(entry)
+    }
+}
+
+fn record_page(cache: &Cache, key: &ProfileCommentsRequest) -> Result<(), Error> {
+    let conn = cache.pool.get()?;
+
+    diesel::delete(profile_comment_pages::table.filter(profile_comment_pages::request_hash.eq(key.database_key()))).execute(&conn)?;
+    diesel::insert_into(profile_comment_pages::table)
+        .values((
+            profile_comment_pages::request_hash.eq(key.database_key()),
+            profile_comment_pages::account_id.eq(key.account_id as i64),
+        ))
+        .execute(&conn)?;
+
+    Ok(())
+}
+
+impl Cache {
+    /// Removes all cached comment pages of the given account
+    pub(crate) fn invalidate_profile_comments(&self, account_id: u64) -> Result<(), Error> {
+        debug!("Invalidating cached comment pages of account {}", account_id);
+
+        let conn = self.pool.get()?;
+        let pages: Vec<i64> = profile_comment_pages::table
+            .filter(profile_comment_pages::account_id.eq(account_id as i64))
+            .select(profile_comment_pages::request_hash)
+            .load(&conn)?;
+
+        diesel::delete(profile_comment_list_meta::table.filter(profile_comment_list_meta::request_hash.eq_any(&pages))).execute(&conn)?;
+        diesel::delete(profile_comment_request_results::table.filter(profile_comment_request_results::request_hash.eq_any(&pages)))
+            .execute(&conn)?;
+        diesel::delete(profile_comment_pages::table.filter(profile_comment_pages::request_hash.eq_any(&pages))).execute(&conn)?;
+
+        Ok(())
+    }
+}
+
+impl Invalidate<UploadProfileCommentRequest> for Cache {
+    fn invalidate(&mut self, request: &UploadProfileCommentRequest) -> Result<(), Self::Err> {
+        self.invalidate_profile_comments(request.account_id)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{key::DatabaseKey, meta::Entry, wrap::Wrapped, Cache};
+use diesel::{
+    backend::Backend, deserialize::FromSqlRow, insertable::Insertable, ExpressionMethods, JoinOnDsl, QueryDsl, Queryable, RunQueryDsl,
+};
+use gdcf::{
+    api::request::UserSearchRequest,
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::{
+    user::{Color, SearchedUser},
+    GameMode,
+};
+use log::{debug, warn};
+
+diesel_stuff! {
+    searched_user (user_id, SearchedUser) {
+        (user_name, name, String),
+        (user_id, user_id, u64),
+        (stars, stars, u32),
+        (demons, demons, u16),
+        (index_6, index_6, Option<String>),
+        (creator_points, creator_points, u16),
+        (icon_index, icon_index, u16),
+        (primary_color, primary_color, Color),
+        (secondary_color, secondary_color, Color),
+        (secret_coins, secret_coins, u8),
+        (icon_type, icon_type, GameMode),
+        (has_glow, has_glow, bool),
+        (account_id, account_id, u64),
+        (user_coins, user_coins, u16)
+    }
+}
+
+// Table mapping the hash of a search string to the user id of the user the search returned
+table! {
+    user_search_results (request_hash) {
+        request_hash -> Int8,
+        user_id -> Int8,
+    }
+}
+
+impl Insertable<user_search_results::table> for (i64, i64) {
+    type Values = <(
+        diesel::dsl::Eq<user_search_results::request_hash, i64>,
+        diesel::dsl::Eq<user_search_results::user_id, i64>,
+    ) as Insertable<user_search_results::table>>::Values;
+
+    fn values(self) -> Self::Values {
+        (user_search_results::request_hash.eq(self.0), user_search_results::user_id.eq(self.1)).values()
+    }
+}
+
+// Metadata table storing information about when a search was cached
+meta_table!(user_search_meta, request_hash);
+
+allow_tables_to_appear_in_same_query!(user_search_results, searched_user);
+
+joinable!(user_search_results -> searched_user(user_id));
+
+impl Lookup<UserSearchRequest> for Cache {
+    fn lookup(&self, key: &UserSearchRequest) -> Result<CacheEntry<SearchedUser, Entry>, Self::Err> {
+        let connection = self.pool.get()?;
+
+        let entry = handle_missing!(user_search_meta::table
+            .filter(user_search_meta::request_hash.eq(key.database_key()))
+            .get_result(&connection));
+
+        let entry = self.entry(key, entry);
+
+        if entry.absent {
+            return Ok(CacheEntry::MarkedAbsent(entry))
+        }
+
+        let user: Wrapped<SearchedUser> = handle_missing!(searched_user::table
+            .inner_join(user_search_results::table.on(searched_user::user_id.eq(user_search_results::user_id)))
+            .filter(user_search_results::request_hash.eq(key.database_key()))
+            .select(searched_user::all_columns)
+            .get_result(&connection));
+
+        Ok(CacheEntry::Cached(user.0, entry))
+    }
+}
+
+impl Store<UserSearchRequest> for Cache {
+    fn mark_absent(&mut self, key: &UserSearchRequest) -> Result<Entry, Self::Err> {
+        warn!("Marking results of UserSearchRequest with key {} as absent!", key);
+
+        let entry = Entry::absent(key.database_key());
+
+        update_entry!(self, entry, user_search_meta::table, user_search_meta::request_hash);
+
+        Ok(entry)
+    }
+
+    fn store(&mut self, user: &SearchedUser, key: &UserSearchRequest) -> Result<Entry, Self::Err> {
+        debug!("Storing {} under key {}", user, key);
+
+        let db_key = key.database_key();
+
+        let conn = self.pool.get()?;
+
+        upsert!(self, user, searched_user::table, searched_user::user_id);
+
+        diesel::delete(user_search_results::table)
+            .filter(user_search_results::request_hash.eq(db_key))
+            .execute(&conn)?;
+        diesel::insert_into(user_search_results::table)
+            .values((db_key, user.user_id as i64))
+            .execute(&conn)?;
+
+        let entry = Entry::new(db_key);
+
+        update_entry!(self, entry, user_search_meta::table, user_search_meta::request_hash);
+
+        Ok(entry)
+    }
+}
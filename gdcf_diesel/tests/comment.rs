@@ -0,0 +1,133 @@
+//! Round-trips of level and profile comment pages through the sqlite cache
+
+use gdcf::{
+    api::request::{LevelCommentsRequest, ProfileCommentsRequest, UploadLevelCommentRequest, UploadProfileCommentRequest},
+    cache::{CacheEntry, Invalidate, Lookup},
+};
+use gdcf_model::{
+    comment::{CommentUser, LevelComment, ProfileComment},
+    user::Color,
+    GameMode,
+};
+
+mod common;
+
+fn comment_user(account_id: Option<u64>) -> CommentUser {
+    CommentUser {
+        name: String::from("Commenter"),
+        icon_index: 12,
+        primary_color: Color::Known(125, 255, 0),
+        secondary_color: Color::Unknown(42),
+        icon_type: GameMode::Ship,
+        has_glow: true,
+        account_id,
+    }
+}
+
+fn level_comment(comment_id: u64, user: Option<CommentUser>) -> LevelComment<Option<CommentUser>> {
+    LevelComment {
+        user,
+        content: Some(format!("Comment {}", comment_id)),
+        user_id: String::from("5"),
+        likes: -3,
+        is_flagged_spam: false,
+        time_since_post: String::from("1 hour"),
+        progress: Some(87),
+        is_elder_mod: true,
+        special_color: None,
+        comment_id: comment_id.to_string(),
+    }
+}
+
+fn profile_comment(comment_id: u64) -> ProfileComment {
+    ProfileComment {
+        content: Some(format!("Comment {}", comment_id)),
+        likes: 7,
+        comment_id: comment_id.to_string(),
+        time_since_post: String::from("2 days"),
+    }
+}
+
+#[test]
+fn level_comments_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(
+        &mut cache,
+        &LevelCommentsRequest::new(1),
+        vec![
+            level_comment(3, Some(comment_user(Some(8)))),
+            level_comment(1, Some(comment_user(None))),
+            level_comment(2, None),
+        ],
+    );
+}
+
+#[test]
+fn level_comment_pages_are_replaced() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &LevelCommentsRequest::new(1), vec![level_comment(1, None), level_comment(2, None)]);
+    common::assert_round_trip(&mut cache, &LevelCommentsRequest::new(1), vec![level_comment(3, None)]);
+}
+
+#[test]
+fn level_comments_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &LevelCommentsRequest::new(1));
+}
+
+#[test]
+fn level_comment_pages_are_separate() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &LevelCommentsRequest::new(1), vec![level_comment(1, None)]);
+
+    assert_eq!(cache.lookup(&LevelCommentsRequest::new(1).liked()).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&LevelCommentsRequest::new(1).page(1)).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&LevelCommentsRequest::new(2)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn profile_comments_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &ProfileCommentsRequest::new(1), vec![profile_comment(2), profile_comment(1)]);
+    common::assert_round_trip(&mut cache, &ProfileCommentsRequest::new(1), vec![profile_comment(3)]);
+}
+
+#[test]
+fn profile_comments_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &ProfileCommentsRequest::new(1));
+}
+
+#[test]
+fn level_comment_upload_invalidates_pages_of_level() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &LevelCommentsRequest::new(1), vec![level_comment(1, None)]);
+    common::assert_round_trip(&mut cache, &LevelCommentsRequest::new(1).liked().page(1), vec![level_comment(2, None)]);
+    common::assert_round_trip(&mut cache, &LevelCommentsRequest::new(2), vec![level_comment(3, None)]);
+
+    cache.invalidate(&UploadLevelCommentRequest::new(1, "Hello")).unwrap();
+
+    assert_eq!(cache.lookup(&LevelCommentsRequest::new(1)).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&LevelCommentsRequest::new(1).liked().page(1)).unwrap(), CacheEntry::Missing);
+    assert!(cache.lookup(&LevelCommentsRequest::new(2)).unwrap() != CacheEntry::Missing);
+}
+
+#[test]
+fn profile_comment_upload_invalidates_pages_of_account() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &ProfileCommentsRequest::new(1), vec![profile_comment(1)]);
+    common::assert_round_trip(&mut cache, &ProfileCommentsRequest::new(2), vec![profile_comment(2)]);
+
+    cache.invalidate(&UploadProfileCommentRequest::new(1, "Hello")).unwrap();
+
+    assert_eq!(cache.lookup(&ProfileCommentsRequest::new(1)).unwrap(), CacheEntry::Missing);
+    assert!(cache.lookup(&ProfileCommentsRequest::new(2)).unwrap() != CacheEntry::Missing);
+}
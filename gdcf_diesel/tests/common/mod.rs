@@ -0,0 +1,53 @@
+//! Fixtures shared between the integration tests
+//!
+//! Every test file only uses some of these, so not all of them are used in every test crate
+
+#![allow(dead_code)]
+
+use gdcf::cache::{CacheEntry, Key, Lookup, Store};
+use gdcf_diesel::Cache;
+use std::fmt::Debug;
+use tempfile::NamedTempFile;
+
+// Reuse the model fixtures of the gdcf tests, so both test suites agree on what objects look like
+#[path = "../../../gdcf/tests/common/mod.rs"]
+mod fixtures;
+
+#[allow(unused_imports)]
+pub use self::fixtures::*;
+
+/// Constructs a [`Cache`] backed by a freshly migrated sqlite database, returning it together with
+/// the temporary file the database lives in. The file is deleted once the handle is dropped.
+///
+/// We cannot use [`Cache::in_memory`] here, as every pooled connection would open its own database.
+pub fn cache() -> (Cache, NamedTempFile) {
+    let file = NamedTempFile::new().unwrap();
+    let cache = Cache::sqlite(file.path().to_str().unwrap()).unwrap();
+
+    cache.initialize().unwrap();
+
+    (cache, file)
+}
+
+/// Stores `object` under `key` and asserts that looking `key` up afterwards yields it unchanged
+pub fn assert_round_trip<K: Key>(cache: &mut Cache, key: &K, object: K::Result)
+where
+    Cache: Lookup<K> + Store<K>,
+    K::Result: Debug + PartialEq,
+{
+    let entry = cache.store(&object, key).unwrap();
+
+    assert_eq!(cache.lookup(key).unwrap(), CacheEntry::Cached(object, entry));
+}
+
+/// Marks `key` as absent and asserts that looking it up afterwards yields a
+/// [`CacheEntry::MarkedAbsent`]
+pub fn assert_marked_absent<K: Key>(cache: &mut Cache, key: &K)
+where
+    Cache: Lookup<K> + Store<K>,
+    K::Result: Debug + PartialEq,
+{
+    let entry = cache.mark_absent(key).unwrap();
+
+    assert_eq!(cache.lookup(key).unwrap(), CacheEntry::MarkedAbsent(entry));
+}
@@ -0,0 +1,69 @@
+//! Round-trips of the daily level and weekly demon through the sqlite cache
+
+use gdcf::{
+    api::request::{DailyLevelRequest, LevelRequest, SpecialLevelRequest},
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::level::{DailyLevel, Level};
+
+mod common;
+
+fn daily_level(index: u32, weekly: bool) -> DailyLevel {
+    DailyLevel {
+        index,
+        seconds_left: 3600,
+        weekly,
+    }
+}
+
+// diesel 1.4 cannot read back empty blobs, so the levels need some level data
+fn level(level_id: u64) -> Level<Option<u64>, u64> {
+    let mut level = common::level(level_id, 5, None);
+    level.level_data = b"H4sIAAAAAAAAC6WQ0Q2DMAxEF0Ky7LtLkPiqmIEBMkBX6PA1DUwQJR".to_vec();
+    level
+}
+
+#[test]
+fn daily_level_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &DailyLevelRequest::new(false), daily_level(1000, false));
+    common::assert_round_trip(&mut cache, &DailyLevelRequest::new(true), daily_level(100_050, true));
+    common::assert_round_trip(&mut cache, &DailyLevelRequest::new(false), daily_level(1001, false));
+}
+
+#[test]
+fn daily_level_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &DailyLevelRequest::new(true));
+}
+
+#[test]
+fn special_level_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &SpecialLevelRequest::new(false), level(1));
+    common::assert_round_trip(&mut cache, &SpecialLevelRequest::new(true), level(2));
+
+    // The levels are also cached under their own IDs
+    match cache.lookup(&LevelRequest::new(1)).unwrap() {
+        CacheEntry::Cached(cached, _) => assert_eq!(cached, level(1)),
+        entry => panic!("Expected cached level, got {:?}", entry),
+    }
+}
+
+#[test]
+fn new_daily_level_replaces_special_level() {
+    let (mut cache, _file) = common::cache();
+
+    cache.store(&daily_level(1000, false), &DailyLevelRequest::new(false)).unwrap();
+    common::assert_round_trip(&mut cache, &SpecialLevelRequest::new(false), level(1));
+
+    // Storing the same daily level again keeps the level cached for the special ID
+    cache.store(&daily_level(1000, false), &DailyLevelRequest::new(false)).unwrap();
+    assert!(cache.lookup(&SpecialLevelRequest::new(false)).unwrap() != CacheEntry::Missing);
+
+    cache.store(&daily_level(1001, false), &DailyLevelRequest::new(false)).unwrap();
+    assert_eq!(cache.lookup(&SpecialLevelRequest::new(false)).unwrap(), CacheEntry::Missing);
+}
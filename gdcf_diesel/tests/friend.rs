@@ -0,0 +1,94 @@
+//! Round-trips of friend lists, blocked users and friend requests through the sqlite cache
+
+use gdcf::{
+    api::request::{BlockedUsersRequest, FriendListRequest, FriendRequestsRequest},
+    cache::{CacheEntry, Lookup},
+};
+use gdcf_model::{
+    user::{Color, FriendRequest, ListedUser},
+    GameMode,
+};
+
+mod common;
+
+fn listed_user(account_id: u64) -> ListedUser {
+    ListedUser {
+        name: format!("User {}", account_id),
+        user_id: account_id + 1000,
+        icon_index: 7,
+        primary_color: Color::Known(185, 0, 255),
+        secondary_color: Color::Known(0, 255, 0),
+        icon_type: GameMode::Ball,
+        has_glow: false,
+        account_id,
+        index_18: None,
+        is_new: true,
+    }
+}
+
+fn friend_request(request_id: u64, account_id: u64) -> FriendRequest {
+    FriendRequest {
+        name: format!("User {}", account_id),
+        user_id: account_id + 1000,
+        icon_index: 7,
+        primary_color: Color::Known(185, 0, 255),
+        secondary_color: Color::Unknown(77),
+        icon_type: GameMode::Spider,
+        has_glow: true,
+        account_id,
+        request_id,
+        message: Some(String::from("Hi!")),
+        time_since_sent: String::from("1 day"),
+        is_new: false,
+    }
+}
+
+#[test]
+fn friend_list_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &FriendListRequest::new(1), vec![listed_user(3), listed_user(2)]);
+    common::assert_round_trip(&mut cache, &FriendListRequest::new(1), vec![listed_user(2)]);
+}
+
+#[test]
+fn blocked_users_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &BlockedUsersRequest::new(1), vec![listed_user(4)]);
+}
+
+#[test]
+fn user_lists_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &FriendListRequest::new(1));
+    common::assert_marked_absent(&mut cache, &BlockedUsersRequest::new(1));
+}
+
+#[test]
+fn friend_list_and_blocked_users_are_separate() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &FriendListRequest::new(1), vec![listed_user(2)]);
+
+    assert_eq!(cache.lookup(&BlockedUsersRequest::new(1)).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&FriendListRequest::new(2)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn friend_requests_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &FriendRequestsRequest::new(1), vec![friend_request(10, 2), friend_request(11, 3)]);
+    common::assert_round_trip(&mut cache, &FriendRequestsRequest::new(1).outgoing(), vec![friend_request(12, 4)]);
+
+    assert_eq!(cache.lookup(&FriendRequestsRequest::new(1).page(1)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn friend_requests_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &FriendRequestsRequest::new(1));
+}
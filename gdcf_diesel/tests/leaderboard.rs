@@ -0,0 +1,77 @@
+//! Round-trips of leaderboards through the sqlite cache
+
+use gdcf::{
+    api::request::{LeaderboardRequest, LeaderboardType},
+    cache::{CacheEntry, Lookup, Store},
+};
+use gdcf_model::{
+    user::{Color, LeaderboardEntry},
+    GameMode,
+};
+use std::collections::HashMap;
+
+mod common;
+
+fn leaderboard_entry(rank: u32, account_id: u64) -> LeaderboardEntry {
+    LeaderboardEntry {
+        rank,
+        name: format!("Player {}", account_id),
+        user_id: account_id + 1000,
+        stars: 20000 - rank,
+        demons: 300,
+        creator_points: 12,
+        icon_index: 35,
+        primary_color: Color::Known(255, 125, 0),
+        secondary_color: Color::Known(0, 0, 0),
+        secret_coins: 149,
+        icon_type: GameMode::Robot,
+        has_glow: true,
+        account_id,
+        user_coins: 1500,
+        diamonds: 50000,
+    }
+}
+
+#[test]
+fn leaderboard_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(
+        &mut cache,
+        &LeaderboardRequest::new(LeaderboardType::Top),
+        vec![leaderboard_entry(1, 10), leaderboard_entry(2, 20), leaderboard_entry(3, 30)],
+    );
+    common::assert_round_trip(&mut cache, &LeaderboardRequest::new(LeaderboardType::Creators), vec![leaderboard_entry(1, 20)]);
+}
+
+#[test]
+fn leaderboard_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &LeaderboardRequest::friends(1));
+}
+
+#[test]
+fn account_specific_leaderboards_are_separate() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &LeaderboardRequest::relative(1), vec![leaderboard_entry(500, 1)]);
+    common::assert_round_trip(&mut cache, &LeaderboardRequest::friends(1), vec![leaderboard_entry(1, 1)]);
+
+    assert_eq!(cache.lookup(&LeaderboardRequest::relative(2)).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&LeaderboardRequest::friends(2)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn previous_ranks_are_kept() {
+    let (mut cache, _file) = common::cache();
+    let request = LeaderboardRequest::new(LeaderboardType::Top);
+
+    cache.store(&vec![leaderboard_entry(1, 10), leaderboard_entry(2, 20)], &request).unwrap();
+
+    assert_eq!(cache.previous_ranks(&request).unwrap(), HashMap::new());
+
+    common::assert_round_trip(&mut cache, &request, vec![leaderboard_entry(1, 20), leaderboard_entry(2, 30)]);
+
+    assert_eq!(cache.previous_ranks(&request).unwrap(), vec![(20, 2)].into_iter().collect());
+}
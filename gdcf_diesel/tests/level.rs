@@ -0,0 +1,140 @@
+//! Round-trips of levels, level lists and the objects embedded in them through the sqlite cache
+
+use gdcf::{
+    api::request::{LevelRequest, LevelsRequest, LikeItemRequest, LikedItem, RateStarsRequest, SongInfoRequest, UploadLevelRequest},
+    cache::{CacheEntry, CreatorKey, Invalidate, Lookup, NewgroundsSongKey},
+};
+use gdcf_model::level::Password;
+
+mod common;
+
+#[test]
+fn level_list_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(
+        &mut cache,
+        &LevelsRequest::default(),
+        vec![common::partial_level(2, 5, Some(10)), common::partial_level(1, 6, None)],
+    );
+}
+
+#[test]
+fn level_lists_are_replaced() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &LevelsRequest::default(), vec![common::partial_level(1, 5, None)]);
+    common::assert_round_trip(&mut cache, &LevelsRequest::default(), vec![common::partial_level(2, 5, None)]);
+
+    assert_eq!(cache.lookup(&LevelsRequest::default().page(1)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn level_list_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &LevelsRequest::default().search(String::from("nothing")));
+}
+
+#[test]
+fn level_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    let mut level = common::level(1, 5, Some(10));
+    level.level_data = b"H4sIAAAAAAAAC6WQ0Q2DMAxEF0Ky7LtLkPiqmIEBMkBX6PA1DUwQJR".to_vec();
+    level.password = Password::PasswordCopy(String::from("123456"));
+
+    common::assert_round_trip(&mut cache, &LevelRequest::new(1), level);
+}
+
+#[test]
+fn level_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &LevelRequest::new(1));
+}
+
+#[test]
+fn creator_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &CreatorKey(5), common::creator(5));
+}
+
+#[test]
+fn song_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &NewgroundsSongKey(10), common::song(10));
+}
+
+#[test]
+fn song_info_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &SongInfoRequest::new(10), common::song(10));
+
+    // Songs retrieved on their own are the same ones embedded in levels
+    assert!(cache.lookup(&NewgroundsSongKey(10)).unwrap() != CacheEntry::Missing);
+}
+
+#[test]
+fn song_info_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &SongInfoRequest::new(10));
+}
+
+#[test]
+fn rating_invalidates_level_and_lists_containing_it() {
+    let (mut cache, _file) = common::cache();
+
+    let mut level = common::level(1, 5, None);
+    level.level_data = b"H4sIAAAAAAAAC6WQ0Q2DMAxEF0Ky7LtLkPiqmIEBMkBX6PA1DUwQJR".to_vec();
+
+    common::assert_round_trip(&mut cache, &LevelRequest::new(1), level);
+    common::assert_round_trip(&mut cache, &LevelsRequest::default(), vec![common::partial_level(1, 5, None)]);
+    common::assert_round_trip(&mut cache, &LevelsRequest::default().page(1), vec![common::partial_level(2, 5, None)]);
+
+    cache.invalidate(&RateStarsRequest::new(1, 5)).unwrap();
+
+    assert_eq!(cache.lookup(&LevelRequest::new(1)).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&LevelsRequest::default()).unwrap(), CacheEntry::Missing);
+    assert!(cache.lookup(&LevelsRequest::default().page(1)).unwrap() != CacheEntry::Missing);
+
+    cache.invalidate(&LikeItemRequest::new(LikedItem::Level(2))).unwrap();
+
+    assert_eq!(cache.lookup(&LevelsRequest::default().page(1)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn uploaded_level_is_stored_under_its_new_id() {
+    let (mut cache, _file) = common::cache();
+
+    let mut level = common::level(0, 5, None);
+    level.level_data = b"H4sIAAAAAAAAC6WQ0Q2DMAxEF0Ky7LtLkPiqmIEBMkBX6PA1DUwQJR".to_vec();
+
+    cache.written(&UploadLevelRequest::new(level.clone()), &42).unwrap();
+
+    level.base.level_id = 42;
+
+    match cache.lookup(&LevelRequest::new(42)).unwrap() {
+        CacheEntry::Cached(cached, _) => assert_eq!(cached, level),
+        entry => panic!("Expected cached level, got {:?}", entry),
+    }
+}
+
+#[test]
+fn level_update_invalidates_lists_containing_it() {
+    let (mut cache, _file) = common::cache();
+
+    let mut level = common::level(1, 5, None);
+    level.level_data = b"H4sIAAAAAAAAC6WQ0Q2DMAxEF0Ky7LtLkPiqmIEBMkBX6PA1DUwQJR".to_vec();
+
+    common::assert_round_trip(&mut cache, &LevelsRequest::default(), vec![common::partial_level(1, 5, None)]);
+
+    cache.written(&UploadLevelRequest::new(level), &1).unwrap();
+
+    assert_eq!(cache.lookup(&LevelsRequest::default()).unwrap(), CacheEntry::Missing);
+    assert!(cache.lookup(&LevelRequest::new(1)).unwrap() != CacheEntry::Missing);
+}
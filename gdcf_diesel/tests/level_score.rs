@@ -0,0 +1,60 @@
+//! Round-trips of level leaderboards through the sqlite cache
+
+use gdcf::{
+    api::request::{LevelScoresRequest, LevelScoresType},
+    cache::{CacheEntry, Lookup},
+};
+use gdcf_model::{
+    user::{Color, LevelScore},
+    GameMode,
+};
+
+mod common;
+
+fn level_score(rank: u32, account_id: u64, progress: u8) -> LevelScore {
+    LevelScore {
+        rank,
+        name: format!("Player {}", account_id),
+        user_id: account_id + 1000,
+        progress,
+        icon_index: 4,
+        primary_color: Color::Known(0, 255, 255),
+        secondary_color: Color::Unknown(99),
+        coins: 2,
+        icon_type: GameMode::Cube,
+        has_glow: false,
+        account_id,
+        time_since_set: String::from("3 weeks"),
+    }
+}
+
+#[test]
+fn level_scores_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(
+        &mut cache,
+        &LevelScoresRequest::new(1),
+        vec![level_score(1, 10, 100), level_score(2, 20, 100), level_score(3, 30, 87)],
+    );
+    common::assert_round_trip(&mut cache, &LevelScoresRequest::new(1), vec![level_score(1, 30, 100)]);
+}
+
+#[test]
+fn level_scores_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &LevelScoresRequest::new(1).leaderboard_type(LevelScoresType::Week));
+}
+
+#[test]
+fn level_score_leaderboards_are_separate() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &LevelScoresRequest::new(1), vec![level_score(1, 10, 100)]);
+    common::assert_round_trip(&mut cache, &LevelScoresRequest::friends(1, 10), vec![level_score(1, 20, 42)]);
+
+    assert_eq!(cache.lookup(&LevelScoresRequest::new(2)).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&LevelScoresRequest::friends(1, 20)).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&LevelScoresRequest::new(1).leaderboard_type(LevelScoresType::Week)).unwrap(), CacheEntry::Missing);
+}
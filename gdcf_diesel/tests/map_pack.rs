@@ -0,0 +1,64 @@
+//! Round-trips of map pack and gauntlet lists through the sqlite cache
+
+use gdcf::{
+    api::request::{GauntletsRequest, MapPacksRequest},
+    cache::{CacheEntry, Lookup},
+};
+use gdcf_model::{
+    level::{Gauntlet, LevelRating, MapPack},
+    user::Color,
+};
+
+mod common;
+
+fn map_pack(pack_id: u64) -> MapPack {
+    MapPack {
+        pack_id,
+        name: format!("Pack {}", pack_id),
+        level_ids: vec![pack_id * 10, pack_id * 10 + 1, pack_id * 10 + 2],
+        stars: 4,
+        coins: 1,
+        difficulty: LevelRating::Harder,
+        text_color: Color::Known(255, 0, 125),
+        bar_color: Color::Known(0, 200, 255),
+    }
+}
+
+fn gauntlet(gauntlet_id: u64) -> Gauntlet {
+    Gauntlet {
+        gauntlet_id,
+        level_ids: (0..5).map(|offset| gauntlet_id * 10 + offset).collect(),
+    }
+}
+
+#[test]
+fn map_packs_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &MapPacksRequest::new(), vec![map_pack(2), map_pack(1)]);
+    common::assert_round_trip(&mut cache, &MapPacksRequest::new(), vec![map_pack(3)]);
+
+    assert_eq!(cache.lookup(&MapPacksRequest::new().page(1)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn map_packs_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &MapPacksRequest::new().page(10));
+}
+
+#[test]
+fn gauntlets_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &GauntletsRequest::new(), vec![gauntlet(1), gauntlet(3), gauntlet(2)]);
+    common::assert_round_trip(&mut cache, &GauntletsRequest::new(), vec![gauntlet(1)]);
+}
+
+#[test]
+fn gauntlets_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &GauntletsRequest::new());
+}
@@ -0,0 +1,63 @@
+//! Round-trips of private messages through the sqlite cache
+
+use gdcf::{
+    api::request::{DownloadMessageRequest, MessagesRequest, SendMessageRequest},
+    cache::{CacheEntry, Invalidate, Lookup},
+};
+use gdcf_model::message::Message;
+
+mod common;
+
+fn message(message_id: u64, body: Option<&str>) -> Message {
+    Message {
+        message_id,
+        account_id: 20,
+        user_id: 1020,
+        subject: format!("Subject {}", message_id),
+        body: body.map(String::from),
+        user_name: String::from("Sender"),
+        age: String::from("5 minutes"),
+        is_read: false,
+        is_sender: false,
+    }
+}
+
+#[test]
+fn message_list_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &MessagesRequest::new(1), vec![message(2, None), message(1, None)]);
+    common::assert_round_trip(&mut cache, &MessagesRequest::new(1), vec![message(3, None)]);
+
+    assert_eq!(cache.lookup(&MessagesRequest::new(1).sent()).unwrap(), CacheEntry::Missing);
+    assert_eq!(cache.lookup(&MessagesRequest::new(1).page(1)).unwrap(), CacheEntry::Missing);
+}
+
+#[test]
+fn message_list_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &MessagesRequest::new(1).sent());
+}
+
+#[test]
+fn downloaded_message_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &DownloadMessageRequest::new(1), message(1, Some("Grüße, José 🎉")));
+}
+
+#[test]
+fn sending_invalidates_sent_messages_of_account() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &MessagesRequest::new(1).sent(), vec![message(1, None)]);
+    common::assert_round_trip(&mut cache, &MessagesRequest::new(1), vec![message(2, None)]);
+    common::assert_round_trip(&mut cache, &MessagesRequest::new(2).sent(), vec![message(3, None)]);
+
+    cache.invalidate(&SendMessageRequest::new(1, 2, "Subject", "Body")).unwrap();
+
+    assert_eq!(cache.lookup(&MessagesRequest::new(1).sent()).unwrap(), CacheEntry::Missing);
+    assert!(cache.lookup(&MessagesRequest::new(1)).unwrap() != CacheEntry::Missing);
+    assert!(cache.lookup(&MessagesRequest::new(2).sent()).unwrap() != CacheEntry::Missing);
+}
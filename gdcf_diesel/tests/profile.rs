@@ -0,0 +1,67 @@
+//! Round-trips of user profiles through the sqlite cache
+
+use gdcf::api::request::UserRequest;
+use gdcf_model::user::{Color, ModLevel, User};
+
+mod common;
+
+fn user(account_id: u64) -> User {
+    User {
+        name: format!("User {}", account_id),
+        user_id: account_id + 1000,
+        stars: 12345,
+        demons: 67,
+        creator_points: 3,
+        primary_color: Color::Known(0, 125, 255),
+        secondary_color: Color::Unknown(100),
+        secret_coins: 149,
+        account_id,
+        user_coins: 220,
+        index_18: String::new(),
+        index_19: String::new(),
+        youtube_url: Some(String::from("UCz_yk8mDSAnxJq0ar66L4sw")),
+        cube_index: 98,
+        ship_index: 21,
+        ball_index: 12,
+        ufo_index: 14,
+        wave_index: 8,
+        robot_index: 16,
+        has_glow: true,
+        index_29: String::new(),
+        global_rank: Some(1234),
+        index_31: String::new(),
+        spider_index: 6,
+        twitter_url: None,
+        twitch_url: Some(String::from("robtopgames")),
+        diamonds: 3000,
+        death_effect_index: 3,
+        mod_level: ModLevel::Elder,
+        index_50: String::new(),
+    }
+}
+
+#[test]
+fn profile_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &UserRequest::new(1), user(1));
+}
+
+#[test]
+fn profile_is_replaced() {
+    let (mut cache, _file) = common::cache();
+
+    let mut updated = user(1);
+    updated.stars += 10;
+    updated.global_rank = None;
+
+    common::assert_round_trip(&mut cache, &UserRequest::new(1), user(1));
+    common::assert_round_trip(&mut cache, &UserRequest::new(1), updated);
+}
+
+#[test]
+fn profile_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &UserRequest::new(1));
+}
@@ -0,0 +1,63 @@
+//! Round-trips of user search results through the sqlite cache
+
+use gdcf::{
+    api::request::UserSearchRequest,
+    cache::{CacheEntry, Lookup},
+};
+use gdcf_model::{
+    user::{Color, SearchedUser},
+    GameMode,
+};
+
+mod common;
+
+fn searched_user(user_id: u64, name: &str) -> SearchedUser {
+    SearchedUser {
+        name: String::from(name),
+        user_id,
+        stars: 12345,
+        demons: 67,
+        index_6: None,
+        creator_points: 3,
+        icon_index: 25,
+        primary_color: Color::Known(0, 255, 125),
+        secondary_color: Color::Known(255, 255, 255),
+        secret_coins: 149,
+        icon_type: GameMode::Wave,
+        has_glow: false,
+        account_id: user_id + 1000,
+        user_coins: 220,
+    }
+}
+
+#[test]
+fn searched_user_round_trip() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &UserSearchRequest::new(String::from("Alice")), searched_user(1, "Alice"));
+}
+
+#[test]
+fn searched_user_is_replaced() {
+    let (mut cache, _file) = common::cache();
+    let request = UserSearchRequest::new(String::from("Alice"));
+
+    common::assert_round_trip(&mut cache, &request, searched_user(1, "Alice"));
+    common::assert_round_trip(&mut cache, &request, searched_user(2, "Alice"));
+}
+
+#[test]
+fn searched_user_marked_absent() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_marked_absent(&mut cache, &UserSearchRequest::new(String::from("Nobody")));
+}
+
+#[test]
+fn searches_are_separate() {
+    let (mut cache, _file) = common::cache();
+
+    common::assert_round_trip(&mut cache, &UserSearchRequest::new(String::from("Alice")), searched_user(1, "Alice"));
+
+    assert_eq!(cache.lookup(&UserSearchRequest::new(String::from("Bob"))).unwrap(), CacheEntry::Missing);
+}
@@ -3,7 +3,7 @@ use crate::{
     GameMode,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ProfileComment {
     /// The actual content of the [`ProfileComment`] made.
     ///
@@ -32,7 +32,7 @@ pub struct ProfileComment {
     pub time_since_post: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LevelComment<User = ()> {
     /// Information about the user that made this [`LevelComment`]. Is generally a [`CommentUser`]
     /// object
@@ -99,7 +99,7 @@ pub struct LevelComment<User = ()> {
     pub special_color: Option<Color>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CommentUser {
     /// This [`CommentUser`]'s name
     ///
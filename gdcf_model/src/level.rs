@@ -2,7 +2,7 @@
 
 pub mod data;
 
-use crate::{song::MainSong, GameVersion};
+use crate::{song::MainSong, user::Color, GameVersion};
 use std::fmt::{Display, Error, Formatter};
 
 #[cfg(feature = "serde_support")]
@@ -497,6 +497,171 @@ impl<Song, User> Display for Level<Song, User> {
     }
 }
 
+/// Struct representing the current daily level or weekly demon
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide this data in a `getGJDailyLevel` response. The level itself is
+/// retrieved by downloading the level with the special ID `-1` (daily level) or `-2` (weekly demon)
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct DailyLevel {
+    /// The index of this daily level, meaning the amount of daily levels that have been featured
+    /// before it
+    ///
+    /// ## GD Internals:
+    /// Weekly demons are counted separately from daily levels. Their indices are offset by
+    /// `100000`.
+    ///
+    /// This value is the first value in the response
+    pub index: u32,
+
+    /// The amount of seconds left until this daily level is replaced by the next one, at the time
+    /// this object was retrieved
+    ///
+    /// ## GD Internals:
+    /// This value is the second value in the response
+    pub seconds_left: u32,
+
+    /// Whether this is the weekly demon instead of the daily level
+    ///
+    /// ## GD Internals:
+    /// This value is not provided by the servers, and is instead derived from the
+    /// [`DailyLevel::index`]
+    pub weekly: bool,
+}
+
+impl Display for DailyLevel {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.weekly {
+            write!(f, "WeeklyDemon({})", self.index)
+        } else {
+            write!(f, "DailyLevel({})", self.index)
+        }
+    }
+}
+
+/// Struct representing a map pack, a collection of levels that award additional stars and coins
+/// once all of them have been beaten
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide a list of map packs in a `getGJMapPacks` response
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct MapPack {
+    /// The map pack's unique ID
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `1`
+    pub pack_id: u64,
+
+    /// The name of the map pack
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `2`
+    pub name: String,
+
+    /// The IDs of the levels contained in this map pack
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `3`, as a comma separated list
+    pub level_ids: Vec<u64>,
+
+    /// The amount of stars awarded for completing all levels in this map pack
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `4`
+    pub stars: u8,
+
+    /// The amount of coins awarded for completing all levels in this map pack
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `5`
+    pub coins: u8,
+
+    /// The difficulty shown on the map pack's icon
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `6`, as the index of the difficulty face. Unlike for levels,
+    /// demon difficulties are part of the same value.
+    pub difficulty: LevelRating,
+
+    /// The color of the map pack's name
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `7`, as a comma separated RGB triple
+    pub text_color: Color,
+
+    /// The color of the map pack's progress bar
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `8`, as a comma separated RGB triple
+    pub bar_color: Color,
+}
+
+impl Display for MapPack {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "MapPack({}, {})", self.pack_id, self.name)
+    }
+}
+
+/// Struct representing a gauntlet, a themed collection of five levels
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide a list of gauntlets in a `getGJGauntlets` response
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Gauntlet {
+    /// The gauntlet's unique ID, which also determines its theme
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `1`
+    pub gauntlet_id: u64,
+
+    /// The IDs of the levels contained in this gauntlet
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `3`, as a comma separated list
+    pub level_ids: Vec<u64>,
+}
+
+impl Gauntlet {
+    /// The name of this gauntlet, if its ID is known to GDCF
+    ///
+    /// ## GD Internals:
+    /// The servers don't provide the names of gauntlets, the client derives them from the ID
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match self.gauntlet_id {
+            1 => "Fire",
+            2 => "Ice",
+            3 => "Poison",
+            4 => "Shadow",
+            5 => "Lava",
+            6 => "Bonus",
+            7 => "Chaos",
+            8 => "Demon",
+            9 => "Time",
+            10 => "Crystal",
+            11 => "Magic",
+            12 => "Spike",
+            13 => "Monster",
+            14 => "Doom",
+            15 => "Death",
+            _ => return None,
+        };
+
+        Some(name)
+    }
+}
+
+impl Display for Gauntlet {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self.name() {
+            Some(name) => write!(f, "Gauntlet({}, {})", self.gauntlet_id, name),
+            None => write!(f, "Gauntlet({})", self.gauntlet_id),
+        }
+    }
+}
+
 #[cfg(feature = "serde_support")]
 fn deserialize_main_song<'de, D>(deserializer: D) -> Result<Option<&'static MainSong>, D::Error>
 where
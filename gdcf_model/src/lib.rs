@@ -3,6 +3,7 @@
 
 pub mod comment;
 pub mod level;
+pub mod message;
 pub mod song;
 pub mod user;
 
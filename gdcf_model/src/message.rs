@@ -0,0 +1,81 @@
+//! Module containing the model for private messages sent between accounts
+
+use std::fmt::{Display, Error, Formatter};
+
+#[cfg(feature = "serde_support")]
+use serde_derive::{Deserialize, Serialize};
+
+/// Struct representing a private message sent from one account to another
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide lists of messages in response to a `getGJMessages` request,
+/// and single messages in response to a `downloadGJMessage` request. Only the latter include the
+/// message's body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Message {
+    /// The unique ID of this message
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `1`
+    pub message_id: u64,
+
+    /// The account ID of the account that sent this message. For messages taken from the sent
+    /// messages of an account, this is the account the message was sent to instead.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `2`
+    pub account_id: u64,
+
+    /// The user ID of the player that sent this message. The same caveat as for
+    /// [`Message::account_id`] applies.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `3`
+    pub user_id: u64,
+
+    /// The subject of this message
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `4` and is base64 encoded
+    pub subject: String,
+
+    /// The body of this message, or [`None`] if this message is part of a message list
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `5`. It is XOR-encrypted with key `14251` and then base64
+    /// encoded
+    pub body: Option<String>,
+
+    /// The name of the player that sent this message. The same caveat as for
+    /// [`Message::account_id`] applies.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `6`
+    pub user_name: String,
+
+    /// Robtop's way of keeping track of when this message was sent
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `7`
+    pub age: String,
+
+    /// Whether the recipient has read this message
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `8`
+    pub is_read: bool,
+
+    /// Whether this message was sent by the account that retrieved it, meaning it was taken from
+    /// the sent messages instead of the inbox
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `9`
+    pub is_sender: bool,
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "Message {} from {}: {}", self.message_id, self.user_name, self.subject)
+    }
+}
@@ -386,6 +386,379 @@ impl Display for SearchedUser {
     }
 }
 
+/// Struct representing a single entry on one of the leaderboards
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide the entries of a leaderboard in a `getGJScores` response.
+/// Index `7` duplicates the account ID at index `16`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardEntry {
+    /// This [`LeaderboardEntry`]'s rank on the leaderboard it was retrieved from
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `6`
+    pub rank: u32,
+
+    /// The name of the user this [`LeaderboardEntry`] belongs to
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `1`
+    pub name: String,
+
+    /// The unique user ID of the user this [`LeaderboardEntry`] belongs to
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `2`
+    pub user_id: u64,
+
+    /// The user's stars
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `3`
+    pub stars: u32,
+
+    /// The user's beaten demons
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `4`
+    pub demons: u16,
+
+    /// The user's creator points
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `8`
+    pub creator_points: u16,
+
+    /// The index of the icon being displayed.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `9`
+    pub icon_index: u16,
+
+    /// The user's primary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `10`
+    pub primary_color: Color,
+
+    /// The user's secondary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `11`
+    pub secondary_color: Color,
+
+    /// The amount of secret coins the user has collected.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `13`
+    pub secret_coins: u8,
+
+    /// The type of icon being displayed
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `14`
+    pub icon_type: GameMode,
+
+    /// Values indicating whether the user has glow activated or not.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `15`
+    pub has_glow: bool,
+
+    /// The unique account ID of the user this [`LeaderboardEntry`] belongs to
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `16`
+    pub account_id: u64,
+
+    /// The amount of user coins the user has collected.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `17`
+    pub user_coins: u16,
+
+    /// The user's diamonds
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `46`
+    pub diamonds: u32,
+}
+
+impl Display for LeaderboardEntry {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "LeaderboardEntry(#{}, {}, {})", self.rank, self.user_id, self.name)
+    }
+}
+
+/// Struct representing a player's score on the leaderboard of a single level
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide the scores on a level's leaderboard in a `getGJLevelScores211`
+/// response. The attempts a player needed are only ever sent to the servers, they aren't part of
+/// the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelScore {
+    /// This [`LevelScore`]'s rank on the leaderboard it was retrieved from
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `6`
+    pub rank: u32,
+
+    /// The name of the player who set this [`LevelScore`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `1`
+    pub name: String,
+
+    /// The unique user ID of the player who set this [`LevelScore`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `2`
+    pub user_id: u64,
+
+    /// The percentage of the level the player has completed
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `3`
+    pub progress: u8,
+
+    /// The index of the icon being displayed.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `9`
+    pub icon_index: u16,
+
+    /// The player's primary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `10`
+    pub primary_color: Color,
+
+    /// The player's secondary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `11`
+    pub secondary_color: Color,
+
+    /// The amount of the level's coins the player has collected
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `13`
+    pub coins: u8,
+
+    /// The type of icon being displayed
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `14`
+    pub icon_type: GameMode,
+
+    /// Values indicating whether the player has glow activated or not.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `15`
+    pub has_glow: bool,
+
+    /// The unique account ID of the player who set this [`LevelScore`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `16`
+    pub account_id: u64,
+
+    /// A string representing the time that has passed since this [`LevelScore`] was set
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `42`
+    pub time_since_set: String,
+}
+
+impl Display for LevelScore {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "LevelScore(#{}, {}, {}%)", self.rank, self.name, self.progress)
+    }
+}
+
+/// Struct representing a user on one of the user lists of an account, meaning its friend list or
+/// the list of users it blocked
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide these lists in a `getGJUserList20` response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedUser {
+    /// The name of this [`ListedUser`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `1`
+    pub name: String,
+
+    /// The unique user ID of this [`ListedUser`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `2`
+    pub user_id: u64,
+
+    /// The index of the icon being displayed.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `9`
+    pub icon_index: u16,
+
+    /// The user's primary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `10`
+    pub primary_color: Color,
+
+    /// The user's secondary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `11`
+    pub secondary_color: Color,
+
+    /// The type of icon being displayed
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `14`
+    pub icon_type: GameMode,
+
+    /// Values indicating whether the user has glow activated or not.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `15`
+    pub has_glow: bool,
+
+    /// The unique account ID of this [`ListedUser`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `16`
+    pub account_id: u64,
+
+    // TODO: figure this value out
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `18`
+    pub index_18: Option<String>,
+
+    /// Whether this user was added to the list since the list was last retrieved
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `41`
+    pub is_new: bool,
+}
+
+impl Display for ListedUser {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "ListedUser({}, {})", self.account_id, self.name)
+    }
+}
+
+/// Struct representing a friend request sent from one account to another
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide friend requests in a `getGJFriendRequests20` response. The
+/// user data contained in a friend request is always the one of the other party, meaning the
+/// sender for incoming and the recipient for outgoing requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FriendRequest {
+    /// The name of the other party of this [`FriendRequest`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `1`
+    pub name: String,
+
+    /// The unique user ID of the other party of this [`FriendRequest`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `2`
+    pub user_id: u64,
+
+    /// The index of the icon being displayed.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `9`
+    pub icon_index: u16,
+
+    /// The user's primary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `10`
+    pub primary_color: Color,
+
+    /// The user's secondary color
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `11`
+    pub secondary_color: Color,
+
+    /// The type of icon being displayed
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `14`
+    pub icon_type: GameMode,
+
+    /// Values indicating whether the user has glow activated or not.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `15`
+    pub has_glow: bool,
+
+    /// The unique account ID of the other party of this [`FriendRequest`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `16`
+    pub account_id: u64,
+
+    /// The unique ID of this [`FriendRequest`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `32`
+    pub request_id: u64,
+
+    /// The message sent along with this [`FriendRequest`], if any
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `35` and is base64 encoded
+    pub message: Option<String>,
+
+    /// A string representing the time that has passed since this [`FriendRequest`] was sent
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `37`
+    pub time_since_sent: String,
+
+    /// Whether this [`FriendRequest`] hasn't been looked at yet
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `41`
+    pub is_new: bool,
+}
+
+impl Display for FriendRequest {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "FriendRequest({}, {})", self.request_id, self.name)
+    }
+}
+
+/// Struct containing the IDs associated with a Geometry Dash account
+///
+/// ## GD Internals:
+/// The Geometry Dash servers provide these values in response to a successful `loginGJAccount`
+/// request, in the form `accountID,userID`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Account {
+    /// The account's unique account ID
+    pub account_id: u64,
+
+    /// The unique user ID of the player the account belongs to
+    pub user_id: u64,
+}
+
+impl Display for Account {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "Account({}, {})", self.account_id, self.user_id)
+    }
+}
+
 impl Into<u8> for ModLevel {
     fn into(self) -> u8 {
         match self {
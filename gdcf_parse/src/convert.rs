@@ -353,6 +353,49 @@ impl RobtopInto<DemonRating, String> for DemonRating {
     }
 }
 
+/// Converter for the difficulty faces of map packs, which combine level and demon ratings into a
+/// single value
+pub struct PackDifficulty;
+
+impl RobtopFrom<LevelRating, &str> for PackDifficulty {
+    fn robtop_from(t: &str) -> Result<LevelRating, String> {
+        Ok(match t {
+            "0" => LevelRating::Auto,
+            "1" => LevelRating::Easy,
+            "2" => LevelRating::Normal,
+            "3" => LevelRating::Hard,
+            "4" => LevelRating::Harder,
+            "5" => LevelRating::Insane,
+            "6" => LevelRating::Demon(DemonRating::Hard),
+            "7" => LevelRating::Demon(DemonRating::Easy),
+            "8" => LevelRating::Demon(DemonRating::Medium),
+            "9" => LevelRating::Demon(DemonRating::Insane),
+            "10" => LevelRating::Demon(DemonRating::Extreme),
+            t => LevelRating::Unknown(i32::robtop_from(t)?),
+        })
+    }
+}
+
+impl RobtopInto<PackDifficulty, String> for LevelRating {
+    fn robtop_into(self) -> String {
+        match self {
+            LevelRating::Auto => "0".to_string(),
+            LevelRating::Easy => "1".to_string(),
+            LevelRating::Normal => "2".to_string(),
+            LevelRating::Hard => "3".to_string(),
+            LevelRating::Harder => "4".to_string(),
+            LevelRating::Insane => "5".to_string(),
+            LevelRating::Demon(DemonRating::Hard) => "6".to_string(),
+            LevelRating::Demon(DemonRating::Easy) => "7".to_string(),
+            LevelRating::Demon(DemonRating::Medium) => "8".to_string(),
+            LevelRating::Demon(DemonRating::Insane) => "9".to_string(),
+            LevelRating::Demon(DemonRating::Extreme) => "10".to_string(),
+            LevelRating::Demon(DemonRating::Unknown(value)) | LevelRating::Unknown(value) => value.to_string(),
+            LevelRating::NotAvailable => "-1".to_string(),
+        }
+    }
+}
+
 impl RobtopFrom<Password, &str> for Password {
     /// Attempts to parse the given `str` into a [`Password`]
     ///
@@ -540,6 +583,24 @@ impl RobtopFrom<Color, &str> for RGBColor {
     }
 }
 
+/// Converter for comma separated lists of IDs, like the levels contained in a map pack
+pub struct IdListConverter;
+
+impl RobtopFrom<Vec<u64>, &str> for IdListConverter {
+    fn robtop_from(s: &str) -> Result<Vec<u64>, String> {
+        s.split(',')
+            .filter(|id| !id.is_empty())
+            .map(|id| id.parse().map_err(|e: ParseIntError| e.to_string()))
+            .collect()
+    }
+}
+
+impl RobtopInto<IdListConverter, String> for Vec<u64> {
+    fn robtop_into(self) -> String {
+        self.iter().map(u64::to_string).collect::<Vec<_>>().join(",")
+    }
+}
+
 pub struct Base64BytesConverter;
 
 impl RobtopFrom<Vec<u8>, &str> for Base64BytesConverter {
@@ -583,6 +644,25 @@ impl RobtopInto<Base64Converter, String> for String {
     }
 }
 
+/// Converter for the bodies of private messages, which are XOR-encrypted with key `14251` before
+/// being base64 encoded
+pub struct MessageBodyConverter;
+
+impl RobtopFromInfallible<Option<String>, &str> for MessageBodyConverter {
+    fn robtop_from_infallible(s: &str) -> Option<String> {
+        util::decode_message_body(s).ok()
+    }
+}
+
+impl RobtopInto<MessageBodyConverter, String> for Option<String> {
+    fn robtop_into(self) -> String {
+        match self {
+            Some(ref body) => util::encode_message_body(body),
+            None => String::new(),
+        }
+    }
+}
+
 pub struct UrlConverter;
 
 impl RobtopFrom<String, &str> for UrlConverter {
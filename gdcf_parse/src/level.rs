@@ -1,10 +1,10 @@
 use crate::{
-    convert::{Base64BytesConverter, Base64Converter, RobtopFrom, RobtopInto},
+    convert::{Base64BytesConverter, Base64Converter, IdListConverter, PackDifficulty, RGBColor, RobtopFrom, RobtopInto},
     error::ValueError,
     Parse,
 };
 use gdcf_model::{
-    level::{DemonRating, Level, LevelRating, PartialLevel},
+    level::{DemonRating, Gauntlet, Level, LevelRating, MapPack, PartialLevel},
     song::{MainSong, MAIN_SONGS, UNKNOWN},
 };
 
@@ -70,7 +70,7 @@ fn extract_main_song_id(main_song: Option<&'static MainSong>) -> String {
 }
 
 fn extract_rating(rating: LevelRating) -> String {
-    rating.robtop_into()
+    RobtopInto::<LevelRating, String>::robtop_into(rating)
 }
 
 fn extract_is_demon(rating: LevelRating) -> String {
@@ -97,4 +97,23 @@ parser! {
         time_since_update(index = 29),
         index_36(index = 36, default),
     }
-}
\ No newline at end of file
+}
+parser! {
+    MapPack => {
+        pack_id(index = 1),
+        name(index = 2),
+        level_ids(index = 3, parse = IdListConverter),
+        stars(index = 4),
+        coins(index = 5),
+        difficulty(index = 6, parse = PackDifficulty),
+        text_color(index = 7, parse = RGBColor),
+        bar_color(index = 8, parse = RGBColor),
+    }
+}
+
+parser! {
+    Gauntlet => {
+        gauntlet_id(index = 1),
+        level_ids(index = 3, parse = IdListConverter),
+    }
+}
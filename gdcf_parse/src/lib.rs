@@ -18,6 +18,7 @@ pub mod comment;
 pub mod convert;
 pub mod error;
 pub mod level;
+pub mod message;
 pub mod song;
 pub mod user;
 
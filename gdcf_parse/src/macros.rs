@@ -256,7 +256,9 @@ macro_rules! parser {
                     let $field_name = __unwrap!($field_name($($tokens)*));
                 )*
 
+                // Ignored helper fields only matter for unparsing and are never read here
                 $(
+                    #[allow(unused_variables)]
                     let $helper_field = __unwrap!($helper_field($($tokens2)*));
                 )*
 
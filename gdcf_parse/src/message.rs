@@ -0,0 +1,19 @@
+use crate::{
+    convert::{Base64Converter, MessageBodyConverter},
+    Parse, ValueError,
+};
+use gdcf_model::message::Message;
+
+parser! {
+    Message => {
+        message_id(index = 1),
+        account_id(index = 2),
+        user_id(index = 3),
+        subject(index = 4, parse = Base64Converter, default),
+        body(index = 5, parse_infallible = MessageBodyConverter, default),
+        user_name(index = 6),
+        age(index = 7),
+        is_read(index = 8, default),
+        is_sender(index = 9, default),
+    }
+}
@@ -41,6 +41,13 @@ pub fn b64_decode_string(encoded: &str) -> Result<String, DecodeError> {
     base64::decode_config(encoded, URL_SAFE).map(|bytes| String::from_utf8_lossy(&bytes[..]).to_string())
 }
 
+/// Performs URL-safe base64 encoding on the given [`str`]
+///
+/// This is the inverse to [`b64_decode_string`]
+pub fn b64_encode_string(plain: &str) -> String {
+    base64::encode_config(plain, URL_SAFE)
+}
+
 /// Performs robtop's XOR en-/decryption routine on `encrypted` using `key`
 ///
 /// Note that although both `encrypted` and `key` are `str`s, the decryption
@@ -55,6 +62,29 @@ pub fn xor_decrypt(encrypted: &str, key: &str) -> String {
         .collect()
 }
 
+/// Performs robtop's XOR en-/decryption routine on the raw bytes `data` using `key`
+///
+/// Unlike [`xor_decrypt`], this works for arbitrary data, not just ASCII strings
+pub fn xor_bytes(data: &[u8], key: &str) -> Vec<u8> {
+    data.iter().zip(key.bytes().cycle()).map(|(byte, key_byte)| byte ^ key_byte).collect()
+}
+
+/// Encodes the body of a private message the way the servers expect it, by XOR-encrypting its
+/// UTF-8 bytes with key `14251` and base64-encoding the result
+pub fn encode_message_body(body: &str) -> String {
+    base64::encode_config(&xor_bytes(body.as_bytes(), "14251"), URL_SAFE)
+}
+
+/// Decodes the body of a private message as sent by the servers
+///
+/// This is the inverse to [`encode_message_body`]
+///
+/// # Errors
+/// If the given string isn't valid URL-safe base64, a [`DecodeError`] is returned
+pub fn decode_message_body(encoded: &str) -> Result<String, DecodeError> {
+    base64::decode_config(encoded, URL_SAFE).map(|bytes| String::from_utf8_lossy(&xor_bytes(&bytes, "14251")).into_owned())
+}
+
 pub fn parse<'a, T>(idx: &'a str, value: &'a str) -> Result<Option<T>, ValueError<'a>>
 where
     T: RobtopFrom<T, &'a str>,
@@ -82,3 +112,22 @@ where
 {
     value.can_omit()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_message_body, encode_message_body};
+
+    #[test]
+    fn message_body_known_value() {
+        // 'a' ^ '1' == 'P'
+        assert_eq!(encode_message_body("a"), "UA==");
+        assert_eq!(decode_message_body("UA==").unwrap(), "a");
+    }
+
+    #[test]
+    fn message_body_round_trips_non_ascii() {
+        let body = "Grüße, José 🎉";
+
+        assert_eq!(decode_message_body(&encode_message_body(body)).unwrap(), body);
+    }
+}
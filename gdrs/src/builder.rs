@@ -0,0 +1,158 @@
+use crate::{
+    handle::Handler,
+    limit::{Limiter, Limits},
+    BoomlingsClient, RetryPolicy,
+};
+use gdcf::api::request::Credentials;
+use futures::{future::Executor, Future};
+use hyper::client::Builder;
+use log::info;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// The base url of the GDPS the [`BoomlingsClient`] talks to unless configured otherwise
+pub const DEFAULT_BASE_URL: &str = "http://absolllute.com/gdps/gdapi/";
+
+/// The base url of RobTop's official Geometry Dash servers
+pub const BOOMLINGS_BASE_URL: &str = "http://www.boomlings.com/database/";
+
+/// The set of urls a [`BoomlingsClient`] sends its requests to
+#[derive(Debug, Clone)]
+pub(crate) struct Endpoints {
+    base_url: String,
+
+    /// Maps endpoint names to urls that are used instead of the one derived from the base url
+    overrides: HashMap<&'static str, String>,
+
+    /// Maps endpoint names to the version suffix that should be used instead of the default one
+    versions: HashMap<&'static str, String>,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            overrides: HashMap::new(),
+            versions: HashMap::new(),
+        }
+    }
+}
+
+impl Endpoints {
+    pub(crate) fn url<R: Handler>(&self) -> String {
+        match self.overrides.get(R::endpoint()) {
+            Some(url) => url.clone(),
+            None => {
+                let version = match self.versions.get(R::endpoint()) {
+                    Some(version) => version,
+                    None => R::version(),
+                };
+
+                format!("{}{}{}.php", self.base_url, R::endpoint(), version)
+            },
+        }
+    }
+}
+
+/// Builder for [`BoomlingsClient`]s
+///
+/// Allows to point the client at a different server than the default one, or to replace single
+/// endpoints of that server
+#[derive(Debug, Default)]
+pub struct BoomlingsClientBuilder {
+    client: Builder,
+    endpoints: Endpoints,
+    retry_policy: RetryPolicy,
+    limits: Limits,
+    credentials: Option<Credentials>,
+}
+
+impl BoomlingsClientBuilder {
+    /// Sets the url all endpoint names are appended to. Needs to end with a `/`.
+    ///
+    /// Use [`BOOMLINGS_BASE_URL`] to talk to the official servers.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.endpoints.base_url = base_url.into();
+        self
+    }
+
+    /// Makes the client send requests of type `R` to the given url, instead of the one derived from
+    /// the base url
+    pub fn endpoint<R: Handler>(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.overrides.insert(R::endpoint(), url.into());
+        self
+    }
+
+    /// Sets the version suffix of the endpoint processing requests of type `R` (e.g. `"22"` to use
+    /// `downloadGJLevel22.php` instead of `downloadGJLevel19.php`)
+    pub fn version<R: Handler>(mut self, version: impl Into<String>) -> Self {
+        self.endpoints.versions.insert(R::endpoint(), version.into());
+        self
+    }
+
+    /// Sets the policy the client uses to retry failed requests
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Allows the client to make at most `requests` requests per `per`, across all endpoints.
+    ///
+    /// The limit is enforced using a token bucket, meaning that up to `requests` requests can be made
+    /// in a burst. Requests exceeding the limit are delayed until they can be made, they don't fail.
+    pub fn rate_limit(mut self, requests: u32, per: Duration) -> Self {
+        self.limits.global.rate = Some((requests, per));
+        self
+    }
+
+    /// Allows at most `max` requests to wait for a response at the same time, across all endpoints.
+    ///
+    /// Further requests are queued until some request completes.
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.limits.global.max_in_flight = Some(max);
+        self
+    }
+
+    /// Like [`BoomlingsClientBuilder::rate_limit`], but only for requests of type `R`. The global
+    /// limit still applies to these requests.
+    pub fn endpoint_rate_limit<R: Handler>(mut self, requests: u32, per: Duration) -> Self {
+        self.limits.endpoints.entry(R::endpoint()).or_default().rate = Some((requests, per));
+        self
+    }
+
+    /// Like [`BoomlingsClientBuilder::max_in_flight`], but only for requests of type `R`. The global
+    /// limit still applies to these requests.
+    pub fn endpoint_max_in_flight<R: Handler>(mut self, max: usize) -> Self {
+        self.limits.endpoints.entry(R::endpoint()).or_default().max_in_flight = Some(max);
+        self
+    }
+
+    /// Makes the client perform requests on behalf of the account with the given credentials.
+    ///
+    /// The credentials are only sent along with requests that require them, like the ones for level
+    /// leaderboards, see [`Handler::authenticated`].
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Sets the executor the underlying hyper client spawns its background tasks on
+    pub fn executor<E>(mut self, exec: E) -> Self
+    where
+        E: Executor<Box<dyn Future<Item = (), Error = ()> + Send>> + Send + Sync + 'static,
+    {
+        self.client.executor(exec);
+        self
+    }
+
+    pub fn build(self) -> BoomlingsClient {
+        info!("Creating new BoomlingsApiClient with base url {}", self.endpoints.base_url);
+
+        BoomlingsClient {
+            client: self.client.build_http(),
+            endpoints: Arc::new(self.endpoints),
+            retry_policy: self.retry_policy,
+            limiter: Arc::new(Limiter::new(self.limits)),
+            credentials: self.credentials.map(Arc::new),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use failure_derive::Fail;
 use gdcf::error::ApiError as TApiError;
 use gdcf_parse::error::ValueError;
 use tokio_retry::Error as RetryError;
+use tokio_timer::Error as TimerError;
 
 #[derive(Fail, Debug)]
 pub enum ApiError {
@@ -33,9 +34,35 @@ pub enum ApiError {
     #[fail(display = "Required data at index {} missing", _0)]
     MissingData(String),
 
+    /// The servers refused to process the request and responded with the given error code
+    ///
+    /// This happens for example when logging in with invalid credentials (`-1`) or into a disabled
+    /// account (`-12`)
+    #[fail(display = "The request was rejected with error code {}", _0)]
+    Rejected(i32),
+
+    /// The account the request was made on behalf of is banned from posting comments for the given
+    /// amount of seconds
+    #[fail(display = "The account is banned from commenting for {} seconds: {}", seconds, reason)]
+    CommentBan { seconds: u64, reason: String },
+
+    /// The request is specific to the account with ID `expected`, but the client is authenticated
+    /// as the account with ID `actual`. The request was never sent.
+    #[fail(display = "The request is for account {}, but the client is authenticated as {}", expected, actual)]
+    WrongAccount { expected: u64, actual: u64 },
+
     /// An error caused by the underlying api client implementation occured
     #[fail(display = "An API client specific error occurate: {}", _0)]
     Custom(#[cause] hyper::Error),
+
+    /// The request didn't complete within the time allowed by the client's
+    /// [`RetryPolicy`](crate::RetryPolicy)
+    #[fail(display = "The request timed out")]
+    Timeout,
+
+    /// The timer used for retry delays and timeouts failed
+    #[fail(display = "Timer error: {}", _0)]
+    Timer(#[cause] TimerError),
 }
 
 impl<'a> From<ValueError<'a>> for ApiError {
@@ -65,7 +92,13 @@ impl From<RetryError<ApiError>> for ApiError {
     fn from(err: RetryError<ApiError>) -> Self {
         match err {
             RetryError::OperationError(e) => e,
-            _ => unimplemented!(),
+            RetryError::TimerError(e) => ApiError::Timer(e),
         }
     }
 }
+
+impl From<TimerError> for ApiError {
+    fn from(err: TimerError) -> Self {
+        ApiError::Timer(err)
+    }
+}
@@ -1,34 +1,225 @@
-use crate::{error::ApiError, Req};
+pub use crate::ser::Checksum;
+use crate::{error::ApiError, ser, Req};
 use gdcf::{
     api::{
         client::Response,
         request::{
-            comment::{LevelCommentsRequest, ProfileCommentsRequest},
-            user::UserSearchRequest,
-            LevelRequest, LevelsRequest, Request as GdcfRequest, UserRequest,
+            account::{Credentials, LoginRequest},
+            comment::{LevelCommentsRequest, ProfileCommentsRequest, UploadLevelCommentRequest, UploadProfileCommentRequest},
+            message::{DownloadMessageRequest, MessagesRequest, SendMessageRequest},
+            rate::{LikeItemRequest, RateDemonRequest, RateStarsRequest},
+            song::SongInfoRequest,
+            user::{
+                BlockedUsersRequest, FriendListRequest, FriendRequestsRequest, LeaderboardRequest, LeaderboardType, LevelScoresRequest,
+                LevelScoresType, UserSearchRequest,
+            },
+            DailyLevelRequest, GauntletsRequest, LevelRequest, LevelRequestType, LevelsRequest, MapPacksRequest, Pagination,
+            Request as GdcfRequest, SpecialLevelRequest, UploadLevelRequest, UserRequest,
         },
     },
     Secondary,
 };
 use gdcf_model::{
     comment::{CommentUser, LevelComment, ProfileComment},
-    level::{Level, PartialLevel},
+    level::{DailyLevel, Gauntlet, Level, MapPack, PartialLevel},
+    message::Message,
     song::NewgroundsSong,
-    user::{Creator, SearchedUser, User},
+    user::{Account, Creator, FriendRequest, LeaderboardEntry, LevelScore, ListedUser, SearchedUser, User},
 };
-use gdcf_parse::Parse;
+use gdcf_parse::{util::b64_encode_string, Parse};
 use log::{info, trace, warn};
 
 pub trait Handler: GdcfRequest {
+    /// The name of the endpoint processing this request, without version suffix and file extension
+    /// (e.g. `downloadGJLevel` for `downloadGJLevel22.php`)
     fn endpoint() -> &'static str;
+
+    /// The version suffix of the endpoint that's used unless the [`BoomlingsClient`] has been
+    /// configured to use a different one
+    ///
+    /// [`BoomlingsClient`]: crate::BoomlingsClient
+    fn version() -> &'static str;
+
     fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError>;
 
     fn to_req(&self) -> Req;
+
+    /// Whether making this request more than once has the same effect as making it once
+    ///
+    /// Requests that write data to the servers may have been processed even if they fail (e.g.
+    /// with a timeout), so a [`BoomlingsClient`] only ever attempts non-idempotent requests once,
+    /// no matter its [`RetryPolicy`].
+    ///
+    /// [`BoomlingsClient`]: crate::BoomlingsClient
+    /// [`RetryPolicy`]: crate::RetryPolicy
+    fn idempotent() -> bool {
+        true
+    }
+
+    /// Whether this request has to be made on behalf of an account
+    ///
+    /// The credentials the [`BoomlingsClient`] has been given are only sent along with requests
+    /// for which this returns `true`. All other requests are made anonymously.
+    ///
+    /// [`BoomlingsClient`]: crate::BoomlingsClient
+    fn authenticated(&self) -> bool {
+        false
+    }
+
+    /// The ID of the account whose data this request retrieves, if it depends on the account
+    /// making it
+    ///
+    /// Such requests are rejected with [`ApiError::WrongAccount`] without being sent if the
+    /// [`BoomlingsClient`] is authenticated as a different account, as their result would otherwise
+    /// be cached under the wrong account.
+    ///
+    /// [`BoomlingsClient`]: crate::BoomlingsClient
+    fn account_id(&self) -> Option<u64> {
+        None
+    }
+
+    /// The `chk` checksum the servers require to accept this request, if any
+    ///
+    /// These checksums generally include the user name of the account the request is made on behalf
+    /// of, so they can only be computed if the client has been given credentials
+    fn chk(&self, _credentials: Option<&Credentials>) -> Option<Checksum> {
+        None
+    }
+}
+
+/// Parses the `total:offset:page_size` section the servers append to paginated responses
+fn parse_pagination(section: &str) -> Result<Pagination, ApiError> {
+    let mut parts = section.split(':').map(str::parse);
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(total)), Some(Ok(offset)), Some(Ok(page_size))) =>
+            Ok(Pagination {
+                total,
+                offset,
+                page_size,
+            }),
+        _ => Err(ApiError::UnexpectedFormat),
+    }
+}
+
+/// Wraps the given page into a response, attaching the pagination information from the given
+/// section if the servers provided it
+fn paginated<T>(page: T, section: Option<&str>) -> Result<Response<T>, ApiError> {
+    match section {
+        Some(section) => Ok(Response::More(page, vec![Secondary::Pagination(parse_pagination(section)?)])),
+        None => Ok(Response::Exact(page)),
+    }
+}
+
+/// Parses the response to a comment upload, which is the ID of the new comment if it was posted
+fn parse_comment_id(response_body: &str) -> Result<Response<u64>, ApiError> {
+    // Comment bans are reported as `temp_<seconds>_<reason>`
+    if response_body.starts_with("temp_") {
+        let mut parts = response_body.splitn(3, '_').skip(1);
+
+        return match (parts.next().map(str::parse), parts.next()) {
+            (Some(Ok(seconds)), Some(reason)) => {
+                warn!("Account is banned from commenting for {} seconds: {}", seconds, reason);
+
+                Err(ApiError::CommentBan {
+                    seconds,
+                    reason: reason.to_string(),
+                })
+            },
+            _ => Err(ApiError::UnexpectedFormat),
+        }
+    }
+
+    parse_upload_id(response_body)
+}
+
+/// Parses the response to an upload, which is the ID of the uploaded object if the upload succeeded
+/// and a negative error code otherwise
+fn parse_upload_id(response_body: &str) -> Result<Response<u64>, ApiError> {
+    match response_body.parse::<i64>() {
+        Ok(id) if id > 0 => Ok(Response::Exact(id as u64)),
+        Ok(code) => {
+            warn!("Upload failed with error code {}", code);
+
+            Err(ApiError::Rejected(code as i32))
+        },
+        Err(_) => Err(ApiError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a request that doesn't return any data, which is some positive integer
+/// if the request succeeded and a negative error code otherwise
+fn parse_success(response_body: &str) -> Result<Response<()>, ApiError> {
+    match response_body.parse::<i64>() {
+        Ok(value) if value > 0 => Ok(Response::Exact(())),
+        Ok(code) => {
+            warn!("Request failed with error code {}", code);
+
+            Err(ApiError::Rejected(code as i32))
+        },
+        Err(_) => Err(ApiError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a request for one of the user lists of an account, which share the same
+/// endpoint
+fn user_list(response_body: &str) -> Result<Vec<ListedUser>, ApiError> {
+    check_resp!(response_body);
+
+    // Empty lists are reported as `-2`
+    if response_body == "-2" {
+        return Err(ApiError::NoData)
+    }
+
+    let mut users = Vec::new();
+
+    for fragment in response_body.split('|') {
+        users.push(ListedUser::parse_str(fragment, ':')?)
+    }
+
+    info!("We got a total of {} users!", users.len());
+
+    Ok(users)
+}
+
+impl Handler for LoginRequest {
+    fn endpoint() -> &'static str {
+        "accounts/loginGJAccount"
+    }
+
+    fn version() -> &'static str {
+        ""
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        if let Ok(code) = response_body.parse::<i32>() {
+            if code < 0 {
+                warn!("Login failed with error code {}", code);
+
+                return Err(ApiError::Rejected(code))
+            }
+        }
+
+        let mut ids = response_body.split(',').map(str::parse);
+
+        match (ids.next(), ids.next()) {
+            (Some(Ok(account_id)), Some(Ok(user_id))) => Ok(Response::Exact(Account { account_id, user_id })),
+            _ => Err(ApiError::UnexpectedFormat),
+        }
+    }
+
+    fn to_req(&self) -> Req {
+        Req::LoginRequest(self)
+    }
 }
 
 impl Handler for LevelRequest {
     fn endpoint() -> &'static str {
-        endpoint!("downloadGJLevel19")
+        "downloadGJLevel"
+    }
+
+    fn version() -> &'static str {
+        "19"
     }
 
     fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
@@ -47,9 +238,62 @@ impl Handler for LevelRequest {
     }
 }
 
+impl Handler for SpecialLevelRequest {
+    fn endpoint() -> &'static str {
+        LevelRequest::endpoint()
+    }
+
+    fn version() -> &'static str {
+        LevelRequest::version()
+    }
+
+    // The response is the same as the one for the level's actual ID
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        LevelRequest::handle(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::SpecialLevelRequest(self)
+    }
+}
+
+impl Handler for DailyLevelRequest {
+    fn endpoint() -> &'static str {
+        "getGJDailyLevel"
+    }
+
+    fn version() -> &'static str {
+        ""
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        let mut parts = response_body.split('|').map(str::parse);
+
+        match (parts.next(), parts.next()) {
+            (Some(Ok(index)), Some(Ok(seconds_left))) =>
+                Ok(Response::Exact(DailyLevel {
+                    index,
+                    seconds_left,
+                    weekly: index >= 100_000,
+                })),
+            _ => Err(ApiError::UnexpectedFormat),
+        }
+    }
+
+    fn to_req(&self) -> Req {
+        Req::DailyLevelRequest(self)
+    }
+}
+
 impl Handler for LevelsRequest {
     fn endpoint() -> &'static str {
-        endpoint!("getGJLevels19")
+        "getGJLevels"
+    }
+
+    fn version() -> &'static str {
+        "19"
     }
 
     fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
@@ -93,6 +337,10 @@ impl Handler for LevelsRequest {
 
         info!("Found {} songs", other.len() - creator_count);
 
+        if let Some(section) = sections.next() {
+            other.push(Secondary::Pagination(parse_pagination(section)?))
+        }
+
         for level in &levels {
             if other
                 .iter()
@@ -135,11 +383,115 @@ impl Handler for LevelsRequest {
     fn to_req(&self) -> Req {
         Req::LevelsRequest(self)
     }
+
+    // Only the levels of an account's friends depend on who is asking
+    fn authenticated(&self) -> bool {
+        self.request_type == LevelRequestType::Friends
+    }
+}
+
+impl Handler for MapPacksRequest {
+    fn endpoint() -> &'static str {
+        "getGJMapPacks"
+    }
+
+    fn version() -> &'static str {
+        "21"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        let mut sections = response_body.split('#');
+
+        match sections.next() {
+            Some(section) => {
+                let mut packs = Vec::new();
+
+                for fragment in section.split('|') {
+                    packs.push(MapPack::parse_str(fragment, ':')?)
+                }
+
+                info!("Found {} map packs", packs.len());
+
+                paginated(packs, sections.next())
+            },
+            None => Err(ApiError::UnexpectedFormat),
+        }
+    }
+
+    fn to_req(&self) -> Req {
+        Req::MapPacksRequest(self)
+    }
+}
+
+impl Handler for GauntletsRequest {
+    fn endpoint() -> &'static str {
+        "getGJGauntlets"
+    }
+
+    fn version() -> &'static str {
+        "21"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        let mut sections = response_body.split('#');
+
+        match sections.next() {
+            Some(section) => {
+                let mut gauntlets = Vec::new();
+
+                for fragment in section.split('|') {
+                    gauntlets.push(Gauntlet::parse_str(fragment, ':')?)
+                }
+
+                info!("Found {} gauntlets", gauntlets.len());
+
+                Ok(Response::Exact(gauntlets))
+            },
+            None => Err(ApiError::UnexpectedFormat),
+        }
+    }
+
+    fn to_req(&self) -> Req {
+        Req::GauntletsRequest(self)
+    }
+}
+
+impl Handler for SongInfoRequest {
+    fn endpoint() -> &'static str {
+        "getGJSongInfo"
+    }
+
+    fn version() -> &'static str {
+        ""
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        // Songs that are banned from being used in Geometry Dash are reported as `-2`
+        if response_body == "-2" {
+            return Err(ApiError::NoData)
+        }
+
+        Ok(Response::Exact(NewgroundsSong::parse_str2(response_body, "~|~")?))
+    }
+
+    fn to_req(&self) -> Req {
+        Req::SongInfoRequest(self)
+    }
 }
 
 impl Handler for UserRequest {
     fn endpoint() -> &'static str {
-        endpoint!("getGJUserInfo20")
+        "getGJUserInfo"
+    }
+
+    fn version() -> &'static str {
+        "20"
     }
 
     fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
@@ -155,7 +507,11 @@ impl Handler for UserRequest {
 
 impl Handler for UserSearchRequest {
     fn endpoint() -> &'static str {
-        endpoint!("getGJUsers20")
+        "getGJUsers"
+    }
+
+    fn version() -> &'static str {
+        "20"
     }
 
     fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
@@ -174,9 +530,96 @@ impl Handler for UserSearchRequest {
     }
 }
 
+impl Handler for LeaderboardRequest {
+    fn endpoint() -> &'static str {
+        "getGJScores"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        let mut entries = Vec::new();
+
+        // The response ends with a trailing `|`
+        for fragment in response_body.split('|').filter(|fragment| !fragment.is_empty()) {
+            entries.push(LeaderboardEntry::parse_str(fragment, ':')?)
+        }
+
+        info!("Found {} leaderboard entries", entries.len());
+
+        Ok(Response::Exact(entries))
+    }
+
+    fn to_req(&self) -> Req {
+        Req::LeaderboardRequest(self)
+    }
+
+    // The relative and friends leaderboards are computed relative to the account making the request
+    fn authenticated(&self) -> bool {
+        match self.leaderboard_type {
+            LeaderboardType::Relative(_) | LeaderboardType::Friends(_) => true,
+            LeaderboardType::Top | LeaderboardType::Creators => false,
+        }
+    }
+
+    fn account_id(&self) -> Option<u64> {
+        match self.leaderboard_type {
+            LeaderboardType::Relative(account_id) | LeaderboardType::Friends(account_id) => Some(account_id),
+            LeaderboardType::Top | LeaderboardType::Creators => None,
+        }
+    }
+}
+
+impl Handler for LevelScoresRequest {
+    fn endpoint() -> &'static str {
+        "getGJLevelScores"
+    }
+
+    fn version() -> &'static str {
+        "211"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        let mut scores = Vec::new();
+
+        for fragment in response_body.split('|').filter(|fragment| !fragment.is_empty()) {
+            scores.push(LevelScore::parse_str(fragment, ':')?)
+        }
+
+        info!("Found {} level scores", scores.len());
+
+        Ok(Response::Exact(scores))
+    }
+
+    fn to_req(&self) -> Req {
+        Req::LevelScoresRequest(self)
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+
+    fn account_id(&self) -> Option<u64> {
+        match self.leaderboard_type {
+            LevelScoresType::Friends(account_id) => Some(account_id),
+            LevelScoresType::Top | LevelScoresType::Week => None,
+        }
+    }
+}
+
 impl Handler for LevelCommentsRequest {
     fn endpoint() -> &'static str {
-        endpoint!("getGJComments21")
+        "getGJComments"
+    }
+
+    fn version() -> &'static str {
+        "21"
     }
 
     fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
@@ -222,7 +665,7 @@ impl Handler for LevelCommentsRequest {
 
                 info!("We got a total of {} comments!", comments.len());
 
-                Ok(Response::Exact(comments))
+                paginated(comments, sections.next())
             },
             None => Err(ApiError::UnexpectedFormat),
         }
@@ -235,7 +678,11 @@ impl Handler for LevelCommentsRequest {
 
 impl Handler for ProfileCommentsRequest {
     fn endpoint() -> &'static str {
-        endpoint!("getGJAccountComments20")
+        "getGJAccountComments"
+    }
+
+    fn version() -> &'static str {
+        "20"
     }
 
     fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
@@ -253,7 +700,7 @@ impl Handler for ProfileCommentsRequest {
 
                 info!("We got a total of {} comments!", comments.len());
 
-                Ok(Response::Exact(comments))
+                paginated(comments, sections.next())
             },
             None => Err(ApiError::UnexpectedFormat),
         }
@@ -263,3 +710,396 @@ impl Handler for ProfileCommentsRequest {
         Req::ProfileCommentsRequest(self)
     }
 }
+
+impl Handler for UploadLevelCommentRequest {
+    fn endpoint() -> &'static str {
+        "uploadGJComment"
+    }
+
+    fn version() -> &'static str {
+        "21"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        parse_comment_id(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::UploadLevelCommentRequest(self)
+    }
+
+    fn idempotent() -> bool {
+        false
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+
+    fn chk(&self, credentials: Option<&Credentials>) -> Option<Checksum> {
+        credentials.map(|credentials| {
+            Checksum::new(
+                &[
+                    &credentials.user_name,
+                    &b64_encode_string(&self.content),
+                    &self.level_id.to_string(),
+                    &self.progress.to_string(),
+                    "0",
+                ],
+                ser::COMMENT_SALT,
+                ser::COMMENT_KEY,
+            )
+        })
+    }
+}
+
+impl Handler for UploadProfileCommentRequest {
+    fn endpoint() -> &'static str {
+        "uploadGJAccComment"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        parse_comment_id(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::UploadProfileCommentRequest(self)
+    }
+
+    fn idempotent() -> bool {
+        false
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+
+    // Profile comments are checksummed like level comments on level `0`, with comment type `1`
+    fn chk(&self, credentials: Option<&Credentials>) -> Option<Checksum> {
+        credentials.map(|credentials| {
+            Checksum::new(
+                &[&credentials.user_name, &b64_encode_string(&self.content), "0", "0", "1"],
+                ser::COMMENT_SALT,
+                ser::COMMENT_KEY,
+            )
+        })
+    }
+}
+
+impl Handler for MessagesRequest {
+    fn endpoint() -> &'static str {
+        "getGJMessages"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        // Empty inboxes are reported as `-2`
+        if response_body == "-2" {
+            return Err(ApiError::NoData)
+        }
+
+        let mut sections = response_body.split('#');
+
+        match sections.next() {
+            Some(section) => {
+                let mut messages = Vec::new();
+
+                for fragment in section.split('|') {
+                    messages.push(Message::parse_str(fragment, ':')?)
+                }
+
+                info!("We got a total of {} messages!", messages.len());
+
+                paginated(messages, sections.next())
+            },
+            None => Err(ApiError::UnexpectedFormat),
+        }
+    }
+
+    fn to_req(&self) -> Req {
+        Req::MessagesRequest(self)
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
+
+impl Handler for DownloadMessageRequest {
+    fn endpoint() -> &'static str {
+        "downloadGJMessage"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        Ok(Response::Exact(Message::parse_str(response_body, ':')?))
+    }
+
+    fn to_req(&self) -> Req {
+        Req::DownloadMessageRequest(self)
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
+
+impl Handler for SendMessageRequest {
+    fn endpoint() -> &'static str {
+        "uploadGJMessage"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        parse_success(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::SendMessageRequest(self)
+    }
+
+    fn idempotent() -> bool {
+        false
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
+
+impl Handler for FriendListRequest {
+    fn endpoint() -> &'static str {
+        "getGJUserList"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        Ok(Response::Exact(user_list(response_body)?))
+    }
+
+    fn to_req(&self) -> Req {
+        Req::FriendListRequest(self)
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
+
+impl Handler for BlockedUsersRequest {
+    fn endpoint() -> &'static str {
+        "getGJUserList"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        Ok(Response::Exact(user_list(response_body)?))
+    }
+
+    fn to_req(&self) -> Req {
+        Req::BlockedUsersRequest(self)
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
+
+impl Handler for FriendRequestsRequest {
+    fn endpoint() -> &'static str {
+        "getGJFriendRequests"
+    }
+
+    fn version() -> &'static str {
+        "20"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        check_resp!(response_body);
+
+        // Accounts without pending friend requests get `-2`
+        if response_body == "-2" {
+            return Err(ApiError::NoData)
+        }
+
+        let mut sections = response_body.split('#');
+
+        match sections.next() {
+            Some(section) => {
+                let mut requests = Vec::new();
+
+                for fragment in section.split('|') {
+                    requests.push(FriendRequest::parse_str(fragment, ':')?)
+                }
+
+                info!("We got a total of {} friend requests!", requests.len());
+
+                paginated(requests, sections.next())
+            },
+            None => Err(ApiError::UnexpectedFormat),
+        }
+    }
+
+    fn to_req(&self) -> Req {
+        Req::FriendRequestsRequest(self)
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
+
+impl Handler for LikeItemRequest {
+    fn endpoint() -> &'static str {
+        "likeGJItem"
+    }
+
+    fn version() -> &'static str {
+        "211"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        parse_success(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::LikeItemRequest(self)
+    }
+
+    fn idempotent() -> bool {
+        false
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+
+    fn chk(&self, credentials: Option<&Credentials>) -> Option<Checksum> {
+        credentials.map(|credentials| {
+            Checksum::randomized(
+                &[
+                    &ser::like_special(self.item).to_string(),
+                    &self.item.item_id().to_string(),
+                    &ser::bool(self.like).to_string(),
+                    &ser::like_type(self.item).to_string(),
+                ],
+                credentials.account_id,
+                ser::LIKE_SALT,
+                ser::LIKE_KEY,
+            )
+        })
+    }
+}
+
+impl Handler for RateStarsRequest {
+    fn endpoint() -> &'static str {
+        "rateGJStars"
+    }
+
+    fn version() -> &'static str {
+        "211"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        parse_success(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::RateStarsRequest(self)
+    }
+
+    fn idempotent() -> bool {
+        false
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+
+    fn chk(&self, credentials: Option<&Credentials>) -> Option<Checksum> {
+        credentials.map(|credentials| {
+            Checksum::randomized(
+                &[&self.level_id.to_string(), &self.stars.to_string()],
+                credentials.account_id,
+                ser::LIKE_SALT,
+                ser::LIKE_KEY,
+            )
+        })
+    }
+}
+
+impl Handler for RateDemonRequest {
+    fn endpoint() -> &'static str {
+        "rateGJDemon"
+    }
+
+    fn version() -> &'static str {
+        "21"
+    }
+
+    // The servers respond with the ID of the rated level
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        parse_success(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::RateDemonRequest(self)
+    }
+
+    fn idempotent() -> bool {
+        false
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
+
+impl Handler for UploadLevelRequest {
+    fn endpoint() -> &'static str {
+        "uploadGJLevel"
+    }
+
+    fn version() -> &'static str {
+        "21"
+    }
+
+    fn handle(response_body: &str) -> Result<Response<Self::Result>, ApiError> {
+        parse_upload_id(response_body)
+    }
+
+    fn to_req(&self) -> Req {
+        Req::UploadLevelRequest(self)
+    }
+
+    fn idempotent() -> bool {
+        false
+    }
+
+    fn authenticated(&self) -> bool {
+        true
+    }
+}
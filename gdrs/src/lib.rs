@@ -10,46 +10,73 @@
     unused_parens
 )]
 
+pub use crate::{
+    builder::{BoomlingsClientBuilder, BOOMLINGS_BASE_URL, DEFAULT_BASE_URL},
+    retry::RetryPolicy,
+};
 use crate::{
+    builder::Endpoints,
     error::ApiError,
     handle::Handler,
-    ser::{LevelCommentsRequestRem, LevelRequestRem, LevelsRequestRem, ProfileCommentsRequestRem, UserRequestRem, UserSearchRequestRem},
+    limit::{Limited, Limiter},
+    retry::{ApiRetryCondition, Strategy, WithTimeout},
+    ser::{
+        Authenticated, BlockedUsersRequestRem, DailyLevelRequestRem, DownloadMessageRequestRem, FriendListRequestRem,
+        FriendRequestsRequestRem, GauntletsRequestRem, LeaderboardRequestRem, LevelCommentsRequestRem, LevelRequestRem,
+        LevelScoresRequestRem, LevelsRequestRem, LikeItemRequestRem, LoginRequestRem, MapPacksRequestRem, MessagesRequestRem,
+        ProfileCommentsRequestRem, RateDemonRequestRem, RateStarsRequestRem, SendMessageRequestRem, SongInfoRequestRem,
+        SpecialLevelRequestRem, UploadLevelCommentRequestRem, UploadLevelRequestRem, UploadProfileCommentRequestRem, UserRequestRem,
+        UserSearchRequestRem,
+    },
 };
 use failure::_core::marker::PhantomData;
 use futures::{
-    future::{Executor, FromErr},
+    future::{self, Either, Executor, FromErr, FutureResult},
     stream::Concat2,
     Async, Future, Stream,
 };
 use gdcf::api::{
     client::{MakeRequest, Response},
     request::{
-        comment::{LevelCommentsRequest, ProfileCommentsRequest},
-        level::{LevelRequest, LevelsRequest},
-        user::{UserRequest, UserSearchRequest},
+        account::{Credentials, LoginRequest},
+        comment::{LevelCommentsRequest, ProfileCommentsRequest, UploadLevelCommentRequest, UploadProfileCommentRequest},
+        level::{DailyLevelRequest, GauntletsRequest, LevelRequest, LevelsRequest, MapPacksRequest, SpecialLevelRequest, UploadLevelRequest},
+        message::{DownloadMessageRequest, MessagesRequest, SendMessageRequest},
+        rate::{LikeItemRequest, RateDemonRequest, RateStarsRequest},
+        song::SongInfoRequest,
+        user::{
+            BlockedUsersRequest, FriendListRequest, FriendRequestsRequest, LeaderboardRequest, LevelScoresRequest, UserRequest,
+            UserSearchRequest,
+        },
         Request as GdcfRequest,
     },
     ApiClient,
 };
 use hyper::{
-    client::{Builder, HttpConnector, ResponseFuture},
+    client::{HttpConnector, ResponseFuture},
     header::HeaderValue,
     Body, Client, Method, Request, StatusCode,
 };
-use log::{debug, error, info, trace, warn};
+use log::{debug, error, info, trace};
 use serde_derive::Serialize;
-use std::{iter::Take, mem, str};
-use tokio_retry::{strategy::ExponentialBackoff, Action, Condition, RetryIf};
+use std::{mem, str, sync::Arc};
+use tokio_retry::{Action, RetryIf};
 
 #[macro_use]
 mod macros;
+mod builder;
 pub mod error;
 pub mod handle;
+mod limit;
+mod retry;
 mod ser;
 
 #[derive(Serialize, Debug)]
 #[serde(untagged)]
 pub enum Req<'a> {
+    #[serde(with = "LoginRequestRem")]
+    LoginRequest(&'a LoginRequest),
+
     #[serde(with = "LevelRequestRem")]
     LevelRequest(&'a LevelRequest),
 
@@ -62,21 +89,85 @@ pub enum Req<'a> {
     #[serde(with = "UserSearchRequestRem")]
     UserSearchRequest(&'a UserSearchRequest),
 
+    #[serde(with = "LeaderboardRequestRem")]
+    LeaderboardRequest(&'a LeaderboardRequest),
+
+    #[serde(with = "LevelScoresRequestRem")]
+    LevelScoresRequest(&'a LevelScoresRequest),
+
     #[serde(with = "LevelCommentsRequestRem")]
     LevelCommentsRequest(&'a LevelCommentsRequest),
 
     #[serde(with = "ProfileCommentsRequestRem")]
     ProfileCommentsRequest(&'a ProfileCommentsRequest),
+
+    #[serde(with = "UploadLevelCommentRequestRem")]
+    UploadLevelCommentRequest(&'a UploadLevelCommentRequest),
+
+    #[serde(with = "UploadProfileCommentRequestRem")]
+    UploadProfileCommentRequest(&'a UploadProfileCommentRequest),
+
+    #[serde(with = "LikeItemRequestRem")]
+    LikeItemRequest(&'a LikeItemRequest),
+
+    #[serde(with = "RateStarsRequestRem")]
+    RateStarsRequest(&'a RateStarsRequest),
+
+    #[serde(with = "RateDemonRequestRem")]
+    RateDemonRequest(&'a RateDemonRequest),
+
+    #[serde(with = "MessagesRequestRem")]
+    MessagesRequest(&'a MessagesRequest),
+
+    #[serde(with = "DownloadMessageRequestRem")]
+    DownloadMessageRequest(&'a DownloadMessageRequest),
+
+    #[serde(with = "SendMessageRequestRem")]
+    SendMessageRequest(&'a SendMessageRequest),
+
+    #[serde(with = "FriendListRequestRem")]
+    FriendListRequest(&'a FriendListRequest),
+
+    #[serde(with = "BlockedUsersRequestRem")]
+    BlockedUsersRequest(&'a BlockedUsersRequest),
+
+    #[serde(with = "FriendRequestsRequestRem")]
+    FriendRequestsRequest(&'a FriendRequestsRequest),
+
+    #[serde(with = "DailyLevelRequestRem")]
+    DailyLevelRequest(&'a DailyLevelRequest),
+
+    #[serde(with = "SpecialLevelRequestRem")]
+    SpecialLevelRequest(&'a SpecialLevelRequest),
+
+    #[serde(with = "MapPacksRequestRem")]
+    MapPacksRequest(&'a MapPacksRequest),
+
+    #[serde(with = "GauntletsRequestRem")]
+    GauntletsRequest(&'a GauntletsRequest),
+
+    #[serde(with = "SongInfoRequestRem")]
+    SongInfoRequest(&'a SongInfoRequest),
+
+    #[serde(with = "UploadLevelRequestRem")]
+    UploadLevelRequest(&'a UploadLevelRequest),
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct BoomlingsClient {
     client: Client<HttpConnector>,
+    endpoints: Arc<Endpoints>,
+    retry_policy: RetryPolicy,
+    limiter: Arc<Limiter>,
+    credentials: Option<Arc<Credentials>>,
 }
 
 #[allow(missing_debug_implementations)]
 pub struct GdrsFuture<R: Handler> {
-    inner: FromErr<RetryIf<Take<ExponentialBackoff>, ApiRequestAction<R>, ApiRetryCondition>, ApiError>,
+    inner: Either<
+        WithTimeout<FromErr<RetryIf<Strategy, ApiRequestAction<R>, ApiRetryCondition>, ApiError>>,
+        FutureResult<Response<R::Result>, ApiError>,
+    >,
 }
 
 impl<R: Handler> Future for GdrsFuture<R> {
@@ -92,16 +183,34 @@ impl BoomlingsClient {
     pub fn new() -> BoomlingsClient {
         info!("Creating new BoomlingsApiClient");
 
-        BoomlingsClient { client: Client::new() }
+        BoomlingsClient::default()
+    }
+
+    pub fn builder() -> BoomlingsClientBuilder {
+        BoomlingsClientBuilder::default()
     }
 
     pub fn with_exec<E>(exec: E) -> Self
     where
         E: Executor<Box<dyn Future<Item = (), Error = ()> + Send>> + Send + Sync + 'static,
     {
-        let client = Builder::default().executor(exec).build_http();
+        BoomlingsClient::builder().executor(exec).build()
+    }
+
+    /// Makes this client perform all further authenticated requests on behalf of the account with
+    /// the given credentials
+    ///
+    /// The credentials of an account can be retrieved by making a [`LoginRequest`].
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        info!("Authenticating BoomlingsApiClient as {}", credentials);
 
-        BoomlingsClient { client }
+        self.credentials = Some(Arc::new(credentials));
+        self
+    }
+
+    /// The credentials of the account this client performs requests on behalf of, if any
+    pub fn credentials(&self) -> Option<&Credentials> {
+        self.credentials.as_ref().map(|credentials| &**credentials)
     }
 }
 
@@ -113,41 +222,63 @@ impl<R: Handler> MakeRequest<R> for BoomlingsClient {
     type Future = GdrsFuture<R>;
 
     fn make(&self, request: &R) -> GdrsFuture<R> {
+        let credentials = if request.authenticated() { self.credentials() } else { None };
+
+        if let (Some(expected), Some(credentials)) = (request.account_id(), credentials) {
+            if expected != credentials.account_id {
+                error!("Refusing to make request for account {} as account {}", expected, credentials.account_id);
+
+                return GdrsFuture {
+                    inner: Either::B(future::err(ApiError::WrongAccount {
+                        expected,
+                        actual: credentials.account_id,
+                    })),
+                }
+            }
+        }
+
+        // Retrying a write that failed after the servers processed it would perform it twice
+        let strategy = if R::idempotent() {
+            self.retry_policy.strategy()
+        } else {
+            RetryPolicy::never().strategy()
+        };
+
         GdrsFuture {
-            inner: RetryIf::spawn(
-                ExponentialBackoff::from_millis(10).take(5),
-                ApiRequestAction {
-                    client: self.client.clone(),
-                    encoded_request: serde_urlencoded::to_string(request.to_req()).unwrap(),
-                    phantom: PhantomData,
-                },
-                ApiRetryCondition,
-            )
-            .from_err(),
+            inner: Either::A(WithTimeout {
+                inner: RetryIf::spawn(
+                    strategy,
+                    ApiRequestAction {
+                        client: self.client.clone(),
+                        url: self.endpoints.url::<R>(),
+                        encoded_request: serde_urlencoded::to_string(Authenticated {
+                            credentials,
+                            request: request.to_req(),
+                            checksum: request.chk(credentials),
+                        })
+                        .unwrap(),
+                        retry_policy: self.retry_policy,
+                        limiter: Arc::clone(&self.limiter),
+                        phantom: PhantomData,
+                    },
+                    self.retry_policy.condition(),
+                )
+                .from_err(),
+                timeout: self.retry_policy.deadline_delay(),
+            }),
         }
     }
 }
 
 struct ApiRequestAction<R: Handler> {
     client: Client<HttpConnector>,
+    url: String,
     encoded_request: String,
+    retry_policy: RetryPolicy,
+    limiter: Arc<Limiter>,
     phantom: PhantomData<R>,
 }
 
-struct ApiRetryCondition;
-
-impl Condition<ApiError> for ApiRetryCondition {
-    fn should_retry(&mut self, error: &ApiError) -> bool {
-        match error {
-            ApiError::Custom(_) => {
-                warn!("Encountered retryable error: {:?}", error);
-                true
-            },
-            _ => false,
-        }
-    }
-}
-
 enum ProcessRequestFuture<R: Handler> {
     WaitingForResponse(ResponseFuture, PhantomData<R>),
     ProcessingResponse(Concat2<Body>),
@@ -218,23 +349,34 @@ impl<R: Handler> Future for ProcessRequestFuture<R> {
 
 impl<R: GdcfRequest + Handler> Action for ApiRequestAction<R> {
     type Error = ApiError;
-    type Future = ProcessRequestFuture<R>;
+    type Future = Limited<WithTimeout<ProcessRequestFuture<R>>>;
     type Item = Response<R::Result>;
 
     fn run(&mut self) -> Self::Future {
-        ProcessRequestFuture::WaitingForResponse(self.client.request(make_request::<R>(&self.encoded_request)), PhantomData)
+        let client = self.client.clone();
+        let request = make_request(&self.url, &self.encoded_request);
+        let retry_policy = self.retry_policy;
+
+        // The attempt timeout only starts once the request is actually sent, not while it is queued
+        Limited::new(self.limiter.acquire(R::endpoint()), move || {
+            WithTimeout {
+                inner: ProcessRequestFuture::WaitingForResponse(client.request(request), PhantomData),
+                timeout: retry_policy.attempt_timeout_delay(),
+            }
+        })
     }
 }
 
-fn make_request<R: GdcfRequest + Handler>(encoded_request: &str) -> Request<Body> {
+fn make_request(url: &str, encoded_request: &str) -> Request<Body> {
     let len = encoded_request.len();
 
-    info!("Preparing request {} to {}", encoded_request, R::endpoint());
+    info!("Preparing request to {}", url);
+    trace!("Request body: {}", redact(encoded_request));
 
     let mut req = Request::new(Body::from(encoded_request.to_string()));
 
     *req.method_mut() = Method::POST;
-    *req.uri_mut() = R::endpoint().parse().unwrap();
+    *req.uri_mut() = url.parse().unwrap();
     req.headers_mut()
         .insert("Content-Type", HeaderValue::from_str("application/x-www-form-urlencoded").unwrap());
     req.headers_mut()
@@ -242,3 +384,18 @@ fn make_request<R: GdcfRequest + Handler>(encoded_request: &str) -> Request<Body
 
     req
 }
+
+/// Replaces the values of all fields containing account passwords in the given url-encoded request
+/// body, so that it can be logged
+fn redact(encoded_request: &str) -> String {
+    encoded_request
+        .split('&')
+        .map(|field| {
+            match field.split('=').next() {
+                Some(name @ "gjp") | Some(name @ "password") => format!("{}=<redacted>", name),
+                _ => field.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
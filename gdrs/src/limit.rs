@@ -0,0 +1,305 @@
+use crate::error::ApiError;
+use futures::{
+    task::{self, Task},
+    try_ready, Async, Future,
+};
+use log::{debug, trace};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio_timer::Delay;
+
+/// Limits imposed on a set of requests
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Limit {
+    /// Allows at most `.0` requests per `.1`, in bursts of at most `.0` requests
+    pub(crate) rate: Option<(u32, Duration)>,
+
+    /// The maximum amount of requests waiting for a response at the same time
+    pub(crate) max_in_flight: Option<usize>,
+}
+
+/// The limits configured for a [`BoomlingsClient`](crate::BoomlingsClient)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Limits {
+    pub(crate) global: Limit,
+
+    /// Maps endpoint names to the limits imposed on requests to that endpoint, in addition to the
+    /// global ones
+    pub(crate) endpoints: HashMap<&'static str, Limit>,
+}
+
+/// Token bucket keeping track of the requests made under a single [`Limit`]
+#[derive(Debug)]
+struct Bucket {
+    limit: Limit,
+    tokens: f64,
+    refilled_at: Instant,
+    in_flight: usize,
+}
+
+/// Reason why a request cannot be made right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Wait {
+    /// The request can be made once enough tokens have been refilled at the given instant
+    Until(Instant),
+
+    /// The request can be made once some other request completes
+    InFlight,
+}
+
+impl Bucket {
+    fn new(limit: Limit) -> Self {
+        Bucket {
+            limit,
+            tokens: limit.rate.map(|(requests, _)| f64::from(requests)).unwrap_or(0.0),
+            refilled_at: Instant::now(),
+            in_flight: 0,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> Result<(), Wait> {
+        if let Some(max_in_flight) = self.limit.max_in_flight {
+            if self.in_flight >= max_in_flight {
+                return Err(Wait::InFlight)
+            }
+        }
+
+        if let Some((requests, per)) = self.limit.rate {
+            let token_duration = per / requests.max(1);
+            let elapsed = now - self.refilled_at;
+
+            self.tokens = (self.tokens + duration_ratio(elapsed, token_duration)).min(f64::from(requests));
+            self.refilled_at = now;
+
+            if self.tokens < 1.0 {
+                return Err(Wait::Until(now + token_duration.mul_f64(1.0 - self.tokens)))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn take(&mut self) {
+        if self.limit.rate.is_some() {
+            self.tokens -= 1.0;
+        }
+
+        self.in_flight += 1;
+    }
+
+    fn release(&mut self) {
+        self.in_flight -= 1;
+    }
+}
+
+fn duration_ratio(a: Duration, b: Duration) -> f64 {
+    a.as_secs_f64() / b.as_secs_f64()
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    global: Bucket,
+    endpoints: HashMap<&'static str, Bucket>,
+
+    /// Tasks waiting for a request to complete because some max-in-flight limit has been reached
+    waiting: Vec<Task>,
+}
+
+/// Enforces the [`Limits`] of a [`BoomlingsClient`](crate::BoomlingsClient) across all of its
+/// clones
+#[derive(Debug)]
+pub(crate) struct Limiter {
+    state: Mutex<LimiterState>,
+}
+
+impl Default for Limiter {
+    fn default() -> Self {
+        Limiter::new(Limits::default())
+    }
+}
+
+impl Limiter {
+    pub(crate) fn new(limits: Limits) -> Self {
+        Limiter {
+            state: Mutex::new(LimiterState {
+                global: Bucket::new(limits.global),
+                endpoints: limits
+                    .endpoints
+                    .into_iter()
+                    .map(|(endpoint, limit)| (endpoint, Bucket::new(limit)))
+                    .collect(),
+                waiting: Vec::new(),
+            }),
+        }
+    }
+
+    pub(crate) fn acquire(self: &Arc<Self>, endpoint: &'static str) -> Acquire {
+        Acquire {
+            limiter: Arc::clone(self),
+            endpoint,
+            delay: None,
+        }
+    }
+
+    fn try_acquire(&self, endpoint: &'static str) -> Result<(), Wait> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let now = Instant::now();
+
+        let global = state.global.check(now);
+        let local = match state.endpoints.get_mut(endpoint) {
+            Some(bucket) => bucket.check(now),
+            None => Ok(()),
+        };
+
+        match (global, local) {
+            (Ok(()), Ok(())) => {
+                state.global.take();
+
+                if let Some(bucket) = state.endpoints.get_mut(endpoint) {
+                    bucket.take()
+                }
+
+                Ok(())
+            },
+            (global, local) => {
+                // If we have to wait for a request to complete, refilled tokens don't help us
+                let wait = global.err().max(local.err()).unwrap();
+
+                if wait == Wait::InFlight {
+                    state.waiting.push(task::current())
+                }
+
+                Err(wait)
+            },
+        }
+    }
+
+    fn release(&self, endpoint: &'static str) {
+        let mut state = self.state.lock().unwrap();
+
+        state.global.release();
+
+        if let Some(bucket) = state.endpoints.get_mut(endpoint) {
+            bucket.release()
+        }
+
+        for task in state.waiting.drain(..) {
+            task.notify()
+        }
+    }
+}
+
+/// Future resolving to a [`Permit`] once a request to the given endpoint can be made without
+/// exceeding any limits
+#[derive(Debug)]
+pub(crate) struct Acquire {
+    limiter: Arc<Limiter>,
+    endpoint: &'static str,
+    delay: Option<Delay>,
+}
+
+impl Future for Acquire {
+    type Error = ApiError;
+    type Item = Permit;
+
+    fn poll(&mut self) -> Result<Async<Permit>, ApiError> {
+        loop {
+            if let Some(ref mut delay) = self.delay {
+                try_ready!(delay.poll());
+            }
+
+            self.delay = None;
+
+            match self.limiter.try_acquire(self.endpoint) {
+                Ok(()) => {
+                    trace!("Acquired permit for request to {}", self.endpoint);
+
+                    return Ok(Async::Ready(Permit {
+                        limiter: Arc::clone(&self.limiter),
+                        endpoint: self.endpoint,
+                    }))
+                },
+                Err(Wait::InFlight) => {
+                    debug!("Too many requests in flight, queueing request to {}", self.endpoint);
+
+                    return Ok(Async::NotReady)
+                },
+                Err(Wait::Until(instant)) => {
+                    debug!("Rate limit reached, delaying request to {}", self.endpoint);
+
+                    self.delay = Some(Delay::new(instant))
+                },
+            }
+        }
+    }
+}
+
+/// Proof that a request is allowed to be made. The request counts as in-flight until the permit is
+/// dropped.
+#[derive(Debug)]
+pub(crate) struct Permit {
+    limiter: Arc<Limiter>,
+    endpoint: &'static str,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.limiter.release(self.endpoint)
+    }
+}
+
+/// Future that only starts the future produced by `start` once a [`Permit`] has been acquired, and
+/// holds on to that permit until the started future completes
+#[allow(missing_debug_implementations)]
+pub(crate) struct Limited<F> {
+    acquire: Acquire,
+    start: Option<Box<dyn FnOnce() -> F + Send>>,
+    running: Option<(F, Permit)>,
+}
+
+impl<F> Limited<F> {
+    pub(crate) fn new(acquire: Acquire, start: impl FnOnce() -> F + Send + 'static) -> Self {
+        Limited {
+            acquire,
+            start: Some(Box::new(start)),
+            running: None,
+        }
+    }
+}
+
+impl<F: Future<Error = ApiError>> Future for Limited<F> {
+    type Error = ApiError;
+    type Item = F::Item;
+
+    fn poll(&mut self) -> Result<Async<F::Item>, ApiError> {
+        if let Some(start) = self.start.take() {
+            match self.acquire.poll() {
+                Ok(Async::Ready(permit)) => self.running = Some((start(), permit)),
+                Ok(Async::NotReady) => {
+                    self.start = Some(start);
+
+                    return Ok(Async::NotReady)
+                },
+                Err(err) => return Err(err),
+            }
+        }
+
+        let result = match self.running {
+            Some((ref mut future, _)) => future.poll(),
+            None => panic!("Limited future polled after completion"),
+        };
+
+        if let Ok(Async::NotReady) = result {
+            return result
+        }
+
+        // Release the permit right away instead of when the future is dropped
+        self.running = None;
+
+        result
+    }
+}
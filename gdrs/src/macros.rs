@@ -1,9 +1,3 @@
-macro_rules! endpoint {
-    ($php:expr) => {
-        concat!("http://absolllute.com/gdps/gdapi/", $php, ".php")
-    };
-}
-
 macro_rules! check_resp {
     ($data:expr) => {{
         if $data == "-1" {
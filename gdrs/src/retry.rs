@@ -0,0 +1,158 @@
+use crate::error::ApiError;
+use futures::{Async, Future};
+use log::warn;
+use std::{
+    iter::{Map, Take},
+    time::{Duration, Instant},
+};
+use tokio_retry::{
+    strategy::{jitter, ExponentialBackoff},
+    Condition,
+};
+use tokio_timer::Delay;
+
+pub(crate) type Strategy = Take<Map<ExponentialBackoff, fn(Duration) -> Duration>>;
+
+/// Policy determining if, when and how often a [`BoomlingsClient`](crate::BoomlingsClient) retries
+/// failed requests, and how long it waits for them to complete
+///
+/// Requests writing data to the servers are never retried, since they might have been processed
+/// even though they failed. The timeouts still apply to them.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    backoff_base: u64,
+    max_attempts: usize,
+    jitter: bool,
+    attempt_timeout: Option<Duration>,
+    deadline: Option<Duration>,
+    retry_if: fn(&ApiError) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            backoff_base: 10,
+            max_attempts: 6,
+            jitter: false,
+            attempt_timeout: None,
+            deadline: None,
+            retry_if: retry_connection_errors,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries anything
+    pub fn never() -> Self {
+        RetryPolicy::default().max_attempts(1)
+    }
+
+    /// Sets the base of the exponential backoff, in milliseconds. The `n`-th retry is delayed by
+    /// `base^n` milliseconds
+    pub fn backoff_base(mut self, backoff_base: u64) -> Self {
+        self.backoff_base = backoff_base;
+        self
+    }
+
+    /// Sets how often a request is attempted at most, including the initial attempt
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets whether the delays between retries should be randomized
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets how long a single attempt may take before it fails with [`ApiError::Timeout`]
+    pub fn attempt_timeout(mut self, timeout: Duration) -> Self {
+        self.attempt_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long a request may take in total, including all retries, before it fails with
+    /// [`ApiError::Timeout`]
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the function deciding which errors are worth retrying
+    ///
+    /// By default, connection errors and timeouts are retried
+    pub fn retry_if(mut self, retry_if: fn(&ApiError) -> bool) -> Self {
+        self.retry_if = retry_if;
+        self
+    }
+
+    pub(crate) fn strategy(&self) -> Strategy {
+        let map: fn(Duration) -> Duration = if self.jitter { jitter } else { std::convert::identity };
+
+        ExponentialBackoff::from_millis(self.backoff_base)
+            .map(map)
+            .take(self.max_attempts.saturating_sub(1))
+    }
+
+    pub(crate) fn condition(&self) -> ApiRetryCondition {
+        ApiRetryCondition(self.retry_if)
+    }
+
+    pub(crate) fn attempt_timeout_delay(&self) -> Option<Delay> {
+        self.attempt_timeout.map(|timeout| Delay::new(Instant::now() + timeout))
+    }
+
+    pub(crate) fn deadline_delay(&self) -> Option<Delay> {
+        self.deadline.map(|deadline| Delay::new(Instant::now() + deadline))
+    }
+}
+
+fn retry_connection_errors(error: &ApiError) -> bool {
+    match error {
+        ApiError::Custom(_) | ApiError::Timeout => true,
+        _ => false,
+    }
+}
+
+pub(crate) struct ApiRetryCondition(fn(&ApiError) -> bool);
+
+impl Condition<ApiError> for ApiRetryCondition {
+    fn should_retry(&mut self, error: &ApiError) -> bool {
+        if (self.0)(error) {
+            warn!("Encountered retryable error: {:?}", error);
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Future that fails with [`ApiError::Timeout`] if the wrapped future doesn't complete before the
+/// given delay elapses
+pub(crate) struct WithTimeout<F> {
+    pub(crate) inner: F,
+    pub(crate) timeout: Option<Delay>,
+}
+
+impl<F: Future<Error = ApiError>> Future for WithTimeout<F> {
+    type Error = ApiError;
+    type Item = F::Item;
+
+    fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
+        if let Async::Ready(item) = self.inner.poll()? {
+            return Ok(Async::Ready(item))
+        }
+
+        if let Some(ref mut timeout) = self.timeout {
+            if let Async::Ready(()) = timeout.poll()? {
+                warn!("Request timed out");
+
+                return Err(ApiError::Timeout)
+            }
+        }
+
+        Ok(Async::NotReady)
+    }
+}
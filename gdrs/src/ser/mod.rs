@@ -1,23 +1,188 @@
 pub use self::request::{
-    comment::{LevelCommentsRequestRem, ProfileCommentsRequestRem},
-    level::{LevelRequestRem, LevelsRequestRem},
-    user::{UserRequestRem, UserSearchRequestRem},
-    BaseRequestRem,
+    account::{CredentialsRem, LoginRequestRem},
+    comment::{LevelCommentsRequestRem, ProfileCommentsRequestRem, UploadLevelCommentRequestRem, UploadProfileCommentRequestRem},
+    message::{DownloadMessageRequestRem, MessagesRequestRem, SendMessageRequestRem},
+    rate::{LikeItemRequestRem, RateDemonRequestRem, RateStarsRequestRem},
+    song::SongInfoRequestRem,
+    level::{
+        DailyLevelRequestRem, GauntletsRequestRem, LevelRequestRem, LevelsRequestRem, MapPacksRequestRem, SpecialLevelRequestRem,
+        UploadLevelRequestRem,
+    },
+    user::{
+        BlockedUsersRequestRem, FriendListRequestRem, FriendRequestsRequestRem, LeaderboardRequestRem, LevelScoresRequestRem,
+        UserRequestRem, UserSearchRequestRem,
+    },
 };
+use crate::Req;
 use gdcf::api::request::{
+    account::Credentials,
     comment::SortMode,
     level::{CompletionFilter, LevelRequestType, SearchFilters, SongFilter},
+    rate::LikedItem,
+    user::{LeaderboardType, LevelScoresType},
 };
 use gdcf_model::{
-    level::{DemonRating, LevelLength, LevelRating},
+    level::{DemonRating, LevelLength, LevelRating, Password},
     GameVersion,
 };
-use gdcf_parse::convert::RobtopInto;
+use gdcf_parse::{
+    convert::RobtopInto,
+    util::{b64_encode_string, encode_message_body, xor_decrypt},
+};
 use joinery::Joinable;
 use serde::{ser::SerializeMap, Serializer};
+use serde_derive::Serialize;
+use rand::Rng;
+use sha1::Sha1;
 
 mod request;
 
+/// A request, together with the credentials of the account it is made on behalf of, if any
+#[derive(Serialize)]
+pub(crate) struct Authenticated<'a> {
+    #[serde(flatten, serialize_with = "credentials")]
+    pub(crate) credentials: Option<&'a Credentials>,
+
+    #[serde(flatten)]
+    pub(crate) request: Req<'a>,
+
+    #[serde(flatten)]
+    pub(crate) checksum: Option<Checksum>,
+}
+
+/// The salt appended to the values a comment upload's `chk` is computed from
+pub const COMMENT_SALT: &str = "xPT6iUrtws0J";
+
+/// The key a comment upload's `chk` is XOR-encrypted with
+pub const COMMENT_KEY: &str = "29481";
+
+/// The salt appended to the values the `chk` of likes and ratings is computed from
+pub const LIKE_SALT: &str = "ysg6pUrtjn0J";
+
+/// The key the `chk` of likes and ratings is XOR-encrypted with
+pub const LIKE_KEY: &str = "58281";
+
+/// The salt appended to the sampled level string a level upload's `seed2` is computed from
+pub const LEVEL_SALT: &str = "xI25fpAapCQg";
+
+/// The key a level upload's `seed2` is XOR-encrypted with
+pub const LEVEL_KEY: &str = "41274";
+
+/// The `chk` checksum of a request, together with the random values that went into it and have
+/// to be sent along with it
+#[derive(Debug, Serialize)]
+pub struct Checksum {
+    /// A random salt of 10 alphanumeric characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rs: Option<String>,
+
+    /// The ID of the device the request is made from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    udid: Option<String>,
+
+    /// The ID of the player the request is made by
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<String>,
+
+    chk: String,
+}
+
+impl Checksum {
+    /// Computes the checksum of the given values, see [`chk`]
+    pub fn new(values: &[&str], salt: &str, key: &str) -> Self {
+        Checksum {
+            rs: None,
+            udid: None,
+            uuid: None,
+            chk: chk(values, salt, key),
+        }
+    }
+
+    /// Computes the checksum of the given values, followed by a random salt, the given account ID
+    /// and random device and player IDs, as required by the endpoints for likes and ratings
+    pub fn randomized(values: &[&str], account_id: u64, salt: &str, key: &str) -> Self {
+        let mut rng = rand::thread_rng();
+
+        let rs: String = rng.gen_ascii_chars().take(10).collect();
+        let udid = format!("S{}", rng.gen_range(1_000_000_000u64, 10_000_000_000u64));
+        let uuid = rng.gen_range(100_000u64, 100_000_000u64).to_string();
+
+        let mut values = values.to_vec();
+        let account_id = account_id.to_string();
+
+        values.extend_from_slice(&[&rs, &account_id, &udid, &uuid]);
+
+        Checksum {
+            chk: chk(&values, salt, key),
+            rs: Some(rs),
+            udid: Some(udid),
+            uuid: Some(uuid),
+        }
+    }
+}
+
+/// Samples 50 bytes, evenly spaced across the given level string, from which the `seed2` checksum
+/// of a level upload is computed. Level strings shorter than that are used as is.
+///
+/// Level strings are base64 encoded, so sampling bytes never splits a character.
+pub fn level_seed(level_string: &str) -> String {
+    const SAMPLES: usize = 50;
+
+    if level_string.len() < SAMPLES {
+        return level_string.to_string()
+    }
+
+    level_string.bytes().step_by(level_string.len() / SAMPLES).take(SAMPLES).map(char::from).collect()
+}
+
+/// GJP-encodes the given password, by XOR-encrypting it with key `37526` and base64-encoding the
+/// result
+pub fn gjp_encode(password: &str) -> String {
+    b64_encode_string(&xor_decrypt(password, "37526"))
+}
+
+/// Computes the `chk` checksum of a request by taking the SHA1 hash of the concatenation of the
+/// given values and the salt, then XOR-encrypting the hex digest with `key` and base64-encoding the
+/// result
+pub fn chk(values: &[&str], salt: &str, key: &str) -> String {
+    let mut sha1 = Sha1::new();
+
+    for value in values {
+        sha1.update(value.as_bytes());
+    }
+
+    sha1.update(salt.as_bytes());
+
+    b64_encode_string(&xor_decrypt(&sha1.digest().to_string(), key))
+}
+
+/// The value of the `type` field of a like request for the given item
+pub(crate) fn like_type(item: LikedItem) -> u8 {
+    match item {
+        LikedItem::Level(_) => 1,
+        LikedItem::LevelComment { .. } => 2,
+        LikedItem::ProfileComment { .. } => 3,
+    }
+}
+
+/// The value of the `special` field of a like request for the given item
+pub(crate) fn like_special(item: LikedItem) -> u64 {
+    match item {
+        LikedItem::Level(_) => 0,
+        LikedItem::LevelComment { level_id, .. } => level_id,
+        LikedItem::ProfileComment { account_id, .. } => account_id,
+    }
+}
+
+/// The unencrypted form of the given password, which is what level uploads expect
+pub(crate) fn level_password(password: &Password) -> String {
+    match password {
+        Password::NoCopy => "0".to_string(),
+        Password::FreeCopy => "1".to_string(),
+        Password::PasswordCopy(password) => format!("1{}", password),
+    }
+}
+
 /// Converts the given [`Vec`] of values convertible into signed integers
 /// into a robtop-approved string.
 pub fn vec<T: RobtopInto<T, String> + Copy>(list: &[T]) -> String {
@@ -50,6 +215,39 @@ where
     serializer.serialize_str(&version.robtop_into())
 }
 
+pub(super) fn base64<S>(plain: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&b64_encode_string(plain))
+}
+
+/// Serializes the body of a private message, which is XOR-encrypted with key `14251` before being
+/// base64 encoded
+pub(super) fn message_body<S>(body: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&encode_message_body(body))
+}
+
+pub(super) fn gjp<S>(password: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&gjp_encode(password))
+}
+
+fn credentials<S>(credentials: &Option<&Credentials>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match credentials {
+        Some(credentials) => CredentialsRem::serialize(credentials, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 pub(super) fn bool_to_int<S>(value: &bool, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -57,6 +255,14 @@ where
     serializer.serialize_u8(*value as u8)
 }
 
+/// The servers refer to the daily level as level `-1`, and to the weekly demon as level `-2`
+pub(super) fn special_level_id<S>(weekly: &bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_i8(-1 - *weekly as i8)
+}
+
 pub(super) fn length_vec<S>(values: &[LevelLength], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -71,6 +277,13 @@ where
     serializer.serialize_str(&vec(values))
 }
 
+pub(super) fn account_list<S>(account_ids: &[u64], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&account_ids.iter().join_with(",").to_string())
+}
+
 pub(super) fn demon_rating<S>(rating: &Option<DemonRating>, serialize: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -78,6 +291,13 @@ where
     serialize.serialize_str(&rating.unwrap().robtop_into_req())
 }
 
+pub(super) fn demon<S>(rating: &DemonRating, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&rating.robtop_into_req())
+}
+
 pub(super) fn req_type<S>(req_type: &LevelRequestType, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -95,6 +315,25 @@ where
     }
 }
 
+pub(super) fn leaderboard_type<S>(leaderboard_type: &LeaderboardType, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match leaderboard_type {
+        LeaderboardType::Top => serializer.serialize_str("top"),
+        LeaderboardType::Creators => serializer.serialize_str("creators"),
+        LeaderboardType::Relative(_) => serializer.serialize_str("relative"),
+        LeaderboardType::Friends(_) => serializer.serialize_str("friends"),
+    }
+}
+
+pub(super) fn level_scores_type<S>(score_type: &LevelScoresType, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_i32(i32::from(*score_type))
+}
+
 pub(super) fn search_filters<S>(filters: &SearchFilters, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -136,3 +375,48 @@ where
 
     map.end()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{b64_encode_string, chk, gjp_encode, level_seed, COMMENT_KEY, COMMENT_SALT, LEVEL_KEY, LEVEL_SALT};
+
+    /// A level string of `len` bytes cycling through the alphabet
+    fn level_string(len: usize) -> String {
+        (0..len).map(|i| char::from(b'a' + (i % 26) as u8)).collect()
+    }
+
+    #[test]
+    fn gjp_known_value() {
+        assert_eq!(gjp_encode("hunter2"), "W0JbRlNBBQ==");
+    }
+
+    #[test]
+    fn comment_chk_known_value() {
+        let content = b64_encode_string("Hello");
+
+        assert_eq!(
+            chk(&["Alice", &content, "1", "0", "0"], COMMENT_SALT, COMMENT_KEY),
+            "Cg0NWgcGAQIKBgJaBw1QBQEFWwkFX1VeAVAJUQwECgxXCFJRDVIAAw=="
+        );
+    }
+
+    #[test]
+    fn level_seed_of_short_level_string() {
+        assert_eq!(level_seed(""), "");
+        assert_eq!(level_seed(&level_string(49)), level_string(49));
+    }
+
+    #[test]
+    fn level_seed_of_multiple_of_sample_count() {
+        assert_eq!(level_seed(&level_string(50)), level_string(50));
+        assert_eq!(level_seed(&level_string(100)), "acegikmoqsuwyacegikmoqsuwyacegikmoqsuwyacegikmoqsu");
+    }
+
+    #[test]
+    fn seed2_known_value() {
+        let seed = level_seed(&level_string(130));
+
+        assert_eq!(seed, "acegikmoqsuwyacegikmoqsuwyacegikmoqsuwyacegikmoqsu");
+        assert_eq!(chk(&[&seed], LEVEL_SALT, LEVEL_KEY), "AlMLBlYGBFYBDVYEAg4ABVIAA1VXBQJUAgUEV1UGAQdXAVIGBVdTAA==");
+    }
+}
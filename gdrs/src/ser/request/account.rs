@@ -0,0 +1,32 @@
+use super::BaseRequestRem;
+use crate::ser;
+use gdcf::api::request::{
+    account::{Credentials, LoginRequest},
+    BaseRequest,
+};
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+#[serde(remote = "Credentials")]
+pub struct CredentialsRem {
+    #[serde(rename = "accountID")]
+    account_id: u64,
+
+    #[serde(rename = "userName")]
+    user_name: String,
+
+    #[serde(rename = "gjp", serialize_with = "ser::gjp")]
+    password: String,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "LoginRequest")]
+pub struct LoginRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    base: BaseRequest,
+
+    #[serde(rename = "userName")]
+    user_name: String,
+
+    password: String,
+}
@@ -1,9 +1,10 @@
 use super::BaseRequestRem;
 use crate::ser;
 use gdcf::api::request::{
-    comment::{LevelCommentsRequest, ProfileCommentsRequest, SortMode},
+    comment::{LevelCommentsRequest, ProfileCommentsRequest, SortMode, UploadLevelCommentRequest, UploadProfileCommentRequest},
     BaseRequest,
 };
+use serde::{Serialize, Serializer};
 use serde_derive::Serialize;
 
 #[derive(Serialize)]
@@ -39,3 +40,49 @@ pub struct ProfileCommentsRequestRem {
     #[serde(rename = "accountID")]
     pub account_id: u64,
 }
+
+#[derive(Serialize)]
+#[serde(remote = "UploadLevelCommentRequest")]
+pub struct UploadLevelCommentRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    pub base: BaseRequest,
+
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+
+    #[serde(rename = "comment", serialize_with = "ser::base64")]
+    pub content: String,
+
+    #[serde(rename = "percent")]
+    pub progress: u8,
+}
+
+/// `uploadGJAccComment` expects a constant `cType` field without a counterpart in
+/// [`UploadProfileCommentRequest`], so we cannot derive a remote implementation here
+pub struct UploadProfileCommentRequestRem;
+
+impl UploadProfileCommentRequestRem {
+    pub fn serialize<S>(request: &UploadProfileCommentRequest, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Fields<'a> {
+            #[serde(flatten, with = "BaseRequestRem")]
+            base: BaseRequest,
+
+            #[serde(serialize_with = "ser::base64")]
+            comment: &'a str,
+
+            #[serde(rename = "cType")]
+            comment_type: u8,
+        }
+
+        Fields {
+            base: request.base,
+            comment: &request.content,
+            comment_type: 1,
+        }
+        .serialize(serializer)
+    }
+}
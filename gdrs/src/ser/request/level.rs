@@ -1,10 +1,16 @@
 use super::BaseRequestRem;
 use crate::ser;
 use gdcf::api::request::{
-    level::{LevelRequestType, LevelsRequest, SearchFilters},
+    level::{
+        DailyLevelRequest, GauntletsRequest, LevelRequestType, LevelsRequest, MapPacksRequest, SearchFilters, SpecialLevelRequest,
+        UploadLevelRequest,
+    },
     BaseRequest, LevelRequest,
 };
 use gdcf_model::level::{DemonRating, LevelLength, LevelRating};
+use gdcf_parse::Parse;
+use rand::Rng;
+use serde::{Serialize, Serializer};
 use serde_derive::Serialize;
 
 #[derive(Serialize)]
@@ -23,6 +29,48 @@ pub struct LevelRequestRem {
     extra: bool,
 }
 
+#[derive(Serialize)]
+#[serde(remote = "SpecialLevelRequest")]
+pub struct SpecialLevelRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    base: BaseRequest,
+
+    #[serde(serialize_with = "ser::special_level_id", rename = "levelID")]
+    weekly: bool,
+
+    #[serde(serialize_with = "ser::bool_to_int")]
+    inc: bool,
+
+    #[serde(serialize_with = "ser::bool_to_int", rename = "extras")]
+    extra: bool,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "DailyLevelRequest")]
+pub struct DailyLevelRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    base: BaseRequest,
+
+    #[serde(serialize_with = "ser::bool_to_int")]
+    weekly: bool,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "MapPacksRequest")]
+pub struct MapPacksRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    base: BaseRequest,
+
+    page: u32,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "GauntletsRequest")]
+pub struct GauntletsRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    base: BaseRequest,
+}
+
 #[derive(Debug, Default, Serialize)]
 #[serde(remote = "LevelsRequest")]
 pub struct LevelsRequestRem {
@@ -54,4 +102,111 @@ pub struct LevelsRequestRem {
 
     #[serde(flatten, serialize_with = "ser::search_filters")]
     pub search_filters: SearchFilters,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", serialize_with = "ser::account_list")]
+    pub followed: Vec<u64>,
+}
+
+/// `uploadGJLevel` expects most values of the level in the same format the servers provide them
+/// in, but under different names and along with values derived from several of its fields, so we
+/// cannot derive a remote implementation here. Instead, we unparse the level and pick the values
+/// from the resulting index map.
+pub struct UploadLevelRequestRem;
+
+impl UploadLevelRequestRem {
+    pub fn serialize<S>(request: &UploadLevelRequest, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Fields<'a> {
+            #[serde(flatten, with = "BaseRequestRem")]
+            base: BaseRequest,
+
+            #[serde(rename = "levelID")]
+            level_id: u64,
+
+            #[serde(rename = "levelName")]
+            name: &'a str,
+
+            #[serde(rename = "levelDesc")]
+            description: &'a str,
+
+            #[serde(rename = "levelVersion")]
+            version: &'a str,
+
+            #[serde(rename = "levelLength")]
+            length: &'a str,
+
+            #[serde(rename = "audioTrack")]
+            main_song: &'a str,
+
+            #[serde(rename = "songID")]
+            custom_song: &'a str,
+
+            #[serde(serialize_with = "ser::bool_to_int")]
+            auto: bool,
+
+            password: String,
+
+            original: &'a str,
+
+            #[serde(rename = "twoPlayer")]
+            two_player: &'a str,
+
+            objects: &'a str,
+
+            coins: &'a str,
+
+            #[serde(rename = "requestedStars")]
+            stars_requested: &'a str,
+
+            #[serde(serialize_with = "ser::bool_to_int")]
+            unlisted: bool,
+
+            #[serde(rename = "extraString")]
+            extra_string: &'a str,
+
+            #[serde(rename = "levelString")]
+            level_string: &'a str,
+
+            seed: String,
+
+            seed2: String,
+        }
+
+        let level = &request.level;
+        let unparsed = level.clone().unparse();
+
+        let raw = |index: &str| unparsed.get(index).map(String::as_str).unwrap_or_default();
+
+        // Numeric values that are omitted, or empty because their default is an empty string, are
+        // sent as `0`
+        let value = |index: &str| Some(raw(index)).filter(|value| !value.is_empty()).unwrap_or("0");
+        let level_string = raw("4");
+
+        Fields {
+            base: request.base,
+            level_id: level.base.level_id,
+            name: &level.base.name,
+            description: raw("3"),
+            version: value("5"),
+            length: value("15"),
+            main_song: value("12"),
+            custom_song: value("35"),
+            auto: level.base.is_auto(),
+            password: ser::level_password(&level.password),
+            original: value("30"),
+            two_player: value("31"),
+            objects: value("45"),
+            coins: value("37"),
+            stars_requested: value("39"),
+            unlisted: request.unlisted,
+            extra_string: raw("36"),
+            level_string,
+            seed: rand::thread_rng().gen_ascii_chars().take(10).collect(),
+            seed2: ser::chk(&[&ser::level_seed(level_string)], ser::LEVEL_SALT, ser::LEVEL_KEY),
+        }
+        .serialize(serializer)
+    }
 }
@@ -0,0 +1,56 @@
+use super::BaseRequestRem;
+use crate::ser;
+use gdcf::api::request::{
+    message::{DownloadMessageRequest, MessagesRequest, SendMessageRequest},
+    BaseRequest,
+};
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+#[serde(remote = "MessagesRequest")]
+pub struct MessagesRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    pub base: BaseRequest,
+
+    #[serde(skip)]
+    pub account_id: u64,
+
+    pub total: u32,
+
+    pub page: u32,
+
+    #[serde(rename = "getSent", serialize_with = "ser::bool_to_int")]
+    pub sent: bool,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "DownloadMessageRequest")]
+pub struct DownloadMessageRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    pub base: BaseRequest,
+
+    #[serde(rename = "messageID")]
+    pub message_id: u64,
+
+    #[serde(rename = "isSender", serialize_with = "ser::bool_to_int")]
+    pub is_sender: bool,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "SendMessageRequest")]
+pub struct SendMessageRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    pub base: BaseRequest,
+
+    #[serde(skip)]
+    pub account_id: u64,
+
+    #[serde(rename = "toAccountID")]
+    pub recipient_id: u64,
+
+    #[serde(serialize_with = "ser::base64")]
+    pub subject: String,
+
+    #[serde(serialize_with = "ser::message_body")]
+    pub body: String,
+}
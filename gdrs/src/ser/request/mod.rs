@@ -3,8 +3,12 @@ use gdcf::api::request::BaseRequest;
 use gdcf_model::GameVersion;
 use serde_derive::Serialize;
 
+pub(super) mod account;
 pub(super) mod comment;
 pub(super) mod level;
+pub(super) mod message;
+pub(super) mod rate;
+pub(super) mod song;
 pub(super) mod user;
 
 #[derive(Serialize)]
@@ -0,0 +1,71 @@
+use super::BaseRequestRem;
+use crate::ser;
+use gdcf::api::request::{
+    rate::{LikeItemRequest, RateDemonRequest, RateStarsRequest},
+    BaseRequest,
+};
+use gdcf_model::level::DemonRating;
+use serde::{Serialize, Serializer};
+use serde_derive::Serialize;
+
+/// The ID of the liked item and the `type` and `special` values describing it are all derived from
+/// the [`LikedItem`](gdcf::api::request::LikedItem), so we cannot derive a remote implementation here
+pub struct LikeItemRequestRem;
+
+impl LikeItemRequestRem {
+    pub fn serialize<S>(request: &LikeItemRequest, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Fields {
+            #[serde(flatten, with = "BaseRequestRem")]
+            base: BaseRequest,
+
+            #[serde(rename = "itemID")]
+            item_id: u64,
+
+            #[serde(serialize_with = "ser::bool_to_int")]
+            like: bool,
+
+            #[serde(rename = "type")]
+            item_type: u8,
+
+            special: u64,
+        }
+
+        Fields {
+            base: request.base,
+            item_id: request.item.item_id(),
+            like: request.like,
+            item_type: ser::like_type(request.item),
+            special: ser::like_special(request.item),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(remote = "RateStarsRequest")]
+pub struct RateStarsRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    pub base: BaseRequest,
+
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+
+    pub stars: u8,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "RateDemonRequest")]
+pub struct RateDemonRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    pub base: BaseRequest,
+
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+
+    #[serde(serialize_with = "ser::demon")]
+    pub rating: DemonRating,
+}
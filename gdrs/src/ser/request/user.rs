@@ -1,8 +1,13 @@
 use super::BaseRequestRem;
+use crate::ser;
 use gdcf::api::request::{
-    user::{UserRequest, UserSearchRequest},
+    user::{
+        BlockedUsersRequest, FriendListRequest, FriendRequestsRequest, LeaderboardRequest, LeaderboardType, LevelScoresRequest,
+        LevelScoresType, UserRequest, UserSearchRequest,
+    },
     BaseRequest,
 };
+use serde::{Serialize, Serializer};
 use serde_derive::Serialize;
 
 #[derive(Serialize)]
@@ -28,3 +33,86 @@ pub struct UserSearchRequestRem {
     #[serde(rename = "str")]
     search_string: String,
 }
+
+#[derive(Serialize)]
+#[serde(remote = "LeaderboardRequest")]
+pub struct LeaderboardRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    base: BaseRequest,
+
+    #[serde(rename = "type", serialize_with = "ser::leaderboard_type")]
+    leaderboard_type: LeaderboardType,
+
+    count: u32,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "LevelScoresRequest")]
+pub struct LevelScoresRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    base: BaseRequest,
+
+    #[serde(rename = "levelID")]
+    level_id: u64,
+
+    #[serde(rename = "type", serialize_with = "ser::level_scores_type")]
+    leaderboard_type: LevelScoresType,
+}
+
+/// Friend lists and lists of blocked users are retrieved from the same endpoint, distinguished only
+/// by a `type` value that isn't part of either request, so we cannot derive remote implementations
+/// for them
+fn user_list<S>(base: BaseRequest, list_type: u8, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    #[derive(Serialize)]
+    struct Fields {
+        #[serde(flatten, with = "BaseRequestRem")]
+        base: BaseRequest,
+
+        #[serde(rename = "type")]
+        list_type: u8,
+    }
+
+    Fields { base, list_type }.serialize(serializer)
+}
+
+pub struct FriendListRequestRem;
+
+impl FriendListRequestRem {
+    pub fn serialize<S>(request: &FriendListRequest, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        user_list(request.base, 0, serializer)
+    }
+}
+
+pub struct BlockedUsersRequestRem;
+
+impl BlockedUsersRequestRem {
+    pub fn serialize<S>(request: &BlockedUsersRequest, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        user_list(request.base, 1, serializer)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(remote = "FriendRequestsRequest")]
+pub struct FriendRequestsRequestRem {
+    #[serde(flatten, with = "BaseRequestRem")]
+    pub base: BaseRequest,
+
+    #[serde(skip)]
+    pub account_id: u64,
+
+    pub total: u32,
+
+    pub page: u32,
+
+    #[serde(rename = "getSent", serialize_with = "ser::bool_to_int")]
+    pub outgoing: bool,
+}